@@ -0,0 +1,575 @@
+use std::fmt;
+
+use rand::rngs::SmallRng;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+use crate::controller::PlayerController;
+use crate::game::{Card, CardType, Color, Move};
+use crate::montecarlo::{MonteCarloBot, MonteCarloConfig};
+use crate::strategy;
+use crate::view::{BotObservation, PlayerView};
+
+const COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+
+// Picks uniformly among the legal moves, colors and swap targets. The reference
+// opponent that smarter bots are measured against. Give it a seeded RNG to make its
+// games reproducible
+#[derive(Debug, Clone)]
+pub struct RandomBot<R: Rng = SmallRng>
+{
+    rng: R
+}
+
+impl RandomBot
+{
+    pub fn new() -> RandomBot
+    {
+        RandomBot { rng: SmallRng::from_entropy() }
+    }
+
+    pub fn seeded(seed: u64) -> RandomBot
+    {
+        RandomBot { rng: SmallRng::seed_from_u64(seed) }
+    }
+}
+
+impl Default for RandomBot
+{
+    fn default() -> Self
+    {
+        RandomBot::new()
+    }
+}
+
+impl<R: Rng> RandomBot<R>
+{
+    pub fn with_rng(rng: R) -> RandomBot<R>
+    {
+        RandomBot { rng }
+    }
+}
+
+impl<R: Rng + Clone + 'static> PlayerController for RandomBot<R>
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let legal = &observation.legal_moves[..];
+        *legal.choose(&mut self.rng).expect("There is always a legal move on the bot's turn")
+    }
+
+    fn choose_color(&mut self, _view: &PlayerView) -> Color
+    {
+        *COLORS.choose(&mut self.rng).expect("There are four colors")
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        view.opponents().map(|(seat, _)| seat).choose(&mut self.rng).unwrap_or(0)
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// Tunes how `GreedyBot` scores each card it could play. The highest score is played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreedyWeights
+{
+    // Per point the card is worth, so high scoring cards are dumped first
+    pub points: i32,
+    // Per other card of the same color in the hand, so the bot plays into its
+    // strongest color
+    pub color_count: i32,
+    // Taken off wildcards so a matching card is played first
+    pub wild_penalty: i32,
+    // Added to Draw Twos and Draw Fours when the next player is down to
+    // `low_hand_size` cards or fewer
+    pub attack_bonus: i32,
+    pub low_hand_size: usize,
+    // Added to Skips, Reverses and Draw Twos whatever the other players hold
+    pub action_bonus: i32,
+    // Only play a Draw Four when nothing else can be played
+    pub hold_draw_fours: bool
+}
+
+impl GreedyWeights
+{
+    pub const BALANCED: GreedyWeights = GreedyWeights {
+        points: 1,
+        color_count: 3,
+        wild_penalty: 50,
+        attack_bonus: 60,
+        low_hand_size: 2,
+        action_bonus: 0,
+        hold_draw_fours: true
+    };
+
+    // Saves every wildcard until nothing else can be played
+    pub const HOARDER: GreedyWeights = GreedyWeights {
+        wild_penalty: 1000,
+        ..GreedyWeights::BALANCED
+    };
+
+    // Plays draw cards and skips at every opportunity
+    pub const AGGRESSOR: GreedyWeights = GreedyWeights {
+        wild_penalty: 0,
+        attack_bonus: 100,
+        low_hand_size: usize::MAX,
+        action_bonus: 100,
+        hold_draw_fours: false,
+        ..GreedyWeights::BALANCED
+    };
+
+    // Steers the game toward the color it holds the most of
+    pub const COLOR_FIXER: GreedyWeights = GreedyWeights {
+        points: 0,
+        color_count: 20,
+        wild_penalty: 5,
+        ..GreedyWeights::BALANCED
+    };
+}
+
+impl Default for GreedyWeights
+{
+    fn default() -> Self
+    {
+        GreedyWeights::BALANCED
+    }
+}
+
+// Follows a few simple rules of thumb, scored with `GreedyWeights`. It plays whenever
+// it can, holds Draw Fours until nothing else is playable, picks the color it holds
+// the most of and never challenges
+#[derive(Debug, Clone, Default)]
+pub struct GreedyBot
+{
+    weights: GreedyWeights
+}
+
+impl GreedyBot
+{
+    pub fn new(weights: GreedyWeights) -> GreedyBot
+    {
+        GreedyBot { weights }
+    }
+
+    pub fn weights(&self) -> &GreedyWeights
+    {
+        &self.weights
+    }
+
+    fn score(&self, view: &PlayerView, card: Card) -> i32
+    {
+        let weights = &self.weights;
+        let mut score = weights.points * card.points() as i32;
+        match card.color {
+            Some(color) => score += weights.color_count * (view.color_count(color) as i32 - 1),
+            None => score -= weights.wild_penalty
+        }
+
+        let next_hand = view.table.players[view.table.next_seat()].number_of_cards;
+        let attacks = matches!(card.card_type, CardType::DrawTwo | CardType::DrawFourWildcard);
+        if attacks && next_hand <= weights.low_hand_size {
+            score += weights.attack_bonus;
+        }
+        if matches!(card.card_type, CardType::Skip | CardType::Reverse | CardType::DrawTwo) {
+            score += weights.action_bonus;
+        }
+        score
+    }
+}
+
+impl PlayerController for GreedyBot
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let (view, legal) = (&observation.view, &observation.legal_moves[..]);
+        if legal.contains(&Move::PlayDrawn) {
+            return Move::PlayDrawn;
+        }
+
+        let plays: Vec<(usize, Card)> = legal.iter()
+            .filter_map(|candidate| match candidate {
+                Move::Play(index) => Some((*index, view.hand[*index])),
+                _ => None
+            })
+            .collect();
+        let holds_other_plays = self.weights.hold_draw_fours &&
+            plays.iter().any(|(_, card)| card.card_type != CardType::DrawFourWildcard);
+
+        plays.into_iter()
+            .filter(|(_, card)| !holds_other_plays || card.card_type != CardType::DrawFourWildcard)
+            .max_by_key(|(_, card)| self.score(view, *card))
+            .map(|(index, _)| Move::Play(index))
+            .or(legal.iter().copied().find(|candidate| matches!(candidate, Move::AcceptPenalty | Move::Draw)))
+            .unwrap_or(legal[0])
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        strategy::suggest_wild_color(view)
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// The built-in bots, by the names menus offer them under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bot
+{
+    Random,
+    Greedy,
+    Hoarder,
+    Aggressor,
+    ColorFixer,
+    MonteCarlo
+}
+
+impl Bot
+{
+    pub const ALL: [Bot; 6] = [Bot::Random, Bot::Greedy, Bot::Hoarder, Bot::Aggressor, Bot::ColorFixer, Bot::MonteCarlo];
+
+    pub fn name(self) -> &'static str
+    {
+        match self {
+            Bot::Random => "random",
+            Bot::Greedy => "greedy",
+            Bot::Hoarder => "hoarder",
+            Bot::Aggressor => "aggressor",
+            Bot::ColorFixer => "color fixer",
+            Bot::MonteCarlo => "monte carlo"
+        }
+    }
+
+    // Ignores case, so "Aggressor" finds the aggressor
+    pub fn from_name(name: &str) -> Option<Bot>
+    {
+        let name = name.trim().to_lowercase();
+        Bot::ALL.into_iter().find(|bot| bot.name() == name)
+    }
+
+    // e.g. `Bot::by_name("aggressor")`
+    pub fn by_name(name: &str) -> Option<Box<dyn PlayerController>>
+    {
+        Bot::from_name(name).map(Bot::controller)
+    }
+
+    pub fn controller(self) -> Box<dyn PlayerController>
+    {
+        self.seeded_controller(rand::random())
+    }
+
+    // The same seed always gives a bot that makes the same decisions
+    pub fn seeded_controller(self, seed: u64) -> Box<dyn PlayerController>
+    {
+        match self {
+            Bot::Random => Box::new(RandomBot::seeded(seed)),
+            Bot::Greedy => Box::new(GreedyBot::new(GreedyWeights::BALANCED)),
+            Bot::Hoarder => Box::new(GreedyBot::new(GreedyWeights::HOARDER)),
+            Bot::Aggressor => Box::new(GreedyBot::new(GreedyWeights::AGGRESSOR)),
+            Bot::ColorFixer => Box::new(GreedyBot::new(GreedyWeights::COLOR_FIXER)),
+            Bot::MonteCarlo => Box::new(MonteCarloBot::new(MonteCarloConfig { seed, ..Default::default() }))
+        }
+    }
+}
+
+impl fmt::Display for Bot
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Makes another controller slip up now and then: with probability `mistake_rate` a
+// decision is made at random among the legal choices instead, and a call of Uno is
+// forgotten
+#[derive(Debug, Clone)]
+pub struct WithMistakes<C: PlayerController, R: Rng = SmallRng>
+{
+    pub inner: C,
+    pub mistake_rate: f64,
+    pub rng: R
+}
+
+impl<C: PlayerController> WithMistakes<C>
+{
+    pub fn new(inner: C, mistake_rate: f64, seed: u64) -> WithMistakes<C>
+    {
+        WithMistakes { inner, mistake_rate, rng: SmallRng::seed_from_u64(seed) }
+    }
+}
+
+impl<C: PlayerController, R: Rng> WithMistakes<C, R>
+{
+    fn blunders(&mut self) -> bool
+    {
+        self.rng.gen_bool(self.mistake_rate.clamp(0.0, 1.0))
+    }
+}
+
+impl<C: PlayerController + Clone + 'static, R: Rng + Clone + 'static> PlayerController for WithMistakes<C, R>
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        if self.blunders() {
+            let legal = &observation.legal_moves[..];
+            *legal.choose(&mut self.rng).expect("There is always a legal move on the bot's turn")
+        } else {
+            self.inner.choose_move(observation)
+        }
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        if self.blunders() {
+            *COLORS.choose(&mut self.rng).expect("There are four colors")
+        } else {
+            self.inner.choose_color(view)
+        }
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        if self.blunders() {
+            view.opponents().map(|(seat, _)| seat).choose(&mut self.rng).unwrap_or(0)
+        } else {
+            self.inner.choose_swap_target(view)
+        }
+    }
+
+    fn wants_to_call_uno(&mut self, view: &PlayerView) -> bool
+    {
+        !self.blunders() && self.inner.wants_to_call_uno(view)
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// How often a bot plays the move it thinks is best, see `WithMistakes`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difficulty
+{
+    Easy,
+    Normal,
+    #[default]
+    Hard
+}
+
+impl Difficulty
+{
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn mistake_rate(self) -> f64
+    {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 0.25,
+            Difficulty::Hard => 0.0
+        }
+    }
+
+    pub fn apply<C: PlayerController>(self, controller: C, seed: u64) -> WithMistakes<C>
+    {
+        WithMistakes::new(controller, self.mistake_rate(), seed)
+    }
+}
+
+impl fmt::Display for Difficulty
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Normal => write!(f, "Normal"),
+            Difficulty::Hard => write!(f, "Hard")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::config::{GameConfig, RULE_NAMES};
+    use crate::controller::{self, Controllers};
+    use crate::events::GameEvent;
+    use crate::game::Lobby;
+    use crate::simulation::{game_seed, Contestant, SimulationRunner};
+
+    fn view() -> PlayerView
+    {
+        let game = Lobby::with_players(["Ann", "Ben", "Cat", "Dan"]).unwrap().start_with_seed(4).unwrap();
+        game.view_for(0).unwrap()
+    }
+
+    // The share of `tries` Uno calls forgotten at each difficulty
+    fn forgotten(difficulty: Difficulty, tries: usize) -> f64
+    {
+        let view = view();
+        let mut bot = difficulty.apply(GreedyBot::default(), 9);
+        (0..tries).filter(|_| !bot.wants_to_call_uno(&view)).count() as f64 / tries as f64
+    }
+
+    #[test]
+    fn mistakes_come_at_the_difficulty_rate()
+    {
+        for difficulty in Difficulty::ALL {
+            let rate = forgotten(difficulty, 10_000);
+            assert!((rate - difficulty.mistake_rate()).abs() < 0.02, "{} forgot {} of calls", difficulty, rate);
+        }
+    }
+
+    #[test]
+    fn a_blundered_swap_goes_to_any_opponent()
+    {
+        let view = view();
+        let mut bot = WithMistakes::new(GreedyBot::default(), 1.0, 2);
+        let mut targets: Vec<usize> = (0..200).map(|_| bot.choose_swap_target(&view)).collect();
+        targets.sort_unstable();
+        targets.dedup();
+        assert_eq!(targets, [1, 2, 3]);
+
+        let mut careful = WithMistakes::new(GreedyBot::default(), 0.0, 2);
+        let chosen = GreedyBot::default().choose_swap_target(&view);
+        assert!((0..200).all(|_| careful.choose_swap_target(&view) == chosen));
+    }
+
+    #[test]
+    fn a_thousand_random_games_finish()
+    {
+        for seed in 0..1000 {
+            // Each game has its own mix of house rules and 2 to 6 players
+            let rules = game_seed(337, seed);
+            let mut config = GameConfig { max_turns: Some(300), ..GameConfig::default() };
+            for (bit, rule) in RULE_NAMES.iter().enumerate() {
+                config.set_rule(rule, rules >> bit & 1 == 1).unwrap();
+            }
+            let names: Vec<String> = (0..2 + seed % 5).map(|seat| format!("Bot {}", seat)).collect();
+            let mut lobby = Lobby::with_players(names).unwrap();
+            lobby.set_config(config);
+            let mut game = lobby.start_with_seed(seed).unwrap();
+
+            let mut controllers: Controllers = game.players().iter().enumerate()
+                .map(|(seat, player)| (player.id(), RandomBot::seeded(game_seed(seed, seat as u64)).clone_box()))
+                .collect();
+            let end = controller::run_game(&mut game, &mut controllers, &mut ());
+            assert!(end.is_ok(), "seed {}: {:?}", seed, end);
+            assert!(game.is_over());
+            assert_eq!(game.check_invariants(), Ok(()), "seed {}", seed);
+        }
+    }
+
+    // How often `bot` beats `other` head to head, playing first in half the games
+    fn win_rate(bot: Bot, other: Bot, games: usize) -> f64
+    {
+        let play = |roster: [Bot; 2], seed| SimulationRunner::new(roster.map(Contestant::bot).to_vec(),
+            GameConfig::default(), seed, games / 2).run().unwrap();
+        let wins = play([bot, other], 1).seats[0].wins + play([other, bot], 2).seats[1].wins;
+        wins as f64 / games as f64
+    }
+
+    #[test]
+    fn greedy_beats_random()
+    {
+        let rate = win_rate(Bot::Greedy, Bot::Random, 300);
+        assert!(rate > 0.8, "greedy only won {} of its games", rate);
+    }
+
+    // Every card `bot` played in games against GreedyBot, with how many cards it held
+    // just before. Worked out backwards from the hand it ended with
+    fn plays(bot: Bot) -> Vec<(usize, Card)>
+    {
+        let mut plays = Vec::new();
+        for seed in 0..200 {
+            let mut game = Lobby::with_players(["Ann", "Ben"]).unwrap().start_with_seed(seed).unwrap();
+            let id = game.players()[0].id();
+            let mut controllers: Controllers = game.players().iter().map(|player| player.id())
+                .zip([bot.seeded_controller(seed), Bot::Greedy.seeded_controller(seed)])
+                .collect();
+            controller::run_game(&mut game, &mut controllers, &mut ()).unwrap();
+
+            let mut held = game.players()[0].number_of_cards();
+            for recorded in game.events().iter().rev() {
+                match recorded.event {
+                    GameEvent::CardPlayed { player, card } if player == id => {
+                        held += 1;
+                        plays.push((held, card));
+                    },
+                    GameEvent::CardDrawn { player, .. } if player == id => held -= 1,
+                    _ => ()
+                }
+            }
+        }
+        plays
+    }
+
+    #[test]
+    fn the_hoarder_plays_wildcards_later()
+    {
+        let held_for_wilds = |bot| {
+            let held: Vec<usize> = plays(bot).into_iter()
+                .filter(|(_, card)| card.is_wild())
+                .map(|(held, _)| held)
+                .collect();
+            held.iter().sum::<usize>() as f64 / held.len() as f64
+        };
+        let (hoarder, aggressor) = (held_for_wilds(Bot::Hoarder), held_for_wilds(Bot::Aggressor));
+        assert!(hoarder + 1.0 < aggressor,
+            "the hoarder held {} cards for wildcards, the aggressor {}", hoarder, aggressor);
+    }
+
+    #[test]
+    fn bots_are_found_by_name()
+    {
+        for bot in Bot::ALL {
+            assert_eq!(Bot::from_name(&bot.name().to_uppercase()), Some(bot));
+        }
+        assert!(Bot::by_name(" aggressor ").is_some());
+        assert!(Bot::by_name("nobody").is_none());
+    }
+
+    // GreedyBot's pick from `hand`, with every card in it playable
+    fn greedy_pick(hand: &[&str]) -> Move
+    {
+        let game = Lobby::with_players(["Ann", "Ben"]).unwrap().start_with_seed(6).unwrap();
+        let mut observation = game.observe(game.current_seat()).unwrap();
+        observation.view.hand = hand.iter().map(|card| card.parse().unwrap()).collect();
+        observation.legal_moves = (0..hand.len()).map(Move::Play).chain([Move::Draw]).collect();
+        GreedyBot::default().choose_move(&observation)
+    }
+
+    #[test]
+    fn greedy_plays_a_matching_card_before_a_wildcard()
+    {
+        assert_eq!(greedy_pick(&["wild", "+4", "red 3"]), Move::Play(2));
+        // And saves its Draw Four for last
+        assert_eq!(greedy_pick(&["+4", "wild"]), Move::Play(1));
+        assert_eq!(greedy_pick(&["+4"]), Move::Play(0));
+        // The highest scoring card goes first
+        assert_eq!(greedy_pick(&["red 3", "red skip", "red 9"]), Move::Play(1));
+    }
+
+    #[test]
+    fn blundered_moves_are_still_legal()
+    {
+        let game = Lobby::with_players(["Ann", "Ben"]).unwrap().start_with_seed(6).unwrap();
+        let observation = game.observe(game.current_seat()).unwrap();
+        let mut bot = WithMistakes::new(GreedyBot::default(), 1.0, 3);
+        for _ in 0..200 {
+            assert!(observation.legal_moves.contains(&bot.choose_move(&observation)));
+        }
+    }
+}
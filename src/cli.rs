@@ -0,0 +1,2320 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::config::StartingPlayer;
+use crate::console::{Console, MemoryConsole};
+use crate::controller::{self, Controllers, Decision, Narrator, PlayerController, Report};
+use crate::error::{GameError, NameError};
+use crate::events::RecordedEvent;
+use crate::history::TurnAction;
+use crate::game::{Card, CardType, Color, Game, GameEnd, Lobby, Move, PendingAction, PlayOutcome, PlayerId, MIN_PLAYERS};
+use crate::matches::{Match, SavedMatch};
+use crate::menu;
+use crate::messages::{fill, Messages};
+use crate::render::Renderer;
+use crate::replay::{Playback, Recording, Replay, ReplayError};
+use crate::simulation::game_seed;
+#[cfg(feature = "serde")]
+use crate::save::SaveFile;
+use crate::scenario::{Goal, Scenario};
+use crate::strategy;
+use crate::transcript::Transcript;
+use crate::view::{BotObservation, PlayerView, SpectatorView};
+
+// The next line the player typed
+pub fn read_line(console: &mut impl Console) -> String
+{
+    console.read_line().expect("Can't read the next line of input :(")
+}
+
+// Tell the player their input can't be used before they're asked again
+pub fn reject(console: &mut impl Console, message: impl fmt::Display)
+{
+    writeln!(console, "{}", message);
+    console.input_rejected();
+}
+
+// Short forms of the play prompt's commands
+pub const ALIASES: [(&str, &str); 6] =
+    [("d", "draw"), ("s", "status"), ("h", "hint"), ("q", "quit"), ("u", "uno"), ("?", "help")];
+
+// Every command the play prompt knows, for suggesting one when a word is nearly right
+const COMMANDS: [&str; 15] = ["draw", "sort", "uno", "catch", "jump", "join", "leave", "quit", "concede", "save",
+    "history", "status", "rules", "hint", "help"];
+
+// What someone typed, tidied up the same way for every prompt: lowercase, without the
+// spaces around it or runs of them inside it, and with a short form of a command
+// spelled out. "p3" and "play red 5" are just "3" and "red 5"
+pub fn normalize_input(input: &str) -> String
+{
+    let words: Vec<String> = input.split_whitespace().map(str::to_lowercase).collect();
+    let text = words.join(" ");
+    let text = match text.strip_prefix("play ") {
+        Some(rest) => rest.to_owned(),
+        None => match text.strip_prefix('p') {
+            Some(rest) if rest.trim_start().starts_with(|c: char| c.is_ascii_digit()) => rest.trim_start().to_owned(),
+            _ => text
+        }
+    };
+
+    let (first, rest) = text.split_once(' ').unwrap_or((&text, ""));
+    match ALIASES.iter().find(|(alias, _)| *alias == first) {
+        Some((_, command)) if rest.is_empty() => command.to_string(),
+        Some((_, command)) => format!("{} {}", command, rest),
+        None => text
+    }
+}
+
+// The command `word` was most likely meant to be, if it's only a letter or two off
+pub fn closest_command(word: &str) -> Option<&'static str>
+{
+    let allowed = if word.chars().count() <= 4 { 1 } else { 2 };
+    COMMANDS.into_iter()
+        .map(|command| (edit_distance(word, command), command))
+        .filter(|(distance, _)| (1..=allowed).contains(distance))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, command)| command)
+}
+
+// How many letters have to be added, removed, changed or swapped with their neighbour
+// to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize
+{
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    table[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            table[i][j] = (table[i - 1][j] + 1).min(table[i][j - 1] + 1).min(table[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                table[i][j] = table[i][j].min(table[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    table[a.len()][b.len()]
+}
+
+// Answered in the players' language or in English. The answer isn't tidied up like a
+// command, since 's' is yes in some languages
+pub fn ask_yes_no(console: &mut impl Console, messages: &Messages, prompt: &str) -> bool
+{
+    loop {
+        console.write(prompt);
+        let answer = read_line(console).trim().to_lowercase();
+        if messages.is_yes(&answer) {
+            break true;
+        }
+        if messages.is_no(&answer) {
+            break false;
+        }
+        reject(console, messages.answer_yes_no);
+    }
+}
+
+// e.g. "'d' for 'draw', 's' for 'status'"
+fn aliases(messages: &Messages) -> String
+{
+    let aliases: Vec<String> = ALIASES.iter()
+        .map(|(alias, command)| fill(messages.alias_for, &[alias, command]))
+        .collect();
+    aliases.join(", ")
+}
+
+fn describe_top_card(renderer: &Renderer, table: &SpectatorView) -> String
+{
+    renderer.top_card(table.top_card, table.active_color)
+}
+
+pub fn player_name(game: &Game, id: PlayerId) -> &str
+{
+    game.player_by_id(id).map_or("Unknown player", |player| player.name())
+}
+
+// e.g. "[Alice·4] -> Bob·1! -> Carol·7 ↻ clockwise"
+pub fn turn_order(renderer: &Renderer, game: &Game) -> String
+{
+    renderer.turn_order(game.turn_order().map(|player| (player.name().as_str(), player.number_of_cards())),
+        game.direction())
+}
+
+fn view_name(table: &SpectatorView, id: PlayerId) -> &str
+{
+    table.players.iter().find(|player| player.id == id).map_or("Unknown player", |player| player.name.as_str())
+}
+
+fn prompt_for_color(console: &mut impl Console, messages: &Messages) -> Color
+{
+    const COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+    loop {
+        writeln!(console, "{}", messages.color_prompt);
+        for (index, color) in COLORS.iter().enumerate() {
+            writeln!(console, "{} - {}", index + 1, messages.color(*color));
+        }
+        console.write(messages.your_choice);
+
+        // The color's name works as well as its number
+        let choice = normalize_input(&read_line(console));
+        break match choice.as_str() {
+            "1" => Color::Red,
+            "2" => Color::Green,
+            "3" => Color::Blue,
+            "4" => Color::Yellow,
+            name => match messages.parse_color(name) {
+                Some(color) => color,
+                None => {
+                    reject(console, format!("{}\n", messages.color_rejected));
+                    continue
+                }
+            }
+        };
+    }
+}
+
+// A player sitting at the keyboard. Everyone shares the one terminal, so any of them
+// can call Uno, catch someone or jump in while it's somebody else's turn
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleController<C: Console>
+{
+    console: C,
+    // With privacy on the screen is cleared once the player has made their move, so
+    // the next player can't see their hand
+    options: CliOptions,
+    // The color asked for along with a wildcard played by name, like "wild blue"
+    wild_color: Option<Color>,
+    // When the turn timer runs out, and on which turn
+    deadline: Option<(u32, Instant)>
+}
+
+impl<C: Console> ConsoleController<C>
+{
+    pub fn new(console: C, options: CliOptions) -> ConsoleController<C>
+    {
+        ConsoleController { console, options, wild_color: None, deadline: None }
+    }
+
+    // What can be typed at the play prompt besides a card number
+    fn commands(&self) -> String
+    {
+        let hint = if self.options.hints { ", 'hint'" } else { "" };
+        fill(self.options.renderer.messages.commands, &[&hint])
+    }
+
+    // The hand index of the card named by `text`, or None if it doesn't name a card. A
+    // card named without its color can be any of them if they're all the same, and a
+    // color named with a wildcard is kept for when the color is asked for
+    fn card_by_name(&mut self, view: &PlayerView, text: &str) -> Option<Result<usize, String>>
+    {
+        let (colors, rest): (Vec<&str>, Vec<&str>) =
+            text.split_whitespace().partition(|word| word.parse::<Color>().is_ok());
+        let card_type: CardType = rest.join(" ").parse().ok()?;
+        let color = match colors.as_slice() {
+            [] => None,
+            [color] => color.parse().ok(),
+            _ => return None
+        };
+
+        let matches: Vec<usize> = (0..view.hand.len())
+            .filter(|&index| {
+                let card = view.hand[index];
+                card.card_type == card_type && (card.is_wild() || color.is_none() || card.color == color)
+            })
+            .collect();
+        let mut distinct: Vec<Card> = matches.iter().map(|&index| view.hand[index]).collect();
+        distinct.sort_by_key(Card::sort_key);
+        distinct.dedup();
+
+        let renderer = self.options.renderer;
+        let messages = renderer.messages;
+        Some(match distinct.as_slice() {
+            [] => Err(fill(messages.dont_have, &[&match color {
+                Some(color) => renderer.card(Card::new(card_type, color)),
+                None => messages.card_type(card_type)
+            }])),
+            [card] => {
+                self.wild_color = color.filter(|_| card.is_wild());
+                Ok(matches[0])
+            },
+            cards => {
+                let names: Vec<String> = cards.iter()
+                    .map(|card| fill(messages.a_card, &[&renderer.card(*card)]))
+                    .collect();
+                let (last, others) = names.split_last().expect("There are at least two cards");
+                Err(fill(messages.which_one, &[&others.join(", "), last]))
+            }
+        })
+    }
+
+    fn find_seat(&mut self, view: &PlayerView, name: &str) -> Option<usize>
+    {
+        let seat = view.table.players.iter().position(|player| player.name.to_lowercase() == name);
+        if seat.is_none() {
+            writeln!(self.console, "{}\n", fill(self.options.renderer.messages.no_player_named, &[&name]));
+        }
+        seat
+    }
+
+    // The bots' choice of move, with the reason a person would give for it
+    fn print_hint(&mut self, view: &PlayerView, legal: &[Move])
+    {
+        let renderer = self.options.renderer;
+        let messages = renderer.messages;
+        let can_play = legal.iter().any(|chosen| matches!(chosen, Move::Play(_)));
+        let hint = match strategy::suggest_move(view, legal) {
+            Some(Move::Play(index)) => {
+                let card = view.hand[index];
+                if let Some(color) = card.color {
+                    let left = view.color_count(color) - 1;
+                    let reason = match left {
+                        0 => fill(messages.hint_last_of_color, &[&renderer.color(color)]),
+                        1 => fill(messages.hint_one_more, &[&renderer.color(color)]),
+                        left => fill(messages.hint_more, &[&left, &renderer.color(color)])
+                    };
+                    fill(messages.hint_play, &[&(index + 1), &renderer.card(card), &reason])
+                } else {
+                    let color = strategy::suggest_wild_color(view);
+                    fill(messages.hint_play_wild, &[&(index + 1), &renderer.card(card), &renderer.color(color)])
+                }
+            },
+            Some(Move::AcceptPenalty) if can_play => messages.hint_take_penalty_early.to_owned(),
+            Some(Move::AcceptPenalty) => messages.hint_take_penalty.to_owned(),
+            Some(Move::Draw) if !can_play => messages.hint_must_draw.to_owned(),
+            Some(_) => messages.hint_draw.to_owned(),
+            None => messages.no_hint.to_owned()
+        };
+        writeln!(self.console, "{}\n", hint);
+    }
+
+    // Everything public about the game so far. The deck never runs out, so there's no
+    // draw pile to count
+    fn print_status(&mut self, view: &PlayerView)
+    {
+        let table = &view.table;
+        let messages = self.options.renderer.messages;
+        writeln!(self.console, "{}", fill(messages.status_turn, &[&table.turn_number,
+            &view_name(table, table.players[table.current_seat].id), &messages.direction(table.direction)]));
+        writeln!(self.console, "{}", fill(messages.top_card, &[&describe_top_card(&self.options.renderer, table)]));
+        if let Some(PendingAction::DrawPenalty(cards)) = table.pending_action {
+            writeln!(self.console, "{}", fill(messages.status_penalty, &[&cards]));
+        }
+        writeln!(self.console, "{}", messages.status_players);
+        for player in table.turn_order() {
+            let uno = if player.called_uno { messages.status_called_uno } else { "" };
+            let line = match player.number_of_cards {
+                1 => fill(messages.status_one_card, &[&player.name, &uno]),
+                cards => fill(messages.status_many_cards, &[&player.name, &cards, &uno])
+            };
+            writeln!(self.console, "  {}", line);
+        }
+        writeln!(self.console);
+    }
+
+    // Newest last, like "T14: bob played a Blue Skip"
+    fn print_history(&mut self, view: &PlayerView)
+    {
+        let turns = &view.table.recent_turns;
+        let renderer = self.options.renderer;
+        let messages = renderer.messages;
+        if turns.is_empty() {
+            writeln!(self.console, "{}\n", messages.history_empty);
+            return;
+        }
+
+        writeln!(self.console, "{}", messages.history_title);
+        for record in turns.iter().skip(turns.len().saturating_sub(self.options.history_length)) {
+            let action = match record.action {
+                TurnAction::Penalized(1) => messages.history_penalized_one.to_owned(),
+                TurnAction::Penalized(cards) => fill(messages.history_penalized, &[&cards]),
+                action => renderer.turn_action(action)
+            };
+            let color = record.chosen_color.map(|color| fill(messages.history_chose, &[&renderer.color(color)]))
+                .unwrap_or_default();
+            let name = view_name(&view.table, record.player);
+            writeln!(self.console, "{}: {} {}{}", renderer.turn_label(record.turn), name, action, color);
+        }
+        writeln!(self.console);
+    }
+
+    fn prompt(&mut self, view: &PlayerView, legal: &[Move])
+    {
+        let table = &view.table;
+        let name = &table.players[view.seat].name;
+        let messages = self.options.renderer.messages;
+        let line = |shown: bool, text: &str| if shown { format!("{}\n", text) } else { String::new() };
+
+        let jump_notice = line(table.config.jump_in, messages.jump_notice);
+        let play_many_notice = line(table.config.play_identical_together, messages.play_many_notice);
+        let penalty_notice = match table.pending_action {
+            Some(PendingAction::DrawPenalty(cards)) => format!("{}\n", fill(messages.penalty_notice, &[&cards])),
+            _ => String::new()
+        };
+        let hint_notice = line(self.options.hints, messages.hint_notice);
+
+        let recent: Vec<String> = table.recently_played.iter().map(|card| self.options.renderer.card(*card)).collect();
+        let recent = self.options.renderer.sequence(&recent);
+        // Only the player whose turn it is sees this, so it gives nothing away
+        let playable: Vec<usize> = legal.iter()
+            .filter_map(|chosen| match chosen {
+                Move::Play(index) => Some(*index),
+                _ => None
+            })
+            .collect();
+        let hand = self.options.renderer.marked_hand(&view.hand, &playable, &view.new_card_indices());
+        let playable_notice = match playable.len() {
+            0 => messages.no_playable.to_owned(),
+            1 => messages.one_playable.to_owned(),
+            count => fill(messages.many_playable, &[&count])
+        };
+
+        let order = self.options.renderer.turn_order(table.turn_order()
+            .map(|player| (player.name.as_str(), player.number_of_cards)), table.direction);
+
+        self.console.write(format!("{}\n{}\n{}\n{}\n\n{}\n{}{}\n{}{}{}{}\n{}\n{}\n{}\n{}{}{}",
+            fill(messages.prompt_turn, &[&table.turn_number, name]), fill(messages.turn_order, &[&order]),
+            fill(messages.top_card, &[&describe_top_card(&self.options.renderer, table)]),
+            fill(messages.recently_played, &[&recent]), fill(messages.cards_of, &[name]), hand,
+            playable_notice, penalty_notice, jump_notice, play_many_notice, messages.prompt_uno,
+            messages.prompt_players,
+            messages.prompt_look, messages.prompt_save, hint_notice, self.time_notice(),
+            messages.prompt_choose).as_str());
+    }
+
+    // When this turn's time runs out, with the timer started on the player's first look at it
+    fn start_timer(&mut self, turn_number: u32) -> Option<Instant>
+    {
+        let limit = self.options.turn_timer?;
+        match self.deadline {
+            Some((turn, deadline)) if turn == turn_number => Some(deadline),
+            _ => {
+                let deadline = self.console.now() + limit;
+                self.deadline = Some((turn_number, deadline));
+                Some(deadline)
+            }
+        }
+    }
+
+    fn seconds_left(&self) -> Option<u64>
+    {
+        let (_, deadline) = self.deadline?;
+        Some(deadline.saturating_duration_since(self.console.now()).as_secs_f64().ceil() as u64)
+    }
+
+    fn time_notice(&self) -> String
+    {
+        self.seconds_left()
+            .map(|seconds| fill(self.options.renderer.messages.time_left, &[&seconds]) + "\n")
+            .unwrap_or_default()
+    }
+
+    // The next line the player typed, or nothing when their time runs out first. They're
+    // reminded how long they have every ten seconds, and warned with ten to go
+    fn read_line_before(&mut self, deadline: Instant) -> Option<String>
+    {
+        const REMINDER: Duration = Duration::from_secs(10);
+        let messages = self.options.renderer.messages;
+        loop {
+            let left = deadline.saturating_duration_since(self.console.now());
+            if left.is_zero() {
+                return None;
+            }
+            let reminder = REMINDER * ((left.as_nanos() - 1) / REMINDER.as_nanos()) as u32;
+            let line = self.console.read_line_within(left - reminder).expect("Can't read the next line of input :(");
+            if line.is_some() || reminder.is_zero() {
+                return line;
+            }
+            let seconds = reminder.as_secs();
+            let notice = if reminder == REMINDER { messages.time_warning } else { messages.time_left };
+            self.console.write(format!("\n{}\n{}", fill(notice, &[&seconds]), messages.prompt_choose).as_str());
+        }
+    }
+
+    // Ask what to do with a playable card that was just drawn
+    fn play_or_keep(&mut self, view: &PlayerView, legal: &[Move]) -> Move
+    {
+        let Some(card) = view.drawn_card.map(|index| view.hand[index]) else {
+            return Move::PlayDrawn;
+        };
+        let messages = self.options.renderer.messages;
+        if !legal.contains(&Move::KeepDrawn) {
+            writeln!(self.console, "{}", fill(messages.drew_must_play, &[&self.options.renderer.card(card)]));
+            return Move::PlayDrawn;
+        }
+        let prompt = fill(messages.drew_play_it, &[&self.options.renderer.card(card)]);
+        if ask_yes_no(&mut self.console, messages, &prompt) {
+            Move::PlayDrawn
+        } else {
+            Move::KeepDrawn
+        }
+    }
+}
+
+// What a player who runs out of time does: draw a card and keep it, or whatever they can
+fn timeout_move(legal: &[Move]) -> Move
+{
+    [Move::KeepDrawn, Move::Draw, Move::AcceptPenalty].into_iter()
+        .find(|chosen| legal.contains(chosen))
+        .unwrap_or(legal[0])
+}
+
+// Joining takes a copy of the controller for the new player
+impl<C: Console + Clone + 'static> ConsoleController<C>
+{
+    // The player's next decision, after any looking around they want to do first
+    fn read_decision(&mut self, observation: &BotObservation) -> Decision
+    {
+        let (view, legal) = (&observation.view, &observation.legal_moves[..]);
+        self.wild_color = None;
+        if self.options.auto_sort && view.drawn_card.is_none() && !view.hand.is_sorted_by_key(Card::sort_key) {
+            return Decision::SortHand;
+        }
+        let deadline = self.start_timer(view.table.turn_number);
+        // Out of time after drawing, the card is kept
+        if deadline.is_some_and(|deadline| deadline <= self.console.now()) {
+            return Decision::Move(timeout_move(legal));
+        }
+        if legal.contains(&Move::PlayDrawn) {
+            return Decision::Move(self.play_or_keep(view, legal));
+        }
+
+        let name = &view.table.players[view.seat].name;
+        let messages = self.options.renderer.messages;
+        if legal.contains(&Move::ChallengeDrawFour) &&
+            ask_yes_no(&mut self.console, messages, &fill(messages.challenge_question, &[name])) {
+            return Decision::Move(Move::ChallengeDrawFour);
+        }
+
+        loop {
+            self.prompt(view, legal);
+
+            let input_raw = match deadline {
+                Some(deadline) => match self.read_line_before(deadline) {
+                    Some(line) => line,
+                    None => {
+                        writeln!(self.console, "\n{}", fill(messages.time_up, &[name]));
+                        return Decision::Move(timeout_move(legal));
+                    }
+                },
+                None => read_line(&mut self.console)
+            };
+            let input = normalize_input(&input_raw);
+            let found = match input.as_str() {
+                "uno" => return Decision::CallUno { seat: view.seat },
+                "history" => {
+                    self.print_history(view);
+                    continue;
+                }
+                "hint" if self.options.hints => {
+                    self.print_hint(view, legal);
+                    continue;
+                }
+                "help" | "hint" => {
+                    writeln!(self.console, "{}\n", fill(messages.help, &[&self.commands(), &aliases(messages)]));
+                    continue;
+                }
+                "status" => {
+                    self.print_status(view);
+                    continue;
+                }
+                "quit" | "concede" => {
+                    if ask_yes_no(&mut self.console, messages, messages.concede_question) {
+                        return Decision::Leave { seat: view.seat };
+                    }
+                    continue;
+                }
+                "rules" => {
+                    writeln!(self.console, "{}\n{}", messages.rules_title, view.table.config);
+                    continue;
+                }
+                "sort" => return Decision::SortHand,
+                "save" => return Decision::Save { file: None },
+                // The file name is taken from the original input, like a joining player's name
+                text if text.starts_with("save ") =>
+                    return Decision::Save { file: Some(input_raw.trim()["save ".len()..].trim().to_owned()) },
+                text if text.starts_with("uno ") => self.find_seat(view, text["uno ".len()..].trim())
+                    .map(|seat| Decision::CallUno { seat }),
+                text if text.starts_with("catch ") => self.find_seat(view, text["catch ".len()..].trim())
+                    .map(|target| Decision::Catch { target }),
+                text if text.starts_with("jump ") => self.find_seat(view, text["jump ".len()..].trim())
+                    .map(|seat| Decision::JumpIn { seat }),
+                text if text.starts_with("leave ") => self.find_seat(view, text["leave ".len()..].trim())
+                    .map(|seat| Decision::Leave { seat }),
+                // Take the name from the original input so its capitalization is kept
+                text if text.starts_with("join ") => Some(Decision::Join {
+                    name: input_raw.trim()["join ".len()..].trim().to_owned(),
+                    controller: Box::new(self.clone())
+                }),
+                "draw" if legal.contains(&Move::AcceptPenalty) => Some(Decision::Move(Move::AcceptPenalty)),
+                "draw" => Some(Decision::Move(Move::Draw)),
+                text if text.contains(',') && view.table.config.play_identical_together => {
+                    let indices: Option<Vec<usize>> = text.split(',')
+                        .map(|choice| choice.trim().parse::<usize>().ok().and_then(|choice| choice.checked_sub(1)))
+                        .collect();
+                    if indices.is_none() {
+                        reject(&mut self.console, format!("{}\n", messages.indices_rejected));
+                    }
+                    indices.map(Decision::PlayMany)
+                }
+                text => {
+                    // "+4" would read as the number 4
+                    let found = match text.parse::<usize>() {
+                        Ok(choice) if !text.starts_with('+') => choice.checked_sub(1).map(Ok),
+                        _ => self.card_by_name(view, text)
+                    };
+                    match found {
+                        Some(Ok(index)) => Some(Decision::Move(Move::Play(index))),
+                        Some(Err(message)) => {
+                            reject(&mut self.console, message + "\n");
+                            None
+                        },
+                        None => {
+                            let first_word = text.split(' ').next().unwrap_or_default();
+                            let message = match closest_command(first_word) {
+                                Some(command) => fill(messages.did_you_mean, &[&command]),
+                                None => fill(messages.card_choice_rejected, &[&view.hand.len(), &self.commands()])
+                            };
+                            reject(&mut self.console, message + "\n");
+                            None
+                        }
+                    }
+                }
+            };
+
+            // Only the player at the keyboard sees the warning, and nothing is said about it after
+            let played = match &found {
+                Some(Decision::Move(Move::Play(index))) => std::slice::from_ref(index),
+                Some(Decision::PlayMany(indices)) => indices.as_slice(),
+                _ => &[]
+            };
+            if self.options.draw_four_warning && risky_draw_four(view, played)
+                && !ask_yes_no(&mut self.console, messages, messages.draw_four_warning) {
+                self.wild_color = None;
+                continue;
+            }
+            if let Some(decision) = found {
+                break decision;
+            }
+        }
+    }
+}
+
+// Whether playing the cards at `played` puts down a Draw Four that can be challenged
+// and lost, because the player holds a card of the active color
+fn risky_draw_four(view: &PlayerView, played: &[usize]) -> bool
+{
+    view.table.config.draw_four_challenge && !view.can_legally_play_draw_four() && played.iter()
+        .any(|index| view.hand.get(*index).is_some_and(|card| card.card_type == CardType::DrawFourWildcard))
+}
+
+impl<C: Console + Clone + 'static> PlayerController for ConsoleController<C>
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        loop {
+            if let Decision::Move(chosen) = self.decide(observation) {
+                break chosen;
+            }
+        }
+    }
+
+    fn choose_color(&mut self, _view: &PlayerView) -> Color
+    {
+        if let Some(color) = self.wild_color.take() {
+            return color;
+        }
+        let color = prompt_for_color(&mut self.console, self.options.renderer.messages);
+        if self.options.privacy {
+            self.console.clear();
+        }
+        color
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        let messages = self.options.renderer.messages;
+        writeln!(self.console, "{}", messages.swap_title);
+        for (seat, player) in view.opponents() {
+            writeln!(self.console, "{}",
+                fill(messages.swap_option, &[&(seat + 1), &player.name, &player.number_of_cards]));
+        }
+
+        loop {
+            self.console.write(messages.your_choice);
+            let choice = read_line(&mut self.console).trim().parse::<usize>().ok().and_then(|choice| choice.checked_sub(1));
+            match choice {
+                Some(seat) if seat < view.table.players.len() && seat != view.seat => break seat,
+                _ => reject(&mut self.console, messages.choose_listed_player)
+            }
+        }
+    }
+
+    // Players call Uno themselves by typing 'uno'
+    fn wants_to_call_uno(&mut self, _view: &PlayerView) -> bool
+    {
+        false
+    }
+
+    fn decide(&mut self, observation: &BotObservation) -> Decision
+    {
+        let decision = self.read_decision(observation);
+        let ends_turn = match decision {
+            Decision::Move(_) | Decision::PlayMany(_) | Decision::JumpIn { .. } => true,
+            Decision::Leave { seat } => seat == observation.view.seat,
+            _ => false
+        };
+        if self.options.privacy && ends_turn {
+            self.console.clear();
+        }
+        decision
+    }
+
+    fn rejected(&mut self, view: &PlayerView, error: &GameError)
+    {
+        let renderer = self.options.renderer;
+        let messages = renderer.messages;
+        match error {
+            GameError::InvalidCardIndex { hand_size, .. } =>
+                writeln!(self.console, "{}\n", fill(messages.index_out_of_range, &[hand_size])),
+            GameError::MustPlay { playable_indices } => {
+                let choices: Vec<String> = playable_indices.iter().map(|index| (index + 1).to_string()).collect();
+                writeln!(self.console, "{}\n", fill(messages.must_play, &[&choices.join(", ")]));
+            },
+            GameError::CardUnplayable { attempted, top } => writeln!(self.console, "{}\n",
+                fill(messages.unplayable, &[&renderer.card(*attempted), &renderer.card(*top)])),
+            GameError::CannotGoOutOnWild(card) =>
+                writeln!(self.console, "{}\n", fill(messages.cannot_go_out_on_wild, &[&renderer.card(*card)])),
+            GameError::NoIdenticalCard { seat, top } => writeln!(self.console, "{}\n",
+                fill(messages.no_identical, &[&view.table.players[*seat].name, &renderer.card(*top)])),
+            GameError::InvalidName(NameError::Taken(name)) =>
+                writeln!(self.console, "{}\n", fill(messages.name_taken, &[name])),
+            GameError::InvalidSwapTarget(_) => writeln!(self.console, "{}", messages.choose_listed_player),
+            error => writeln!(self.console, "{}\n", error)
+        }
+        self.console.input_rejected();
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// Everything about how a game is shown at the console that isn't part of the game itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliOptions
+{
+    pub bot_delay: BotDelay,
+    pub renderer: Renderer,
+    // Hide each player's hand from the others when they pass the computer around. Only
+    // used when more than one person is playing at a terminal
+    pub privacy: bool,
+    // Whether players can ask for a hint. Some tables count it as cheating
+    pub hints: bool,
+    // Put each player's hand in order whenever it's their turn
+    pub auto_sort: bool,
+    // How many turns 'history' shows, up to `game::RECENT_TURNS`
+    pub history_length: usize,
+    // Where 'save' writes the game when it isn't given a file
+    pub save_file: Option<PathBuf>,
+    // Where every game is logged in full, see `transcript::Transcript`
+    pub log_file: Option<PathBuf>,
+    // How long each player at the keyboard has to make their move before a card is drawn
+    // for them. Bots are never timed
+    pub turn_timer: Option<Duration>,
+    // Where each game that's dealt is written as a `replay::Replay`, replacing the last
+    pub replay_file: Option<PathBuf>,
+    // With Draw Four challenges on, ask a player who holds the active color whether they
+    // really want to play a Draw Four, since it can be challenged
+    pub draw_four_warning: bool
+}
+
+impl Default for CliOptions
+{
+    fn default() -> Self
+    {
+        CliOptions { bot_delay: BotDelay::default(), renderer: Renderer::default(), privacy: false, hints: true,
+            auto_sort: false, history_length: 8, save_file: None, log_file: None, turn_timer: None, replay_file: None,
+            draw_four_warning: true }
+    }
+}
+
+// How long bots seem to think before each move in a live game, so their turns don't
+// flash by. Each pause is `delay` plus a random part of `jitter`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BotDelay
+{
+    pub delay: Duration,
+    pub jitter: Duration
+}
+
+impl BotDelay
+{
+    pub fn is_none(&self) -> bool
+    {
+        self.delay.is_zero() && self.jitter.is_zero()
+    }
+
+    fn pause(&self)
+    {
+        let jitter = rand::thread_rng().gen_range(0..=self.jitter.as_millis() as u64);
+        thread::sleep(self.delay + Duration::from_millis(jitter));
+    }
+}
+
+// Reads durations like "800ms", "1.5s" or "0". A bare number is in milliseconds
+pub fn parse_duration(text: &str) -> Option<Duration>
+{
+    let text = text.trim();
+    let (number, seconds) = match text.strip_suffix("ms") {
+        Some(number) => (number, false),
+        None => match text.strip_suffix('s') {
+            Some(number) => (number, true),
+            None => (text, false)
+        }
+    };
+    let number: f64 = number.trim().parse().ok().filter(|number: &f64| number.is_finite() && *number >= 0.0)?;
+    Some(Duration::from_secs_f64(if seconds { number } else { number / 1000.0 }))
+}
+
+// Tells everyone at the terminal what just happened. Bots' cards are never shown, and
+// with privacy on neither are cards anyone draws
+#[derive(Debug, Clone, Default)]
+pub struct CliNarrator<C: Console>
+{
+    console: C,
+    bots: BTreeSet<PlayerId>,
+    options: CliOptions,
+    // The player the computer was last passed to, and on which turn
+    handed_to: Option<(PlayerId, u32)>,
+    // The match the game is a round of, saved along with it. Without serde there's no
+    // saving
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    in_match: Option<SavedMatch>
+}
+
+impl<C: Console> CliNarrator<C>
+{
+    pub fn new(console: C, bots: BTreeSet<PlayerId>, options: CliOptions) -> CliNarrator<C>
+    {
+        CliNarrator { console, bots, options, handed_to: None, in_match: None }
+    }
+
+    pub fn in_match(self, in_match: Option<SavedMatch>) -> CliNarrator<C>
+    {
+        CliNarrator { in_match, ..self }
+    }
+
+    // Wait for the player in `seat` to be the only one looking at the screen. The last
+    // player's hand was cleared away when they moved, so what's left on it is what
+    // everyone has seen happen since
+    fn hand_off(&mut self, game: &Game, seat: usize)
+    {
+        let player = &game.players()[seat];
+        let renderer = self.options.renderer;
+        write!(self.console, "{}", fill(renderer.messages.pass_computer, &[player.name()]));
+        read_line(&mut self.console);
+        self.handed_to = Some((player.id(), game.turn_number()));
+
+        // A wildcard turned over at the start is the first thing they have to deal with
+        if game.pending_action() == Some(PendingAction::AwaitingWildColor) {
+            writeln!(self.console, "{}\n\n{}\n{}", fill(renderer.messages.top_card, &[&renderer.card(game.top_card())]),
+                fill(renderer.messages.cards_of, &[player.name()]), renderer.hand(player.cards()));
+        }
+    }
+
+    // e.g. "2 cards: Red 5, Blue Skip", or just "2 cards" when they're kept private
+    fn drawn_cards(&self, cards: &[Card]) -> String
+    {
+        if self.options.privacy {
+            fill(self.options.renderer.messages.many_cards, &[&cards.len()])
+        } else {
+            self.options.renderer.describe(cards)
+        }
+    }
+
+    // Write the game out, then let the players stop there or keep going
+    #[cfg(feature = "serde")]
+    fn save(&mut self, game: &Game, file: Option<&str>)
+    {
+        let messages = self.options.renderer.messages;
+        let Some(path) = file.map(PathBuf::from).or_else(|| self.options.save_file.clone()) else {
+            writeln!(self.console, "{}\n", messages.nowhere_to_save);
+            return;
+        };
+        // The cards nobody has seen are shuffled again when the game is picked back up
+        let save = SaveFile::new(game, self.bots.iter().copied().collect(), rand::random(), self.in_match.clone());
+        if let Err(error) = save.write(&path) {
+            writeln!(self.console, "{}\n", fill(messages.save_failed, &[&path.display(), &error]));
+            return;
+        }
+        writeln!(self.console, "{}", fill(messages.saved, &[&path.display()]));
+        if !ask_yes_no(&mut self.console, messages, messages.keep_playing) {
+            writeln!(self.console, "{}", messages.save_waiting);
+            process::exit(0);
+        }
+        writeln!(self.console);
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn save(&mut self, _game: &Game, _file: Option<&str>)
+    {
+        writeln!(self.console, "{}\n", self.options.renderer.messages.save_unavailable);
+    }
+
+    fn print_hand_sizes(&mut self, game: &Game)
+    {
+        let sizes: Vec<String> = game.players().iter()
+            .map(|player| format!("{}: {}", player.name(), player.number_of_cards()))
+            .collect();
+        writeln!(self.console, "{}\n", fill(self.options.renderer.messages.hand_sizes, &[&sizes.join(", ")]));
+    }
+
+    // Announce what happened after a card was played
+    fn print_outcome(&mut self, game: &Game, outcome: &PlayOutcome)
+    {
+        let renderer = self.options.renderer;
+        let messages = renderer.messages;
+        let name = player_name(game, outcome.player);
+        if outcome.copies > 1 {
+            writeln!(self.console, "{}\n", fill(messages.played_copies, &[&name, &outcome.copies,
+                &renderer.card(outcome.card)]));
+        } else {
+            writeln!(self.console, "{}\n", fill(messages.played, &[&name, &renderer.card(outcome.card)]));
+        }
+
+        if let Some(winner) = outcome.winner {
+            writeln!(self.console, "{}\n", fill(messages.last_card_winner, &[&player_name(game, winner)]));
+            return;
+        }
+
+        if let Some(target) = outcome.swapped_with {
+            writeln!(self.console, "{}", fill(messages.swapped, &[&name, &player_name(game, target)]));
+            self.print_hand_sizes(game);
+        }
+
+        if outcome.hands_rotated {
+            writeln!(self.console, "{}", messages.rotated);
+            self.print_hand_sizes(game);
+        }
+
+        if outcome.reversed {
+            writeln!(self.console, "{}\n{}\n", fill(messages.reversed, &[&messages.direction(game.direction())]),
+                fill(messages.new_turn_order, &[&turn_order(&renderer, game)]));
+        }
+
+        if let Some((player, cards)) = &outcome.penalty {
+            writeln!(self.console, "{}",
+                fill(messages.drew_cards, &[&player_name(game, *player), &self.drawn_cards(cards)]));
+        }
+
+        for &player in &outcome.skipped {
+            writeln!(self.console, "{}\n", fill(messages.skipped, &[&player_name(game, player)]));
+        }
+
+        if outcome.pending_penalty > 0 {
+            writeln!(self.console, "{}\n", fill(messages.must_stack, &[&player_name(game, outcome.next_player),
+                &outcome.pending_penalty]));
+        }
+    }
+}
+
+impl<C: Console> Narrator for CliNarrator<C>
+{
+    fn turn_starting(&mut self, game: &Game, seat: usize)
+    {
+        let player = &game.players()[seat];
+        if self.bots.contains(&player.id()) {
+            if !self.options.bot_delay.is_none() {
+                writeln!(self.console, "{}", fill(self.options.renderer.messages.thinking, &[player.name()]));
+                self.options.bot_delay.pause();
+            }
+        } else if self.options.privacy && self.handed_to != Some((player.id(), game.turn_number())) {
+            self.hand_off(game, seat);
+        }
+    }
+
+    fn report(&mut self, game: &Game, report: &Report)
+    {
+        let renderer = self.options.renderer;
+        let messages = renderer.messages;
+        let name = |id: PlayerId| player_name(game, id);
+        match report {
+            Report::Played(outcome) => self.print_outcome(game, outcome),
+            Report::WildColorChosen { color, .. } =>
+                writeln!(self.console, "{}\n", fill(messages.wild_color_now, &[&renderer.color(*color)])),
+            Report::Drew { player, drawn } if self.bots.contains(player) || self.options.privacy => {
+                if !drawn.playable {
+                    writeln!(self.console, "{}\n", fill(messages.drew_unplayable_hidden, &[&name(*player)]));
+                } else if drawn.auto_played.is_none() {
+                    writeln!(self.console, "{}", fill(messages.drew_playable_hidden, &[&name(*player)]));
+                }
+            },
+            Report::Drew { player, drawn } => {
+                if !drawn.playable {
+                    writeln!(self.console, "{}", fill(messages.drew_unplayable, &[&renderer.card(drawn.card)]));
+                    writeln!(self.console, "{}\n", fill(messages.unable_to_play, &[&name(*player)]));
+                } else if drawn.auto_played.is_some() {
+                    writeln!(self.console, "{}", fill(messages.drew_playable, &[&renderer.card(drawn.card)]));
+                }
+            },
+            Report::DrewUntilPlayable { player, outcome } => match &outcome.found {
+                Some(drawn) => {
+                    if outcome.cards_drawn > 1 {
+                        writeln!(self.console, "{}", fill(messages.drew_before_playable, &[&name(*player),
+                            &outcome.cards_drawn]));
+                    }
+                    self.report(game, &Report::Drew { player: *player, drawn: drawn.clone() });
+                },
+                None => writeln!(self.console, "{}\n", fill(messages.drew_none_playable, &[&name(*player),
+                    &outcome.cards_drawn]))
+            },
+            Report::KeptDrawn { player, .. } if self.options.privacy =>
+                writeln!(self.console, "{}\n", fill(messages.kept_drawn_hidden, &[&name(*player)])),
+            Report::KeptDrawn { player, card } =>
+                writeln!(self.console, "{}\n", fill(messages.kept_drawn, &[&name(*player), &renderer.card(*card)])),
+            Report::PenaltyAccepted(outcome) => {
+                let player = name(outcome.player);
+                writeln!(self.console, "{}",
+                    fill(messages.drew_cards, &[&player, &self.drawn_cards(&outcome.cards_drawn)]));
+                writeln!(self.console, "{}\n", fill(messages.skipped, &[&player]));
+            },
+            Report::Challenged(outcome) => if outcome.offender_guilty {
+                writeln!(self.console, "{}\n", fill(messages.challenge_succeeded, &[&name(outcome.offender),
+                    &self.drawn_cards(&outcome.cards_drawn)]));
+            } else {
+                let challenger = name(outcome.challenger);
+                writeln!(self.console, "{}\n{}\n", fill(messages.challenge_failed, &[&challenger,
+                    &self.drawn_cards(&outcome.cards_drawn)]), fill(messages.skipped, &[&challenger]));
+            },
+            Report::JumpedIn { player, interrupted } =>
+                writeln!(self.console, "{}", fill(messages.jumped_in, &[&name(*player), &name(*interrupted)])),
+            Report::UnoCalled(player) => writeln!(self.console, "{}\n", fill(messages.called_uno, &[&name(*player)])),
+            Report::Caught(outcome) => if outcome.caught {
+                writeln!(self.console, "{}\n", fill(messages.caught, &[&name(outcome.accuser), &name(outcome.target),
+                    &name(outcome.penalized), &self.drawn_cards(&outcome.cards_drawn)]));
+            } else {
+                writeln!(self.console, "{}\n", fill(messages.false_accusation, &[&name(outcome.target),
+                    &name(outcome.penalized), &self.drawn_cards(&outcome.cards_drawn)]));
+            },
+            Report::Joined { player } => writeln!(self.console, "{}\n{}\n", fill(messages.joined, &[&name(*player)]),
+                fill(messages.turn_order, &[&turn_order(&renderer, game)])),
+            Report::Left(removed) => {
+                writeln!(self.console, "{}\n", fill(messages.left, &[&removed.name, &removed.cards.len()]));
+                if let Some(winner) = removed.winner {
+                    writeln!(self.console, "{}\n", fill(messages.last_player_standing, &[&name(winner)]));
+                    return;
+                }
+                // There's no point in the bots playing on with nobody watching
+                let humans_left = game.players().iter().any(|player| !self.bots.contains(&player.id()));
+                if !self.bots.contains(&removed.id) && !humans_left {
+                    writeln!(self.console, "{}", messages.nobody_at_keyboard);
+                    process::exit(0);
+                }
+                if removed.was_current {
+                    writeln!(self.console, "{}\n", fill(messages.turn_passes, &[game.player().name()]));
+                }
+                writeln!(self.console, "{}\n", fill(messages.turn_order, &[&turn_order(&renderer, game)]));
+            },
+            Report::SaveRequested { file } => self.save(game, file.as_deref())
+        }
+    }
+}
+
+// Narrates a game played by bots alone with every hand on show, for demos. Typing 'q'
+// and pressing enter stops the program before the next turn
+pub struct DemoNarrator<C: Console>
+{
+    inner: CliNarrator<C>,
+    quit: Arc<AtomicBool>
+}
+
+impl<C: Console> DemoNarrator<C>
+{
+    // Starts listening for 'q' on standard input
+    pub fn new(console: C, bots: BTreeSet<PlayerId>, options: CliOptions) -> DemoNarrator<C>
+    {
+        let quit = Arc::new(AtomicBool::new(false));
+        let listener = Arc::clone(&quit);
+        thread::spawn(move || {
+            let mut line = String::new();
+            while io::stdin().read_line(&mut line).is_ok_and(|read| read > 0) {
+                if line.trim().eq_ignore_ascii_case("q") {
+                    listener.store(true, Ordering::Relaxed);
+                }
+                line.clear();
+            }
+        });
+        DemoNarrator { inner: CliNarrator::new(console, bots, CliOptions { privacy: false, ..options }), quit }
+    }
+}
+
+impl<C: Console> Narrator for DemoNarrator<C>
+{
+    fn turn_starting(&mut self, game: &Game, seat: usize)
+    {
+        let renderer = self.inner.options.renderer;
+        if self.quit.load(Ordering::Relaxed) {
+            writeln!(self.inner.console, "{}", renderer.messages.demo_stopped);
+            std::process::exit(0);
+        }
+
+        writeln!(self.inner.console, "{}", fill(renderer.messages.demo_turn, &[&game.turn_number(),
+            &describe_top_card(&renderer, &game.spectator_view())]));
+        for player in game.players() {
+            if renderer.accessible {
+                writeln!(self.inner.console, "{}", fill(renderer.messages.spoken_holds,
+                    &[player.name(), &renderer.list(player.cards())]));
+            } else {
+                writeln!(self.inner.console, "{:>20}: {}", player.name(), renderer.list(player.cards()));
+            }
+        }
+        writeln!(self.inner.console);
+        self.inner.turn_starting(game, seat);
+    }
+
+    fn report(&mut self, game: &Game, report: &Report)
+    {
+        self.inner.report(game, report);
+    }
+
+    fn event(&mut self, game: &Game, event: &RecordedEvent)
+    {
+        self.inner.event(game, event);
+    }
+}
+
+// Where each turn starts in what's been written about a game, so it can be shown a turn at
+// a time
+struct TurnPages
+{
+    screen: MemoryConsole,
+    starts: Vec<(u32, usize)>
+}
+
+impl Narrator for TurnPages
+{
+    fn turn_starting(&mut self, game: &Game, _seat: usize)
+    {
+        if self.starts.last().map(|(turn, _)| *turn) != Some(game.turn_number()) {
+            self.starts.push((game.turn_number(), self.screen.output().len()));
+        }
+    }
+}
+
+// Show a recorded game with every hand on show, told the way the demo tells it, a turn at
+// a time. Playing on by itself, each turn stays up for `pace`. The whole game is played
+// again before any of it is shown, so a replay that doesn't come out the way it was
+// recorded is turned down rather than shown wrong
+pub fn play_back(console: &mut impl Console, replay: &Replay, options: CliOptions, pace: Duration)
+    -> Result<(), ReplayError>
+{
+    let renderer = options.renderer;
+    let messages = renderer.messages;
+    let options = CliOptions { bot_delay: BotDelay::default(), privacy: false, log_file: None, replay_file: None,
+        ..options };
+
+    let mut screen = MemoryConsole::default();
+    let mut game = replay.start()?;
+    let playback = Playback::new(replay);
+    let mut controllers = playback.controllers(&game);
+    let demo = DemoNarrator {
+        inner: CliNarrator::new(screen.clone(), controllers.keys().copied().collect(), options),
+        quit: Arc::new(AtomicBool::new(false))
+    };
+    let mut narrator = (TurnPages { screen: screen.clone(), starts: Vec::new() }, demo);
+    writeln!(screen, "{}", fill(messages.replay_intro, &[&replay.seed]));
+    announce_start(&mut screen, &renderer, &game, false);
+    let end = controller::run_game(&mut game, &mut controllers, &mut narrator)?;
+    playback.finish(&game)?;
+    announce_end(&mut screen, messages, &game, end);
+    print_summary(&mut screen, &renderer, &game, end, Duration::from_secs(replay.seconds));
+
+    // Each turn runs up to the start of the next, and the last to the end of the summary
+    let text = screen.output();
+    let starts = &narrator.0.starts;
+    let turns: Vec<(u32, &str)> = starts.iter().enumerate()
+        .map(|(index, (turn, start))| (*turn, &text[*start..starts.get(index + 1).map_or(text.len(), |next| next.1)]))
+        .collect();
+    console.write(&text[..starts.first().map_or(text.len(), |first| first.1)]);
+
+    let mut next = 0;
+    let mut auto = false;
+    while let Some((_, shown)) = turns.get(next) {
+        console.write(shown);
+        next += 1;
+        if next == turns.len() {
+            break;
+        }
+        loop {
+            let input = if auto {
+                match console.read_line_within(pace) {
+                    Ok(Some(line)) => line,
+                    _ => break
+                }
+            } else {
+                console.write(messages.replay_prompt);
+                read_line(console)
+            };
+            match input.trim().to_lowercase().as_str() {
+                "" => break,
+                "a" => {
+                    auto = !auto;
+                    if auto {
+                        writeln!(console, "{}", messages.replay_auto);
+                        break;
+                    }
+                },
+                "q" => return Ok(()),
+                number => match number.parse::<u32>().ok()
+                    .and_then(|number| turns.iter().position(|(turn, _)| *turn >= number)) {
+                    Some(index) => {
+                        next = index;
+                        break;
+                    },
+                    None => reject(console, fill(messages.replay_no_turn, &[&number, &turns[0].0,
+                        &turns[turns.len() - 1].0]))
+                }
+            }
+        }
+    }
+    writeln!(console, "{}", messages.replay_over);
+    Ok(())
+}
+
+pub fn announce_end(console: &mut impl Console, messages: &Messages, game: &Game, end: GameEnd)
+{
+    if let GameEnd::TurnLimitReached { winner } = end {
+        writeln!(console, "{}", messages.turn_limit_reached);
+        match winner {
+            Some(winner) =>
+                writeln!(console, "{}\n", fill(messages.fewest_points_winner, &[&player_name(game, winner)])),
+            None => writeln!(console, "{}\n", messages.nobody_wins)
+        }
+    }
+}
+
+// Each column is as wide as its heading, so a translation's longer words still line up.
+// A screen reader gets a sentence for each player instead of a table
+pub fn print_stats(console: &mut impl Console, renderer: &Renderer, game: &Game)
+{
+    let messages = renderer.messages;
+    let rows: Vec<[String; 8]> = game.stats().iter()
+        .map(|(id, stats)| [player_name(game, id).to_string(), stats.cards_played.to_string(),
+            stats.cards_drawn.to_string(), stats.times_skipped.to_string(), stats.penalty_cards_received.to_string(),
+            stats.penalty_cards_dealt.to_string(), stats.wilds_played.to_string(),
+            stats.longest_play_streak.to_string()])
+        .collect();
+    if renderer.accessible {
+        let [top, bottom] = messages.stats_headings;
+        for row in rows {
+            let counts: Vec<String> = (1..row.len())
+                .map(|column| {
+                    let heading = format!("{} {}", top[column], bottom[column]);
+                    fill(messages.spoken_stat, &[&heading.trim(), &row[column]])
+                })
+                .collect();
+            writeln!(console, "{}", fill(messages.spoken_stats, &[&row[0], &counts.join(", ")]));
+        }
+        writeln!(console);
+        return;
+    }
+
+    let mut widths = [20, 6, 6, 7, 9, 9, 5, 7];
+    for line in messages.stats_headings {
+        for (width, heading) in widths.iter_mut().zip(line) {
+            *width = (*width).max(heading.chars().count());
+        }
+    }
+    let mut print_row = |cells: [String; 8]| {
+        let mut row = format!("{:<1$}", cells[0], widths[0]);
+        for (cell, width) in cells.iter().zip(widths).skip(1) {
+            row += &format!(" {:>1$}", cell, width);
+        }
+        writeln!(console, "{}", row);
+    };
+    for line in messages.stats_headings {
+        print_row(line.map(String::from));
+    }
+    for row in rows {
+        print_row(row);
+    }
+    writeln!(console);
+}
+
+// Everything about a game once it's over: where everyone finished, what the winner
+// scored, how long it took and what each player did
+pub fn print_summary(console: &mut impl Console, renderer: &Renderer, game: &Game, end: GameEnd, took: Duration)
+{
+    let messages = renderer.messages;
+    writeln!(console, "{}", renderer.heading(messages.summary_title));
+    print_standings(console, renderer, game, end);
+    if let Some(winner) = end.winner() {
+        writeln!(console, "{}", fill(messages.round_points, &[&player_name(game, winner), &game.round_points()]));
+    }
+    // The turn that went over the limit never started
+    let turns = match end {
+        GameEnd::TurnLimitReached { .. } => game.turn_number() - 1,
+        _ => game.turn_number()
+    };
+    writeln!(console, "{}\n", fill(messages.summary_turns, &[&turns, &clock_time(took)]));
+    print_stats(console, renderer, game);
+}
+
+// The winner first, then everyone still playing from the fewest points in their hand
+fn print_standings(console: &mut impl Console, renderer: &Renderer, game: &Game, end: GameEnd)
+{
+    let messages = renderer.messages;
+    let mut players: Vec<_> = game.players().iter().collect();
+    players.sort_by_key(|player| (Some(player.id()) != end.winner(), player.hand_points(), player.number_of_cards()));
+    let rows: Vec<[String; 4]> = players.iter().enumerate()
+        .map(|(place, player)| [format!("{}.", place + 1), player.name().to_string(),
+            player.number_of_cards().to_string(), player.hand_points().to_string()])
+        .collect();
+    if renderer.accessible {
+        for [place, name, cards, points] in rows {
+            writeln!(console, "{}", fill(messages.spoken_standing, &[&place.trim_end_matches('.'), &name, &cards,
+                &points]));
+        }
+        return;
+    }
+
+    let mut widths = messages.standings_headings.map(|heading| heading.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut print_row = |[place, name, cards, points]: [String; 4]| {
+        writeln!(console, "{:>4$} {:<5$} {:>6$} {:>7$}", place, name, cards, points, widths[0], widths[1], widths[2],
+            widths[3]);
+    };
+    print_row(messages.standings_headings.map(String::from));
+    for row in rows {
+        print_row(row);
+    }
+}
+
+// e.g. "4:05", or "1:02:30" past an hour
+fn clock_time(took: Duration) -> String
+{
+    let seconds = took.as_secs();
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60)
+    }
+}
+
+// Play a whole game at the console, starting with the lobby menu when fewer than two
+// players have been seated. Everyone without a controller in the lobby plays at the
+// console, and with privacy on they pass it around between turns. The lobby is left as
+// the menu set it up, ready for a rematch
+pub fn run_game<C: Console + Clone + 'static>(console: &mut C, lobby: &mut Lobby, options: CliOptions, seed: u64)
+    -> Result<GameEnd, GameError>
+{
+    if lobby.number_of_players() < MIN_PLAYERS {
+        menu::run_lobby_menu(console, &options, lobby);
+    }
+    let mut game = lobby.clone().start_with_seed(seed)?;
+    play_dealt(console, &mut game, lobby.bots().clone(), options, seed, None)
+}
+
+// Watch the bots in `lobby` play a whole game on their own with their hands on show,
+// see `DemoNarrator`
+pub fn run_demo<C: Console + Clone + 'static>(console: &mut C, lobby: Lobby, options: CliOptions, seed: u64)
+    -> Result<GameEnd, GameError>
+{
+    let renderer = options.renderer;
+    let messages = renderer.messages;
+    let mut controllers = lobby.bots().clone();
+    let transcript = open_transcript(console, &options, &format!("A demo game with seed {}", seed));
+    let demo = DemoNarrator::new(console.clone(), controllers.keys().copied().collect(), options.clone());
+    let mut narrator = (demo, transcript);
+    let mut game = lobby.start_with_seed(seed)?;
+    writeln!(console, "{}", fill(messages.demo_intro, &[&seed]));
+    announce_start(console, &renderer, &game, false);
+
+    let started = console.now();
+    let end = controller::run_game(&mut game, &mut controllers, &mut narrator)?;
+    announce_end(console, messages, &game, end);
+    close_transcript(console, &options, narrator.1, &game, end);
+    let took = console.now() - started;
+    print_summary(console, &renderer, &game, end, took);
+    Ok(end)
+}
+
+// Say how a game that was just dealt starts, then play it. `in_match` is saved along with
+// the game if the players stop partway through
+fn play_dealt<C: Console + Clone + 'static>(console: &mut C, game: &mut Game, controllers: Controllers,
+    options: CliOptions, seed: u64, in_match: Option<SavedMatch>) -> Result<GameEnd, GameError>
+{
+    let renderer = options.renderer;
+    let messages = renderer.messages;
+    let options = resolve_privacy(console, options, game.players().len() - controllers.len());
+    announce_start(console, &renderer, game, options.privacy);
+    // Bots keep their hands to themselves, and with privacy on the hand is shown once
+    // the computer has been passed to its player
+    if game.start_effect().awaiting_wild_color && !controllers.contains_key(&game.player().id()) && !options.privacy {
+        writeln!(console, "{}\n{}", fill(messages.cards_of, &[game.player().name()]),
+            renderer.hand(game.player().cards()));
+    }
+    let heading = format!("A new game with seed {}", seed);
+    play(console, game, controllers, options, &heading, in_match, Some(seed))
+}
+
+// What the first card turned over did and who goes first. Everyone's penalty cards are
+// shown unless `privacy` is on
+fn announce_start(console: &mut impl Console, renderer: &Renderer, game: &Game, privacy: bool)
+{
+    let messages = renderer.messages;
+    let effect = game.start_effect();
+    if !effect.draw_for_deal.is_empty() {
+        writeln!(console, "\n{}", messages.draw_for_deal);
+        for (player, card) in effect.draw_for_deal.iter() {
+            writeln!(console, "{}",
+                fill(messages.drew_for_deal, &[&player_name(game, *player), &renderer.card(*card)]));
+        }
+    }
+    writeln!(console, "\n{}\n", fill(messages.starting_game, &[&player_name(game, effect.first_player)]));
+    for card in effect.redrawn.iter() {
+        writeln!(console, "{}", fill(messages.put_back, &[&renderer.card(*card)]));
+    }
+    writeln!(console, "{}\n", fill(messages.top_card, &[&renderer.card(effect.top_card)]));
+
+    if effect.reversed {
+        writeln!(console, "{}\n", fill(messages.reversed, &[&messages.direction(game.direction())]));
+    }
+    if let Some((player, cards)) = &effect.penalty {
+        let cards = if privacy { fill(messages.many_cards, &[&cards.len()]) } else { renderer.describe(cards) };
+        writeln!(console, "{}", fill(messages.drew_cards, &[&player_name(game, *player), &cards]));
+    }
+    if let Some(player) = effect.skipped {
+        writeln!(console, "{}\n", fill(messages.skipped, &[&player_name(game, player)]));
+        writeln!(console, "{}\n", fill(messages.new_starting_player, &[&player_name(game, effect.starting_player)]));
+    }
+    writeln!(console, "{}\n", fill(messages.turn_order, &[&turn_order(renderer, game)]));
+}
+
+// Play games with the same players until they've had enough, counting everyone's wins.
+// Each game starts from the lobby afresh, so nothing carries over but the seating
+pub fn run_session<C: Console + Clone + 'static>(console: &mut C, mut lobby: Lobby, options: CliOptions, seed: u64)
+    -> Result<(), GameError>
+{
+    let messages = options.renderer.messages;
+    let mut wins: BTreeMap<PlayerId, u32> = BTreeMap::new();
+    for round in 0.. {
+        // The first game is the one `seed` replays on its own
+        let round_seed = if round == 0 { seed } else { game_seed(seed, round) };
+        let end = run_game(console, &mut lobby, options.clone(), round_seed)?;
+        if let Some(winner) = end.winner() {
+            *wins.entry(winner).or_default() += 1;
+        }
+        let tally: Vec<String> = lobby.seating().iter()
+            .map(|player| format!("{} {}", player.name(), wins.get(&player.id()).copied().unwrap_or_default()))
+            .collect();
+        writeln!(console, "{}\n", fill(messages.wins_so_far, &[&tally.join(" – ")]));
+
+        if !ask_yes_no(console, messages, messages.play_again) {
+            break;
+        }
+        rotate_start(console, messages, &mut lobby)?;
+    }
+    Ok(())
+}
+
+// Offer to let someone else go first in a rematch: the next seat starts when the game
+// picks a seat to start, and otherwise everyone moves one seat along
+fn rotate_start(console: &mut impl Console, messages: &Messages, lobby: &mut Lobby) -> Result<(), GameError>
+{
+    let players = lobby.number_of_players();
+    if let StartingPlayer::Seat(seat) = lobby.config().starting_player {
+        if ask_yes_no(console, messages, messages.pass_deal) {
+            let mut config = lobby.config().clone();
+            config.starting_player = StartingPlayer::Seat((seat + 1) % players);
+            lobby.set_config(config);
+        }
+    } else if ask_yes_no(console, messages, messages.move_seats) {
+        lobby.move_player(0, players - 1)?;
+    }
+    Ok(())
+}
+
+// Play rounds with the same players until someone has `target` points, see
+// `matches::Match`. The first round is the game `seed` replays on its own
+pub fn run_match<C: Console + Clone + 'static>(console: &mut C, mut lobby: Lobby, options: CliOptions, seed: u64,
+    target: u32) -> Result<(), GameError>
+{
+    if lobby.number_of_players() < MIN_PLAYERS {
+        menu::run_lobby_menu(console, &options, &mut lobby);
+    }
+    let mut series = Match::new(lobby);
+    series.set_target(target);
+    play_rounds(console, series, options, seed)
+}
+
+// Finish a saved round of a match, then play the rest of the match
+pub fn resume_match<C: Console + Clone + 'static>(console: &mut C, mut game: Game, bots: Controllers,
+    options: CliOptions, saved: &SavedMatch) -> Result<(), GameError>
+{
+    let mut series = Match::resume(Lobby::from_game(&game, bots.clone()), saved);
+    resume_game(console, &mut game, bots, options.clone(), Some(saved.clone()))?;
+    if score_round(console, &options.renderer, &mut series, &game) {
+        return Ok(());
+    }
+    play_rounds(console, series, options, rand::random())
+}
+
+// Deal round after round until the match is won, letting the players look at the
+// standings or stop between rounds
+fn play_rounds<C: Console + Clone + 'static>(console: &mut C, mut series: Match, options: CliOptions, seed: u64)
+    -> Result<(), GameError>
+{
+    let messages = options.renderer.messages;
+    loop {
+        let round = series.rounds_started();
+        let round_seed = if round == 0 { seed } else { game_seed(seed, round as u64) };
+        if round > 0 && !between_rounds(console, &options, &series, round_seed)? {
+            return Ok(());
+        }
+
+        let mut game = series.start_round_with_seed(round_seed)?;
+        writeln!(console, "\n{}", fill(messages.match_round, &[&series.rounds_started(), &series.target()]));
+        let bots = series.lobby().bots().clone();
+        play_dealt(console, &mut game, bots, options.clone(), round_seed, Some(series.save()))?;
+        if score_round(console, &options.renderer, &mut series, &game) {
+            return Ok(());
+        }
+    }
+}
+
+// Add up a finished round and show where the match stands. Returns whether it's over
+fn score_round(console: &mut impl Console, renderer: &Renderer, series: &mut Match, game: &Game) -> bool
+{
+    series.record_round(game);
+    print_match(console, renderer, series);
+    match series.winner() {
+        Some(winner) => {
+            let name = player_name(game, winner);
+            writeln!(console, "{}\n", fill(renderer.messages.match_won, &[&name, &series.total(winner)]));
+            true
+        },
+        None => false
+    }
+}
+
+// Everyone's points in the match, the most first
+fn print_match(console: &mut impl Console, renderer: &Renderer, series: &Match)
+{
+    let messages = renderer.messages;
+    let name = |id| series.lobby().seating().iter().find(|player| player.id() == id)
+        .map_or(String::new(), |player| player.name().to_string());
+    let rows: Vec<[String; 4]> = series.standings().into_iter().enumerate()
+        .map(|(place, (id, total))| [format!("{}.", place + 1), name(id), series.last_round_points(id).to_string(),
+            total.to_string()])
+        .collect();
+    writeln!(console, "{}", renderer.heading(messages.match_title));
+    if renderer.accessible {
+        for [place, name, round, total] in rows {
+            writeln!(console, "{}", fill(messages.spoken_match_standing, &[&place.trim_end_matches('.'), &name,
+                &round, &total]));
+        }
+    } else {
+        let mut widths = messages.match_headings.map(|heading| heading.chars().count());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let mut print_row = |[place, name, round, total]: [String; 4]| {
+            writeln!(console, "{:>4$} {:<5$} {:>6$} {:>7$}", place, name, round, total, widths[0], widths[1],
+                widths[2], widths[3]);
+        };
+        print_row(messages.match_headings.map(String::from));
+        for row in rows {
+            print_row(row);
+        }
+    }
+    writeln!(console);
+}
+
+// Say who deals next, then let the players carry on, look at the standings or save the
+// match to finish another time. Returns whether to play the next round
+fn between_rounds(console: &mut impl Console, options: &CliOptions, series: &Match, seed: u64)
+    -> Result<bool, GameError>
+{
+    let messages = options.renderer.messages;
+    if let Some(dealer) = series.next_dealer().and_then(|seat| series.lobby().seating().get(seat)) {
+        writeln!(console, "{}\n", fill(messages.next_round, &[dealer.name()]));
+    }
+    loop {
+        writeln!(console, "{}", messages.lobby_title);
+        for (index, option) in messages.between_rounds_options.iter().enumerate() {
+            writeln!(console, "{}. {}", index + 1, option);
+        }
+        console.write(messages.choose_option);
+        match normalize_input(&read_line(console)).as_str() {
+            "1" => {
+                writeln!(console);
+                return Ok(true);
+            },
+            "2" => {
+                writeln!(console);
+                print_match(console, &options.renderer, series);
+            },
+            "3" => {
+                writeln!(console);
+                save_match(console, options, series, seed)?;
+            },
+            _ => reject(console, format!("{}\n", fill(messages.choice_rejected, &[&3])))
+        }
+    }
+}
+
+// Deal the next round and save it along with the match, then stop. Nothing is dealt for
+// good if the match can't be saved, so the players can carry on
+#[cfg(feature = "serde")]
+fn save_match(console: &mut impl Console, options: &CliOptions, series: &Match, seed: u64) -> Result<(), GameError>
+{
+    let messages = options.renderer.messages;
+    let Some(path) = &options.save_file else {
+        writeln!(console, "{}\n", messages.match_nowhere_to_save);
+        return Ok(());
+    };
+    let mut next = series.clone();
+    let game = next.start_round_with_seed(seed)?;
+    let save = SaveFile::new(&game, next.lobby().bots().keys().copied().collect(), rand::random(), Some(next.save()));
+    if let Err(error) = save.write(path) {
+        writeln!(console, "{}\n", fill(messages.save_failed, &[&path.display(), &error]));
+        return Ok(());
+    }
+    writeln!(console, "{}\n{}", fill(messages.saved, &[&path.display()]), messages.save_waiting);
+    process::exit(0);
+}
+
+#[cfg(not(feature = "serde"))]
+fn save_match(console: &mut impl Console, options: &CliOptions, _series: &Match, _seed: u64) -> Result<(), GameError>
+{
+    writeln!(console, "{}\n", options.renderer.messages.save_unavailable);
+    Ok(())
+}
+
+// Carry on a game that was saved, with `bots` playing for the players bots played for
+// before, see `save::SaveFile`. `in_match` is the match it's a round of, if it is one
+pub fn resume_game<C: Console + Clone + 'static>(console: &mut C, game: &mut Game, bots: Controllers,
+    options: CliOptions, in_match: Option<SavedMatch>) -> Result<GameEnd, GameError>
+{
+    let renderer = options.renderer;
+    let messages = renderer.messages;
+    let options = resolve_privacy(console, options, game.players().len() - bots.len());
+    writeln!(console, "\n{}\n", fill(messages.resuming, &[&game.turn_number(), game.player().name()]));
+    writeln!(console, "{}\n{}\n", fill(messages.top_card, &[&renderer.top_card(game.top_card(), game.active_color())]),
+        fill(messages.turn_order, &[&turn_order(&renderer, game)]));
+    if game.pending_action() == Some(PendingAction::AwaitingWildColor) && !bots.contains_key(&game.player().id())
+        && !options.privacy {
+        writeln!(console, "{}\n{}", fill(messages.cards_of, &[game.player().name()]),
+            renderer.hand(game.player().cards()));
+    }
+    let heading = format!("Picking a saved game back up on turn {}", game.turn_number());
+    play(console, game, bots, options, &heading, in_match, None)
+}
+
+// Play a puzzle from a scenario file, with `bots` playing for the players it says are
+// bots, then say whether the player whose turn it was at the start met its goal
+pub fn play_scenario<C: Console + Clone + 'static>(console: &mut C, scenario: &Scenario, game: &mut Game,
+    bots: Controllers, options: CliOptions) -> Result<GameEnd, GameError>
+{
+    let renderer = options.renderer;
+    let messages = renderer.messages;
+    let options = resolve_privacy(console, options, game.players().len() - bots.len());
+    let player = game.player();
+    let solver = player.id();
+    writeln!(console, "\n{}", fill(messages.scenario_title, &[&scenario.name]));
+    if !scenario.description.is_empty() {
+        writeln!(console, "{}", scenario.description);
+    }
+    match scenario.goal {
+        Some(Goal::Win) => writeln!(console, "{}", fill(messages.goal_win, &[player.name()])),
+        Some(Goal::WinWithin(turns)) =>
+            writeln!(console, "{}", fill(messages.goal_win_within, &[player.name(), &turns])),
+        None => ()
+    }
+    let top_card = renderer.top_card(game.top_card(), game.active_color());
+    writeln!(console, "\n{}\n{}\n", fill(messages.top_card, &[&top_card]),
+        fill(messages.turn_order, &[&turn_order(&renderer, game)]));
+
+    let end = play(console, game, bots, options, &format!("Playing the scenario '{}'", scenario.name), None, None)?;
+    if let Some(goal) = scenario.goal {
+        writeln!(console, "{}\n", if goal.met(game, solver) { messages.goal_met } else { messages.goal_missed });
+    }
+    Ok(end)
+}
+
+// Privacy only matters with more than one person taking turns at a real terminal
+fn resolve_privacy(console: &impl Console, options: CliOptions, humans: usize) -> CliOptions
+{
+    CliOptions { privacy: options.privacy && humans > 1 && console.is_tty(), ..options }
+}
+
+// Everyone not in `controllers` plays at the console. The game is logged under `heading`
+// if there's a log file. A game just dealt from `seed` is written to the replay file if
+// there is one; other games can't be played back
+fn play<C: Console + Clone + 'static>(console: &mut C, game: &mut Game, mut controllers: Controllers,
+    options: CliOptions, heading: &str, in_match: Option<SavedMatch>, seed: Option<u64>) -> Result<GameEnd, GameError>
+{
+    let narrator = CliNarrator::new(console.clone(), controllers.keys().copied().collect(), options.clone())
+        .in_match(in_match);
+    for player in game.players() {
+        controllers.entry(player.id())
+            .or_insert_with(|| Box::new(ConsoleController::new(console.clone(), options.clone())));
+    }
+    let recording = seed.filter(|_| options.replay_file.is_some()).map(|seed| Recording::start(game, seed));
+    if let Some(recording) = &recording {
+        controllers = recording.record(controllers);
+    }
+    let mut narrators = (narrator, open_transcript(console, &options, heading));
+    let started = console.now();
+    let end = controller::run_game(game, &mut controllers, &mut narrators)?;
+    announce_end(console, options.renderer.messages, game, end);
+    close_transcript(console, &options, narrators.1, game, end);
+
+    let took = console.now() - started;
+    if let Some(recording) = recording {
+        write_replay(console, &options, &recording.finish(game, took));
+    }
+    print_summary(console, &options.renderer, game, end, took);
+    Ok(end)
+}
+
+// Only reported when it can't be written, since the game is over either way
+#[cfg(feature = "serde")]
+fn write_replay(console: &mut impl Console, options: &CliOptions, replay: &Replay)
+{
+    if let Some(path) = &options.replay_file {
+        if let Err(error) = replay.write(path) {
+            writeln!(console, "{}\n", fill(options.renderer.messages.replay_write_failed, &[&path.display(), &error]));
+        }
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn write_replay(_console: &mut impl Console, _options: &CliOptions, _replay: &Replay) {}
+
+// The log file was checked before the game, so it's only reported if it can't be opened
+// now, and the game goes on without it
+pub fn open_transcript(console: &mut impl Console, options: &CliOptions, heading: &str) -> Option<Transcript>
+{
+    let path = options.log_file.as_ref()?;
+    match Transcript::open(path) {
+        Ok(mut transcript) => {
+            transcript.note(&format!("{}. Times are UTC", heading));
+            Some(transcript)
+        },
+        Err(error) => {
+            writeln!(console, "{}\n", fill(options.renderer.messages.log_open_failed, &[&path.display(), &error]));
+            None
+        }
+    }
+}
+
+// Log the result, and say so if anything couldn't be written
+pub fn close_transcript(console: &mut impl Console, options: &CliOptions, transcript: Option<Transcript>, game: &Game,
+    end: GameEnd)
+{
+    let (Some(mut transcript), Some(path)) = (transcript, options.log_file.as_ref()) else {
+        return;
+    };
+    transcript.game_over(game, end);
+    if let Some(error) = transcript.error() {
+        writeln!(console, "{}\n", fill(options.renderer.messages.log_write_failed, &[&path.display(), error]));
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::controller::FirstMoveBot;
+
+    fn cards(names: &[&str]) -> Vec<Card>
+    {
+        names.iter().map(|name| name.parse().expect("Test cards are spelled right")).collect()
+    }
+
+    // Alice, Bob and Carol, as many as there are hands, with Alice going first. `top` is
+    // turned over, everyone is dealt their hand from `hands`, and then the deck hands out
+    // `draws`
+    fn scripted(top: &str, hands: &[&[&str]], draws: &[&str]) -> Game
+    {
+        let mut lobby = Lobby::with_players(["Alice", "Bob", "Carol"].into_iter().take(hands.len())).unwrap();
+        lobby.set_config(GameConfig {
+            starting_player: StartingPlayer::Seat(0),
+            hand_size: Some(hands[0].len()),
+            ..GameConfig::default()
+        });
+        let mut deck = cards(&[top]);
+        for hand in hands {
+            deck.extend(cards(hand));
+        }
+        deck.extend(cards(draws));
+        lobby.start_with_deck(0, deck).unwrap()
+    }
+
+    #[test]
+    fn input_is_tidied_up()
+    {
+        assert_eq!(normalize_input("  Draw \n"), "draw");
+        assert_eq!(normalize_input("RED   5"), "red 5");
+        assert_eq!(normalize_input("p3"), "3");
+        assert_eq!(normalize_input("P 12"), "12");
+        assert_eq!(normalize_input("play Wild  Blue"), "wild blue");
+        // A word that only starts with a p is left alone
+        assert_eq!(normalize_input("pass"), "pass");
+        assert_eq!(normalize_input(""), "");
+    }
+
+    #[test]
+    fn short_forms_are_spelled_out()
+    {
+        for (alias, command) in ALIASES {
+            assert_eq!(normalize_input(alias), command);
+            assert_eq!(normalize_input(&alias.to_uppercase()), command);
+        }
+        assert_eq!(normalize_input("q now"), "quit now");
+        assert_eq!(normalize_input("dr"), "dr");
+    }
+
+    #[test]
+    fn near_misses_suggest_a_command()
+    {
+        assert_eq!(closest_command("drwa"), Some("draw"));
+        assert_eq!(closest_command("dra"), Some("draw"));
+        assert_eq!(closest_command("hsitory"), Some("history"));
+        assert_eq!(closest_command("statsu"), Some("status"));
+        assert_eq!(closest_command("sve"), Some("save"));
+        // Exact commands and words that are too far off get nothing
+        assert_eq!(closest_command("draw"), None);
+        assert_eq!(closest_command("xyz"), None);
+        assert_eq!(closest_command("dr"), None);
+    }
+
+    #[test]
+    fn edit_distances()
+    {
+        assert_eq!(edit_distance("draw", "draw"), 0);
+        assert_eq!(edit_distance("draw", "drwa"), 1);
+        assert_eq!(edit_distance("draw", "dra"), 1);
+        assert_eq!(edit_distance("", "uno"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn the_turn_order_has_everyones_card_count()
+    {
+        let alice = ["red +2", "red reverse", "green 1"];
+        let mut game = scripted("red 5", &[&alice, &["blue 1", "blue 2", "blue 3"], &["red 1", "yellow 2", "yellow 3"]],
+            &["green 2", "green 3"]);
+        assert_eq!(turn_order(&Renderer::PLAIN, &game), "[Alice·3] -> Bob·3 -> Carol·3 ↻ clockwise");
+
+        // Bob takes the Draw Two and loses his turn
+        game.play(0).unwrap();
+        assert_eq!(turn_order(&Renderer::PLAIN, &game), "[Carol·3] -> Alice·2 -> Bob·5 ↻ clockwise");
+
+        game.play(0).unwrap();
+        game.play(0).unwrap();
+        assert_eq!(turn_order(&Renderer::PLAIN, &game), "[Carol·2] -> Bob·5 -> Alice·1! ↺ counter-clockwise");
+        let ascii = Renderer { unicode: false, ..Renderer::PLAIN };
+        assert_eq!(turn_order(&ascii, &game), "[Carol:2] -> Bob:5 -> Alice:1! (counter-clockwise)");
+    }
+
+    #[test]
+    fn a_game_in_spanish()
+    {
+        let mut game = scripted("red 5", &[&["red 1", "red 2"], &["blue 3", "red 4"]], &[]);
+        let options = CliOptions {
+            renderer: Renderer { messages: &Messages::SPANISH, ..Renderer::PLAIN },
+            ..CliOptions::default()
+        };
+        let mut console = MemoryConsole::new(["9", "uno", "1", "2", "1"]);
+        let end = resume_game(&mut console, &mut game, Controllers::new(), options, None);
+        assert_eq!(end, Ok(GameEnd::Won(game.players()[0].id())));
+
+        let output = console.output();
+        let lines: Vec<&str> = output.lines().collect();
+        for expected in [
+            "¡Retomando la partida en el turno 1! Le toca a Alice",
+            "Turno 2: ¡le toca a Bob!",
+            "Orden de turno: [Bob·2] -> Alice·1!",
+            "La carta de arriba es 1 Rojo",
+            "Cartas de Bob:",
+            "1. 3 Azul",
+            "1 carta jugable",
+            "Elige una carta o escribe 'draw': Escribe un número de carta del 1 al 2, o 'draw' para robar",
+            "Elige una carta o escribe 'draw': ¡Alice cantó Uno!",
+            "Elige una carta o escribe 'draw': ¡Bob jugó la carta 4 Rojo!",
+            "¡Alice ha jugado su última carta! ¡Es quien gana!",
+            "Alice suma 3 puntos por las cartas que quedan en las manos de los demás"
+        ] {
+            assert!(lines.contains(&expected), "'{}' isn't in:\n{}", expected, output);
+        }
+        assert!(!output.contains("Red") && !output.contains("Turn "));
+    }
+
+    // A memory console with a clock of its own, where a line of '~' lets a timed read run
+    // out. No time passes otherwise
+    #[derive(Debug, Clone)]
+    struct TimedConsole
+    {
+        inner: MemoryConsole,
+        started: Instant,
+        waited: Rc<Cell<Duration>>
+    }
+
+    impl TimedConsole
+    {
+        fn new(lines: &[&str]) -> TimedConsole
+        {
+            let inner = MemoryConsole::new(lines.iter().copied());
+            TimedConsole { inner, started: Instant::now(), waited: Rc::default() }
+        }
+    }
+
+    impl Console for TimedConsole
+    {
+        fn read_line(&mut self) -> io::Result<String>
+        {
+            self.inner.read_line()
+        }
+
+        fn read_line_within(&mut self, timeout: Duration) -> io::Result<Option<String>>
+        {
+            let line = self.inner.read_line()?;
+            if line == "~\n" {
+                self.waited.set(self.waited.get() + timeout);
+                return Ok(None);
+            }
+            Ok(Some(line))
+        }
+
+        fn now(&self) -> Instant
+        {
+            self.started + self.waited.get()
+        }
+
+        fn write(&mut self, text: &str)
+        {
+            self.inner.write(text);
+        }
+    }
+
+    #[test]
+    fn a_player_who_runs_out_of_time_draws()
+    {
+        let mut game = scripted("red 5", &[&["blue 1", "blue 2"], &["red 4", "green 3"]], &["green 9"]);
+        let bob = game.players()[1].id();
+        let mut bots = Controllers::new();
+        bots.insert(bob, Box::new(FirstMoveBot) as Box<dyn PlayerController>);
+        let options = CliOptions { turn_timer: Some(Duration::from_secs(30)), ..CliOptions::default() };
+        let mut console = TimedConsole::new(&["~", "~", "~", "quit", "y"]);
+        let end = resume_game(&mut console, &mut game, bots, options, None);
+        assert_eq!(end, Ok(GameEnd::LastPlayerStanding(bob)));
+
+        let output = console.inner.output();
+        let lines: Vec<&str> = output.lines().collect();
+        let countdown: Vec<&str> = lines.iter().copied()
+            .filter(|line| line.contains("seconds left") || line.starts_with("Time's up"))
+            .collect();
+        // Alice's first turn runs out, and her second starts with all her time back
+        assert_eq!(countdown, ["30 seconds left", "20 seconds left", "Only 10 seconds left!", "Time's up for Alice!",
+            "30 seconds left"]);
+        assert!(lines.contains(&"You drew a Green 9! It's not playable on the current card!"));
+        // Only the time Alice ran out of has gone by, and none on Bob's turn
+        assert!(lines.contains(&"4 turns in 0:30"));
+    }
+
+    #[test]
+    fn a_human_and_two_bots_play_to_the_end()
+    {
+        let mut lobby = Lobby::new();
+        lobby.add_player("Alice").unwrap();
+        assert!(lobby.add_bot("Bob", Box::new(FirstMoveBot)));
+        assert!(lobby.add_bot("Carol", Box::new(FirstMoveBot)));
+        assert!(!lobby.add_bot("alice", Box::new(FirstMoveBot)));
+        lobby.set_config(GameConfig { starting_player: StartingPlayer::Seat(0), hand_size: Some(2),
+            ..GameConfig::default() });
+        let deck = cards(&["red 5", "red 1", "red 2", "blue 3", "red 4", "green 6", "red 7"]);
+        let mut game = lobby.clone().start_with_deck(0, deck).unwrap();
+
+        let mut console = MemoryConsole::new(["uno", "1", "1"]);
+        let end = resume_game(&mut console, &mut game, lobby.bots().clone(), CliOptions::default(), None);
+        assert_eq!(end, Ok(GameEnd::Won(game.players()[0].id())));
+        assert_eq!(console.remaining_input(), 0);
+
+        // Only Alice is asked anything, and the bots' hands are never shown
+        let output = console.output();
+        let lines: Vec<&str> = output.lines().collect();
+        for expected in ["Bob played a Red 4!", "Carol played a Red 7!",
+            "Alice has played their last card! They are the winner!"] {
+            assert!(lines.contains(&expected), "'{}' isn't in:\n{}", expected, output);
+        }
+        assert_eq!(output.matches("Choose a card or type 'draw': ").count(), 3);
+        assert!(!output.contains("Bob's Cards:") && !output.contains("Carol's Cards:"), "{}", output);
+    }
+
+    #[test]
+    fn status_recaps_the_game_without_using_up_the_turn()
+    {
+        let mut game = scripted("red 5", &[&["red 1", "red 2"], &["blue 3", "red 4"]], &[]);
+        let mut console = MemoryConsole::new(["status", "uno", "1", "s", "2", "1"]);
+        let end = resume_game(&mut console, &mut game, Controllers::new(), CliOptions::default(), None);
+        assert_eq!(end, Ok(GameEnd::Won(game.players()[0].id())));
+        assert_eq!(console.remaining_input(), 0);
+
+        // Each recap is written where the answer to the prompt goes, up to a blank line
+        let output = console.output();
+        let status: Vec<&str> = output.split("Choose a card or type 'draw': ")
+            .filter(|answer| answer.starts_with("Turn "))
+            .map(|answer| answer.split("\n\n").next().unwrap())
+            .collect();
+        assert_eq!(status, [
+            "Turn 1: It's Alice's turn, and play goes Clockwise\nThe top card is a Red 5\nPlayers in turn order:\n  \
+                Alice: 2 cards\n  Bob: 2 cards",
+            "Turn 2: It's Bob's turn, and play goes Clockwise\nThe top card is a Red 1\nPlayers in turn order:\n  \
+                Bob: 2 cards\n  Alice: 1 card, called Uno"
+        ]);
+    }
+
+    #[test]
+    fn a_sorted_hand_is_shown_and_played_from()
+    {
+        let mut game = scripted("red 5", &[&["wild", "blue 2", "red 3"], &["green 3", "green 4", "green 6"]], &[]);
+        let mut console = MemoryConsole::new(["sort", "1", "quit", "y"]);
+        resume_game(&mut console, &mut game, Controllers::new(), CliOptions::default(), None).unwrap();
+
+        let output = console.output();
+        let sorted = output.split("Choose a card or type 'draw': ").nth(1).unwrap();
+        assert!(sorted.contains("Alice's Cards:\n1. Red 3 *\n2. Blue 2\n3. Wildcard *\n"), "{}", output);
+        assert!(output.contains("Alice played a Red 3!"), "{}", output);
+    }
+
+    #[test]
+    fn history_lists_the_last_turns()
+    {
+        let hands: &[&[&str]] = &[&["red 9", "blue 1", "green 2"], &["wild", "green 4", "green 5"],
+            &["blue +2", "yellow 2", "yellow 3"]];
+        let mut game = scripted("red 5", hands, &["yellow 7", "yellow 8"]);
+        let options = CliOptions { history_length: 3, ..CliOptions::default() };
+        let mut console = MemoryConsole::new(["history", "1", "1", "blue", "1", "history", "quit", "y", "quit", "y"]);
+        resume_game(&mut console, &mut game, Controllers::new(), options, None).unwrap();
+        assert_eq!(console.remaining_input(), 0);
+
+        let output = console.output();
+        let history: Vec<&str> = output.split("Choose a card or type 'draw': ")
+            .filter_map(|answer| answer.split("\n\n").next())
+            .filter(|answer| answer.starts_with("The last") || answer.starts_with("Nobody"))
+            .collect();
+        // Only the last 3 turns, so Alice's first play is left out
+        assert_eq!(history, [
+            "Nobody has had a turn yet",
+            "The last few turns:\nT2: Bob played a Wildcard and chose Blue\nT3: Carol played a Blue Draw 2\n\
+                T4: Alice drew 2 penalty cards and lost their turn"
+        ]);
+    }
+
+    #[test]
+    fn cards_can_be_played_by_name()
+    {
+        let hands: &[&[&str]] = &[&["red skip", "blue skip", "wild", "red 7"],
+            &["green 1", "green 2", "green 4", "green 6"]];
+        let mut game = scripted("red 5", hands, &[]);
+        let mut console = MemoryConsole::new(["skip", "green 7", "banana", "wild blue", "quit", "y"]);
+        let end = resume_game(&mut console, &mut game, Controllers::new(), CliOptions::default(), None);
+        assert_eq!(end, Ok(GameEnd::LastPlayerStanding(game.players()[0].id())));
+        assert_eq!(console.remaining_input(), 0);
+
+        let output = console.output();
+        let answers: Vec<&str> = output.lines()
+            .filter_map(|line| line.strip_prefix("Choose a card or type 'draw': "))
+            .collect();
+        assert_eq!(answers, [
+            "Which one? You have a Red Skip and a Blue Skip",
+            "You don't have a Green 7",
+            "Please enter a card index in the range 1 - 4, a card like 'red 5' or 'wild blue', or one of 'draw', \
+                'sort', 'uno', 'catch <name>', 'jump <name>', 'join <name>', 'leave <name>', 'quit', 'save', \
+                'history', 'status', 'rules', 'help', 'hint'",
+            "The wildcard color is now Blue",
+            "Concede the game? Your cards will leave play (y/n): Bob left the game! Their 4 cards are out of play"
+        ]);
+        // The color came with the card, so it wasn't asked for
+        assert!(!output.contains("Select a color for the wildcard:"));
+        assert_eq!(game.players()[0].cards(), cards(&["red skip", "blue skip", "red 7"]));
+    }
+
+    // Alice's hand and what's said about it, then Bob's, for the first turn of each
+    fn marked_hands(renderer: Renderer) -> Vec<String>
+    {
+        let mut game = scripted("red 5", &[&["red 1", "blue 2", "wild"], &["green 3", "green 4", "green 6"]], &[]);
+        let options = CliOptions { renderer, ..CliOptions::default() };
+        let mut console = MemoryConsole::new(["1", "quit", "y"]);
+        resume_game(&mut console, &mut game, Controllers::new(), options, None).unwrap();
+
+        let output = console.output();
+        output.split("'s Cards:\n").skip(1)
+            .map(|hand| hand.lines().take_while(|line| !line.starts_with("Type ")).collect::<Vec<&str>>().join("\n"))
+            .collect()
+    }
+
+    #[test]
+    fn playable_cards_are_marked()
+    {
+        assert_eq!(marked_hands(Renderer::PLAIN), [
+            "1. Red 1 *\n2. Blue 2\n3. Wildcard *\n2 playable cards",
+            "1. Green 3\n2. Green 4\n3. Green 6\nNo playable cards, so you must draw"
+        ]);
+        assert_eq!(marked_hands(Renderer::COMPACT), [
+            "1:🟥1* 2:🟦2 3:🃏*\n2 playable cards",
+            "1:🟩3 2:🟩4 3:🟩6\nNo playable cards, so you must draw"
+        ]);
+        // The marker goes after the color codes, so it isn't painted
+        assert_eq!(marked_hands(Renderer::COLORED)[0], "1. \x1b[1;31mRed 1\x1b[0m *\n2. \x1b[1;34mBlue 2\x1b[0m\n\
+            3. \x1b[1;97;40mWildcard\x1b[0m *\n2 playable cards");
+    }
+
+    #[test]
+    fn penalty_cards_are_pointed_out()
+    {
+        let hands: &[&[&str]] = &[&["red +2", "red 1", "red 9"], &["green 3", "green 4", "yellow 6"]];
+        let mut game = scripted("red 5", hands, &["blue 2", "wild"]);
+        let mut console = MemoryConsole::new(["1", "1", "sort", "quit", "y"]);
+        resume_game(&mut console, &mut game, Controllers::new(), CliOptions::default(), None).unwrap();
+        assert_eq!(console.remaining_input(), 0);
+
+        // Bob's hand as it's shown, then again once he sorts it
+        let output = console.output();
+        let hands: Vec<&str> = output.split("Bob's Cards:\n").skip(1)
+            .map(|hand| hand.split("\n1 playable card").next().unwrap())
+            .collect();
+        assert_eq!(hands, [
+            "1. Green 3\n2. Green 4\n3. Yellow 6\n4. Blue 2 (new)\n5. Wildcard * (new)",
+            "1. Green 3\n2. Green 4\n3. Blue 2 (new)\n4. Yellow 6\n5. Wildcard * (new)"
+        ]);
+        // Alice didn't draw anything, so none of her cards are marked
+        let mut alice = output.split("Alice's Cards:\n").skip(1).map(|hand| hand.split(" playable").next().unwrap());
+        assert!(!alice.any(|hand| hand.contains("(new)")), "{}", output);
+    }
+
+    #[test]
+    fn a_short_match_is_played_out()
+    {
+        let mut lobby = Lobby::new();
+        assert!(lobby.add_bot("Bob", Box::new(FirstMoveBot)));
+        assert!(lobby.add_bot("Carol", Box::new(FirstMoveBot)));
+        // Between the rounds, look at the standings and then carry on
+        let mut console = MemoryConsole::new(["2", "1"]);
+        run_match(&mut console, lobby, CliOptions::default(), 7, 150).unwrap();
+        assert_eq!(console.remaining_input(), 0);
+
+        let output = console.output();
+        let progress: Vec<&str> = output.lines()
+            .filter(|line| line.starts_with("Round") || line.starts_with("Next round") || line.contains(" scores ")
+                || line.contains("wins the match") || line.starts_with("1. Bob "))
+            .collect();
+        assert_eq!(progress, [
+            "Round 1! The first to 150 points wins the match",
+            "1. Bob        0      0",
+            "Bob scores 117 points for the cards left in everyone else's hands",
+            "1. Bob           117   117",
+            "Next round: Carol deals",
+            "1. Bob           117   117",
+            "Round 2! The first to 150 points wins the match",
+            "1. Bob        0      0",
+            "Bob scores 122 points for the cards left in everyone else's hands",
+            "1. Bob           122   239",
+            "Bob wins the match with 239 points!"
+        ]);
+    }
+
+    // Alice starts on a Red 5 with Draw Four challenges on, and Bob holds greens
+    fn draw_four_game(hand: &[&str], draw_four_warning: bool, input: &[&str]) -> String
+    {
+        let mut lobby = Lobby::with_players(["Alice", "Bob"]).unwrap();
+        lobby.set_config(GameConfig {
+            starting_player: StartingPlayer::Seat(0),
+            hand_size: Some(hand.len()),
+            draw_four_challenge: true,
+            ..GameConfig::default()
+        });
+        let mut deck = cards(&["red 5"]);
+        deck.extend(cards(hand));
+        deck.extend(cards(&["green 1", "green 2", "green 3", "green 4", "green 6", "green 7", "green 8", "green 9"]));
+        let mut game = lobby.start_with_deck(0, deck).unwrap();
+        let options = CliOptions { draw_four_warning, ..CliOptions::default() };
+        let mut console = MemoryConsole::new(input.iter().copied());
+        resume_game(&mut console, &mut game, Controllers::new(), options, None).unwrap();
+        assert_eq!(console.remaining_input(), 0);
+        console.output()
+    }
+
+    #[test]
+    fn a_challengeable_draw_four_is_warned_about()
+    {
+        let warning = "You hold a matching color, so playing this Draw Four can be challenged. Play it anyway? (y/n): ";
+
+        // Alice has a red card, thinks better of it and plays that instead
+        let output = draw_four_game(&["+4", "red 1"], true, &["1", "n", "2", "quit", "y"]);
+        assert_eq!(output.matches(warning).count(), 1, "{}", output);
+        assert!(!output.contains("Alice played a Draw 4 Wildcard!"), "{}", output);
+        assert!(output.contains("Alice played a Red 1!"), "{}", output);
+
+        // Or she goes ahead with it
+        let output = draw_four_game(&["+4", "red 1"], true, &["1", "y", "yellow", "n", "quit", "y"]);
+        assert_eq!(output.matches(warning).count(), 1, "{}", output);
+        assert!(output.contains("Alice played a Draw 4 Wildcard!"), "{}", output);
+
+        // Without a red card there's nothing to warn about
+        let output = draw_four_game(&["+4", "blue 1"], true, &["1", "yellow", "n", "quit", "y"]);
+        assert!(!output.contains(warning), "{}", output);
+        assert!(output.contains("Alice played a Draw 4 Wildcard!"), "{}", output);
+
+        // And groups who'd rather not be warned can turn it off
+        let output = draw_four_game(&["+4", "red 1"], false, &["1", "yellow", "n", "quit", "y"]);
+        assert!(!output.contains(warning), "{}", output);
+        assert!(output.contains("Alice played a Draw 4 Wildcard!"), "{}", output);
+    }
+
+    #[test]
+    fn a_demo_game_is_narrated_from_the_first_card()
+    {
+        let mut lobby = Lobby::new();
+        for name in ["Bob", "Carol", "Dan"] {
+            assert!(lobby.add_bot(name, Box::new(FirstMoveBot)));
+        }
+        // A game that starts on a Skip, so there's more to say before the first turn
+        let starts_on = |seed| lobby.clone().start_with_seed(seed).unwrap().start_effect().top_card.card_type;
+        let seed = (0..).find(|seed| starts_on(*seed) == CardType::Skip).unwrap();
+        let demo = |seed| {
+            let mut console = MemoryConsole::new(Vec::<String>::new());
+            let end = run_demo(&mut console, lobby.clone(), CliOptions::default(), seed).unwrap();
+            (end, console.output())
+        };
+        let (end, output) = demo(seed);
+        assert_eq!(demo(seed), (end, output.clone()));
+        assert!(end.winner().is_some(), "{}", output);
+
+        let game = lobby.clone().start_with_seed(seed).unwrap();
+        let effect = game.start_effect();
+        let expected = format!("Watching the bots play! Type q and press enter to stop\n\
+            Replay this game with --demo --seed {}\n\n\
+            Starting the game! The starting player is {}\n\n\
+            The top card is a {}\n\n\
+            {} had their turn skipped!\n\n\
+            The new starting player is {}\n\n",
+            seed, player_name(&game, effect.first_player), effect.top_card, player_name(&game, effect.first_player),
+            player_name(&game, effect.starting_player));
+        assert!(output.starts_with(&expected), "{}", output);
+        assert!(output.contains("How the game went:"), "{}", output);
+    }
+
+    #[test]
+    fn the_summary_at_the_end_of_a_game()
+    {
+        let mut game = scripted("red 5", &[&["red 1", "red 2"], &["blue 3", "red 4"]], &["green 2"]);
+        let mut console = MemoryConsole::new(["uno", "1", "draw", "1"]);
+        let end = resume_game(&mut console, &mut game, Controllers::new(), CliOptions::default(), None).unwrap();
+        assert_eq!(end, GameEnd::Won(game.players()[0].id()));
+        assert_eq!(console.remaining_input(), 0);
+
+        // With the time taken pinned down, the summary comes out the same every time
+        let renderer = CliOptions::default().renderer;
+        let mut summary = MemoryConsole::new(Vec::<String>::new());
+        print_summary(&mut summary, &renderer, &game, end, Duration::from_secs(125));
+        let mut series = Match::new(Lobby::from_game(&game, Controllers::new()));
+        series.set_target(500);
+        assert!(!score_round(&mut summary, &renderer, &mut series, &game));
+        assert_eq!(summary.output(), [
+            "How the game went:",
+            "   Player Cards Points",
+            "1. Alice      0      0",
+            "2. Bob        3      9",
+            "Alice scores 9 points for the cards left in everyone else's hands",
+            "3 turns in 2:05",
+            "",
+            "Player               Played  Drawn Skipped Penalized Penalties Wilds Longest",
+            "                                                         dealt        streak",
+            "Alice                     2      0       0         0         0     0       2",
+            "Bob                       0      1       0         0         0     0       0",
+            "",
+            "The match so far:",
+            "   Player This round Total",
+            "1. Alice           9     9",
+            "2. Bob             0     0",
+            "",
+            ""
+        ].join("\n"));
+    }
+
+    #[test]
+    fn an_accessible_game()
+    {
+        let mut game = scripted("red 5", &[&["red 1", "wild"], &["blue 3", "red 4"]], &["green 2"]);
+        let options = CliOptions { renderer: Renderer::ACCESSIBLE, ..CliOptions::default() };
+        let mut console = MemoryConsole::new(["uno", "1", "draw", "1", "blue"]);
+        let end = resume_game(&mut console, &mut game, Controllers::new(), options, None);
+        assert_eq!(end, Ok(GameEnd::Won(game.players()[0].id())));
+
+        // Everything is said in words, a line at a time
+        let output = console.output();
+        for symbol in ["->", "[", "·", "!]", "↻", "*", "\x1b"] {
+            assert!(!output.contains(symbol), "'{}' is in:\n{}", symbol, output);
+        }
+        let lines: Vec<&str> = output.lines().collect();
+        for expected in [
+            "Turn order: Current player: Bob with 2 cards. Next: Alice with 1 card.",
+            "Recently played: Red 5, then Red 1",
+            "Card 1: Blue 3.",
+            "Card 2: Red 4, playable.",
+            "Choose a card or type 'draw': You drew a Green 2! It's not playable on the current card!",
+            "Your choice: The wildcard color is now Blue",
+            "2. Bob: 3 cards, 9 points.",
+            "Bob: Played 0, Drawn 1, Skipped 0, Penalized 0, Penalties dealt 0, Wilds 0, Longest streak 0."
+        ] {
+            assert!(lines.contains(&expected), "'{}' isn't in:\n{}", expected, output);
+        }
+    }
+
+    #[test]
+    fn bad_choices_are_explained()
+    {
+        let mut game = scripted("red 5", &[&["red 1", "blue 2", "red 3"], &["green 3", "green 4", "green 6"]], &[]);
+        let mut console = MemoryConsole::new(["9", "2", "quit", "y"]);
+        let end = resume_game(&mut console, &mut game, Controllers::new(), CliOptions::default(), None).unwrap();
+        assert_eq!(end, GameEnd::LastPlayerStanding(game.players()[0].id()));
+        assert_eq!(console.remaining_input(), 0);
+
+        let output = console.output();
+        let answers: Vec<&str> = output.lines()
+            .filter_map(|line| line.strip_prefix("Choose a card or type 'draw': "))
+            .collect();
+        assert_eq!(answers, [
+            "Please enter a card index in the range 1 - 3, or type 'draw' to draw",
+            "The Blue 2 you picked cannot be played on a Red 5. Select a different card or choose the 'draw' option",
+            "Concede the game? Your cards will leave play (y/n): Alice left the game! Their 3 cards are out of play"
+        ]);
+        assert!(output.contains("Bob is the last player left! They are the winner!"));
+        // Nothing was played while Alice was being asked again
+        assert_eq!(game.top_card(), cards(&["red 5"])[0]);
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Seconds since the Unix epoch, or 0 if the system clock is set before it
+pub fn now() -> u64
+{
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs())
+}
+
+// A moment in UTC, for showing a time stamp. Local time would need the system's time
+// zone database, so everything is written in UTC and says so
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtcTime
+{
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32
+}
+
+impl UtcTime
+{
+    pub fn from_unix(seconds: u64) -> UtcTime
+    {
+        let days = (seconds / 86_400) as i64;
+        let time = (seconds % 86_400) as u32;
+
+        // Days since 1970 to a date, from Howard Hinnant's `civil_from_days`
+        let shifted = days + 719_468;
+        let era = shifted.div_euclid(146_097);
+        let day_of_era = shifted.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        UtcTime {
+            year,
+            month: month as u32,
+            day: day as u32,
+            hour: time / 3600,
+            minute: time / 60 % 60,
+            second: time % 60
+        }
+    }
+
+    pub fn now() -> UtcTime
+    {
+        UtcTime::from_unix(now())
+    }
+
+    // e.g. "2026-10-15 14:03 UTC"
+    pub fn to_minute(&self) -> String
+    {
+        format!("{}-{:02}-{:02} {:02}:{:02} UTC", self.year, self.month, self.day, self.hour, self.minute)
+    }
+}
+
+// e.g. "2026-10-15 14:03:05"
+impl fmt::Display for UtcTime
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}-{:02}-{:02} {:02}:{:02}:{:02}", self.year, self.month, self.day, self.hour, self.minute,
+            self.second)
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt;
+
+use crate::error::UnknownRule;
+use crate::game::STARTING_HAND_SIZE;
+
+// Who wins when a game is stopped by the turn limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StalematePolicy
+{
+    // The player holding the fewest points wins, a tie means nobody does
+    #[default]
+    LowestPoints,
+    Draw
+}
+
+// How the player who takes the first turn is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StartingPlayer
+{
+    #[default]
+    Random,
+    // Everyone is dealt a card face up and the highest number goes first. Action
+    // cards and wildcards count as zero and tied players draw again
+    DrawForDeal,
+    // The player in this seat goes first, which lets a match rotate the deal
+    Seat(usize)
+}
+
+// Which draw cards may be stacked onto a pending penalty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StackingPolicy
+{
+    pub allow_two_on_two: bool,
+    pub allow_four_on_two: bool,
+    pub allow_two_on_four: bool,
+    pub allow_four_on_four: bool,
+    // A stacked Draw Two may be any color rather than the active color
+    pub allow_color_change: bool,
+    // No card can be stacked once it would take the penalty above this
+    pub max_penalty: Option<u8>
+}
+
+impl StackingPolicy
+{
+    // Only a card of the same kind can be stacked
+    pub const SAME_KIND: StackingPolicy = StackingPolicy {
+        allow_two_on_two: true,
+        allow_four_on_two: false,
+        allow_two_on_four: false,
+        allow_four_on_four: true,
+        allow_color_change: true,
+        max_penalty: None
+    };
+
+    // A Draw Four can raise a Draw Two, but nothing smaller goes on a Draw Four
+    pub const PROGRESSIVE: StackingPolicy = StackingPolicy {
+        allow_four_on_two: true,
+        ..StackingPolicy::SAME_KIND
+    };
+
+    // Any draw card on any other, in any color
+    pub const ANYTHING_GOES: StackingPolicy = StackingPolicy {
+        allow_four_on_two: true,
+        allow_two_on_four: true,
+        ..StackingPolicy::SAME_KIND
+    };
+}
+
+impl fmt::Display for StackingPolicy
+{
+    // e.g. "Draw Two on Draw Two and Draw Four on Draw Four, in any color"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let allowed = [
+            (self.allow_two_on_two, "Draw Two on Draw Two"),
+            (self.allow_four_on_two, "Draw Four on Draw Two"),
+            (self.allow_two_on_four, "Draw Two on Draw Four"),
+            (self.allow_four_on_four, "Draw Four on Draw Four")
+        ];
+        let allowed: Vec<&str> = allowed.iter().filter(|(allowed, _)| *allowed).map(|(_, name)| *name).collect();
+        match allowed.split_last() {
+            None => write!(f, "nothing can be stacked")?,
+            Some((last, [])) => write!(f, "{}", last)?,
+            Some((last, rest)) => write!(f, "{} and {}", rest.join(", "), last)?
+        }
+        if allowed.is_empty() {
+            return Ok(());
+        }
+        write!(f, "{}", if self.allow_color_change { ", in any color" } else { ", in the active color" })?;
+        if let Some(max_penalty) = self.max_penalty {
+            write!(f, ", up to {} cards", max_penalty)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for StackingPolicy
+{
+    fn default() -> Self
+    {
+        StackingPolicy::SAME_KIND
+    }
+}
+
+// House rules and other settings that change how a game is played. The defaults
+// follow the official rules
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameConfig
+{
+    // Allow a player hit with a Draw Two (or Draw Four) to pass the penalty on by
+    // playing another draw card, adding to the total
+    pub stacking: bool,
+    // Which draw cards can be stacked while `stacking` is on
+    pub stacking_policy: StackingPolicy,
+    // A Draw Four may only be played when the player holds no card of the active
+    // color, and the next player may challenge it
+    pub draw_four_challenge: bool,
+    // Drawing is only allowed when nothing in the player's hand can be played
+    pub forced_play: bool,
+    // Any player may play a card identical to the top card out of turn
+    pub jump_in: bool,
+    // Playing a 7 swaps hands with a chosen opponent and playing a 0 passes every
+    // hand on in the direction of play
+    pub seven_zero: bool,
+    // Accusing a player who doesn't need to call Uno makes the accuser draw 2 cards
+    pub penalize_false_uno_accusations: bool,
+    // Play a drawn card straight away when it can be played instead of letting the
+    // player choose whether to keep it
+    pub auto_play_drawn_cards: bool,
+    // Drawing continues until a playable card turns up
+    pub draw_until_playable: bool,
+    // Symbols only match within the same color, so a card must match the top card's
+    // color unless it is a wildcard. Applied through `rules::StrictColorMatching`
+    pub strict_color_matching: bool,
+    // Identical cards can be played together with `Game::play_many`, each copy
+    // applying its effect
+    pub play_identical_together: bool,
+    // A wildcard can't be a player's last card played. Holding only a wildcard means
+    // drawing instead
+    pub no_going_out_on_wild: bool,
+    // End the game once this many turns have been taken, so games that go nowhere
+    // still finish
+    pub max_turns: Option<u32>,
+    pub stalemate_policy: StalematePolicy,
+    pub starting_player: StartingPlayer,
+    // How many turns `Game::history` remembers. None keeps every turn
+    pub history_limit: Option<usize>,
+    // How many cards everyone is dealt, and how many a late joiner gets. None deals
+    // the usual 7
+    pub hand_size: Option<usize>
+}
+
+// The house rules that can be switched on and off by name, as in `--rules stacking,seven-zero`
+pub const RULE_NAMES: [&str; 12] = ["stacking", "draw-four-challenge", "forced-play", "jump-in", "seven-zero",
+    "penalize-false-uno", "auto-play-drawn", "draw-until-playable", "strict-colors", "play-identical",
+    "no-wild-finish", "draw-for-deal"];
+
+impl GameConfig
+{
+    // Fewer cards and fewer decisions, for younger players. Drawn cards that can be
+    // played are played, and nothing can be stacked or challenged
+    pub fn junior() -> GameConfig
+    {
+        GameConfig {
+            auto_play_drawn_cards: true,
+            hand_size: Some(5),
+            ..GameConfig::default()
+        }
+    }
+
+    pub fn starting_hand_size(&self) -> usize
+    {
+        self.hand_size.unwrap_or(STARTING_HAND_SIZE)
+    }
+
+    // Every setting in words, as a label and a value, for telling players what game
+    // they're in. `Display` writes them one to a line
+    pub fn describe(&self) -> Vec<(&'static str, String)>
+    {
+        let choose = |on: bool, yes: &str, no: &str| String::from(if on { yes } else { no });
+        vec![
+            ("Hand size", format!("{} cards", self.starting_hand_size())),
+            ("Deck", String::from("a standard deck that never runs out")),
+            ("First player", match self.starting_player {
+                StartingPlayer::Random => String::from("chosen at random"),
+                StartingPlayer::DrawForDeal => String::from("whoever draws the highest number"),
+                StartingPlayer::Seat(seat) => format!("the player in seat {}", seat + 1)
+            }),
+            ("Matching", choose(self.strict_color_matching, "by color only, unless it's a wildcard",
+                "by color or by number and symbol")),
+            ("Stacking", if self.stacking { self.stacking_policy.to_string() } else { String::from("off") }),
+            ("Draw Four", choose(self.draw_four_challenge,
+                "only without a card of the active color, and can be challenged", "can always be played")),
+            ("Drawing", choose(self.forced_play, "only when nothing can be played", "allowed at any time")),
+            ("Cards drawn", choose(self.draw_until_playable, "until a playable one turns up", "one at a time")),
+            ("Drawn playable cards", choose(self.auto_play_drawn_cards, "played straight away", "player's choice")),
+            ("Seven-zero", choose(self.seven_zero, "7 swaps hands with someone and 0 passes every hand on", "off")),
+            ("Jump-in", choose(self.jump_in, "an identical card can be played out of turn", "off")),
+            ("Identical cards", choose(self.play_identical_together, "can be played together", "one at a time")),
+            ("Last card", choose(self.no_going_out_on_wild, "can't be a wildcard", "can be anything")),
+            ("False Uno calls", choose(self.penalize_false_uno_accusations, "the accuser draws 2", "no penalty")),
+            ("Turn limit", match (self.max_turns, self.stalemate_policy) {
+                (None, _) => String::from("none"),
+                (Some(turns), StalematePolicy::LowestPoints) => format!("{} turns, then the fewest points wins", turns),
+                (Some(turns), StalematePolicy::Draw) => format!("{} turns, then nobody wins", turns)
+            })
+        ]
+    }
+
+    // Turn one of `RULE_NAMES` on or off
+    pub fn set_rule(&mut self, name: &str, enabled: bool) -> Result<(), UnknownRule>
+    {
+        let rule = match name.trim().to_lowercase().as_str() {
+            "stacking" => &mut self.stacking,
+            "draw-four-challenge" => &mut self.draw_four_challenge,
+            "forced-play" => &mut self.forced_play,
+            "jump-in" => &mut self.jump_in,
+            "seven-zero" => &mut self.seven_zero,
+            "penalize-false-uno" => &mut self.penalize_false_uno_accusations,
+            "auto-play-drawn" => &mut self.auto_play_drawn_cards,
+            "draw-until-playable" => &mut self.draw_until_playable,
+            "strict-colors" => &mut self.strict_color_matching,
+            "play-identical" => &mut self.play_identical_together,
+            "no-wild-finish" => &mut self.no_going_out_on_wild,
+            "draw-for-deal" => {
+                self.starting_player = if enabled { StartingPlayer::DrawForDeal } else { StartingPlayer::Random };
+                return Ok(());
+            },
+            _ => return Err(UnknownRule(name.trim().to_owned()))
+        };
+        *rule = enabled;
+        Ok(())
+    }
+}
+
+impl fmt::Display for GameConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        for (label, value) in self.describe() {
+            writeln!(f, "{}: {}", label, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn a_house_rules_config_in_words()
+    {
+        let config = GameConfig {
+            stacking: true,
+            stacking_policy: StackingPolicy { max_penalty: Some(8), ..StackingPolicy::PROGRESSIVE },
+            forced_play: true,
+            seven_zero: true,
+            auto_play_drawn_cards: true,
+            max_turns: Some(200),
+            stalemate_policy: StalematePolicy::Draw,
+            starting_player: StartingPlayer::Seat(2),
+            hand_size: Some(5),
+            ..GameConfig::default()
+        };
+        assert_eq!(config.to_string(), "\
+Hand size: 5 cards
+Deck: a standard deck that never runs out
+First player: the player in seat 3
+Matching: by color or by number and symbol
+Stacking: Draw Two on Draw Two, Draw Four on Draw Two and Draw Four on Draw Four, in any color, up to 8 cards
+Draw Four: can always be played
+Drawing: only when nothing can be played
+Cards drawn: one at a time
+Drawn playable cards: played straight away
+Seven-zero: 7 swaps hands with someone and 0 passes every hand on
+Jump-in: off
+Identical cards: one at a time
+Last card: can be anything
+False Uno calls: no penalty
+Turn limit: 200 turns, then nobody wins
+");
+    }
+
+    #[test]
+    fn stacking_policies_in_words()
+    {
+        assert_eq!(StackingPolicy::SAME_KIND.to_string(),
+            "Draw Two on Draw Two and Draw Four on Draw Four, in any color");
+        let policy = StackingPolicy {
+            allow_four_on_four: false,
+            allow_color_change: false,
+            ..StackingPolicy::SAME_KIND
+        };
+        assert_eq!(policy.to_string(), "Draw Two on Draw Two, in the active color");
+        let policy = StackingPolicy { allow_two_on_two: false, max_penalty: Some(4), ..policy };
+        assert_eq!(policy.to_string(), "nothing can be stacked");
+    }
+}
@@ -0,0 +1,301 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::process;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Where the terminal frontend reads its input and writes its output. Consoles are
+// handles: every clone reads from and writes to the same place, so the controllers
+// and narrators of one game can each hold their own
+pub trait Console
+{
+    // The next line of input, with its line ending. An empty string once there is no
+    // more input
+    fn read_line(&mut self) -> io::Result<String>;
+
+    // Like `read_line`, but None once `timeout` has passed without a whole line. Consoles
+    // that can only wait forever do
+    fn read_line_within(&mut self, _timeout: Duration) -> io::Result<Option<String>>
+    {
+        self.read_line().map(Some)
+    }
+
+    // The time, by the console's clock. A console can keep its own, so that the time a
+    // read gives up after is the time that passes
+    fn now(&self) -> Instant
+    {
+        Instant::now()
+    }
+
+    // Written straight away, even without a line ending, so prompts show up
+    fn write(&mut self, text: &str);
+
+    // Whether the output goes to a terminal someone is looking at
+    fn is_tty(&self) -> bool
+    {
+        false
+    }
+
+    // The last line read couldn't be used and will be asked for again
+    fn input_rejected(&mut self) {}
+
+    // Wipe everything written so far off the screen, where there is one
+    fn clear(&mut self) {}
+
+    // Lets `write!` and `writeln!` print to a console
+    fn write_fmt(&mut self, args: fmt::Arguments)
+    {
+        self.write(&args.to_string());
+    }
+}
+
+// Standard input and output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdioConsole;
+
+// Lines of standard input, read on a thread of their own from the first time a read
+// has to be able to give up. Every read goes through it after that, so a line typed
+// after a read gave up isn't lost
+static STDIN_LINES: OnceLock<Mutex<Receiver<io::Result<String>>>> = OnceLock::new();
+
+fn stdin_lines() -> &'static Mutex<Receiver<io::Result<String>>>
+{
+    STDIN_LINES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut line = String::new();
+            let read = io::stdin().read_line(&mut line).map(|_| line);
+            // Nothing more will come after the end of the input or an error
+            let last = read.as_ref().map_or(true, String::is_empty);
+            if sender.send(read).is_err() || last {
+                break;
+            }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+impl Console for StdioConsole
+{
+    fn read_line(&mut self) -> io::Result<String>
+    {
+        match STDIN_LINES.get() {
+            Some(lines) => lines.lock().expect("Nothing panics holding the lines").recv().unwrap_or(Ok(String::new())),
+            None => {
+                let mut line = String::new();
+                io::stdin().read_line(&mut line)?;
+                Ok(line)
+            }
+        }
+    }
+
+    fn read_line_within(&mut self, timeout: Duration) -> io::Result<Option<String>>
+    {
+        match stdin_lines().lock().expect("Nothing panics holding the lines").recv_timeout(timeout) {
+            Ok(line) => line.map(Some),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Ok(Some(String::new()))
+        }
+    }
+
+    fn write(&mut self, text: &str)
+    {
+        let mut stdout = io::stdout();
+        stdout.write_all(text.as_bytes()).and_then(|()| stdout.flush()).expect("Error while writing to stdout");
+    }
+
+    fn is_tty(&self) -> bool
+    {
+        io::stdout().is_terminal()
+    }
+
+    // Older Windows consoles don't understand escape codes, so it's left to `cls` there
+    fn clear(&mut self)
+    {
+        if !self.is_tty() {
+            return;
+        }
+        if cfg!(windows) {
+            let _ = process::Command::new("cmd").args(["/C", "cls"]).status();
+        } else {
+            // Clear the screen and the scrollback, then go back to the top left
+            self.write("\x1b[2J\x1b[3J\x1b[H");
+        }
+    }
+}
+
+// The keyboard with line editing: the arrow keys, Ctrl+U and Ctrl+W, and the lines
+// typed earlier in the session a press of Up away. Ctrl+C and Ctrl+D read as 'quit',
+// which concedes after asking at the play prompt and is turned down anywhere else, and
+// a second Ctrl+C in a row ends the program
+#[cfg(feature = "readline")]
+#[derive(Clone)]
+pub struct EditorConsole
+{
+    editor: Rc<RefCell<EditorState>>
+}
+
+#[cfg(feature = "readline")]
+struct EditorState
+{
+    editor: rustyline::DefaultEditor,
+    // What's been written since the last line ending. The editor redraws the line it's
+    // on, so this is handed to it as the prompt instead of being printed
+    prompt: String,
+    interrupted: bool
+}
+
+#[cfg(feature = "readline")]
+impl EditorConsole
+{
+    // None when input isn't coming from a terminal, so piped input is read plainly
+    pub fn new() -> Option<EditorConsole>
+    {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+        let editor = rustyline::DefaultEditor::new().ok()?;
+        let state = EditorState { editor, prompt: String::new(), interrupted: false };
+        Some(EditorConsole { editor: Rc::new(RefCell::new(state)) })
+    }
+}
+
+#[cfg(feature = "readline")]
+impl Console for EditorConsole
+{
+    fn read_line(&mut self) -> io::Result<String>
+    {
+        use rustyline::error::ReadlineError;
+
+        let mut state = self.editor.borrow_mut();
+        let prompt = std::mem::take(&mut state.prompt);
+        match state.editor.readline(&prompt) {
+            Ok(line) => {
+                state.interrupted = false;
+                if !line.trim().is_empty() {
+                    let _ = state.editor.add_history_entry(line.as_str());
+                }
+                Ok(line + "\n")
+            },
+            Err(ReadlineError::Interrupted) if state.interrupted => process::exit(130),
+            Err(ReadlineError::Interrupted) => {
+                state.interrupted = true;
+                StdioConsole.write("(Press Ctrl+C again to close the program)\n");
+                Ok(String::from("quit\n"))
+            },
+            Err(ReadlineError::Eof) => {
+                state.interrupted = false;
+                Ok(String::from("quit\n"))
+            },
+            Err(ReadlineError::Io(error)) => Err(error),
+            Err(error) => Err(io::Error::other(error))
+        }
+    }
+
+    fn write(&mut self, text: &str)
+    {
+        let mut state = self.editor.borrow_mut();
+        state.prompt.push_str(text);
+        if let Some(end) = state.prompt.rfind('\n') {
+            let rest = state.prompt.split_off(end + 1);
+            StdioConsole.write(&std::mem::replace(&mut state.prompt, rest));
+        }
+    }
+
+    fn is_tty(&self) -> bool
+    {
+        StdioConsole.is_tty()
+    }
+
+    // Anything waiting to be a prompt is wiped along with the rest
+    fn clear(&mut self)
+    {
+        self.editor.borrow_mut().prompt.clear();
+        StdioConsole.clear();
+    }
+}
+
+#[derive(Debug, Default)]
+struct Buffers
+{
+    input: VecDeque<String>,
+    output: String
+}
+
+// Input given up front and output kept in memory, for driving the frontend from code.
+// Running out of input is an error rather than the end of it, so a frontend waiting
+// on more input can't loop forever
+#[derive(Debug, Clone, Default)]
+pub struct MemoryConsole
+{
+    buffers: Rc<RefCell<Buffers>>
+}
+
+impl MemoryConsole
+{
+    pub fn new<I, S>(lines: I) -> MemoryConsole
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let input = lines.into_iter().map(|line| line.into() + "\n").collect();
+        MemoryConsole { buffers: Rc::new(RefCell::new(Buffers { input, output: String::new() })) }
+    }
+
+    // Everything written so far
+    pub fn output(&self) -> String
+    {
+        self.buffers.borrow().output.clone()
+    }
+
+    // Lines that haven't been read yet
+    pub fn remaining_input(&self) -> usize
+    {
+        self.buffers.borrow().input.len()
+    }
+}
+
+impl Console for MemoryConsole
+{
+    fn read_line(&mut self) -> io::Result<String>
+    {
+        self.buffers.borrow_mut().input.pop_front()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "the console has run out of input"))
+    }
+
+    fn write(&mut self, text: &str)
+    {
+        self.buffers.borrow_mut().output.push_str(text);
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn a_memory_console_reads_its_lines_in_order()
+    {
+        let mut console = MemoryConsole::new(["draw", "2"]);
+        assert_eq!(console.read_line().unwrap(), "draw\n");
+        assert_eq!(console.remaining_input(), 1);
+        assert_eq!(console.read_line().unwrap(), "2\n");
+        assert_eq!(console.read_line().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn clones_of_a_memory_console_share_it()
+    {
+        let mut console = MemoryConsole::new(["y"]);
+        let mut other = console.clone();
+        write!(console, "Play it? ");
+        writeln!(other, "{} cards", 3);
+        assert_eq!(other.read_line().unwrap(), "y\n");
+        assert_eq!(console.remaining_input(), 0);
+        assert_eq!(console.output(), "Play it? 3 cards\n");
+    }
+}
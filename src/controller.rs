@@ -0,0 +1,442 @@
+use std::collections::BTreeMap;
+
+use crate::error::GameError;
+use crate::events::RecordedEvent;
+use crate::game::{CatchOutcome, ChallengeOutcome, Card, Color, DrawUntilOutcome, DrawnCard, Game, GameEnd, Move,
+                  PendingAction, PenaltyOutcome, PlayOutcome, PlayerId, RemovedPlayer};
+use crate::strategy;
+use crate::view::{BotObservation, PlayerView};
+
+// Makes the decisions for one player. The same driver runs a game whether a seat is
+// filled by someone at the keyboard, a bot or a player on the other end of a socket
+pub trait PlayerController
+{
+    // Pick one of the observation's legal moves, which are never empty
+    fn choose_move(&mut self, observation: &BotObservation) -> Move;
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color;
+
+    // Who to swap hands with under the seven-zero rule. Defaults to whoever holds the
+    // fewest cards
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        view.opponents().min_by_key(|(_, player)| player.number_of_cards).map_or(0, |(seat, _)| seat)
+    }
+
+    // Asked after each of the player's turns that leaves them holding one card
+    fn wants_to_call_uno(&mut self, _view: &PlayerView) -> bool
+    {
+        true
+    }
+
+    // Controllers that do more than make moves, like calling out other players or
+    // adding players, override this instead of just `choose_move`
+    fn decide(&mut self, observation: &BotObservation) -> Decision
+    {
+        Decision::Move(self.choose_move(observation))
+    }
+
+    // The game refused the last decision. The same player is asked again
+    fn rejected(&mut self, _view: &PlayerView, _error: &GameError) {}
+
+    // Lobbies are cloneable, so the bots seated in them have to be too
+    fn clone_box(&self) -> Box<dyn PlayerController>;
+}
+
+impl Clone for Box<dyn PlayerController>
+{
+    fn clone(&self) -> Self
+    {
+        self.clone_box()
+    }
+}
+
+// Lets a boxed controller be wrapped like any other
+impl<C: PlayerController + ?Sized> PlayerController for Box<C>
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        (**self).choose_move(observation)
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        (**self).choose_color(view)
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        (**self).choose_swap_target(view)
+    }
+
+    fn wants_to_call_uno(&mut self, view: &PlayerView) -> bool
+    {
+        (**self).wants_to_call_uno(view)
+    }
+
+    fn decide(&mut self, observation: &BotObservation) -> Decision
+    {
+        (**self).decide(observation)
+    }
+
+    fn rejected(&mut self, view: &PlayerView, error: &GameError)
+    {
+        (**self).rejected(view, error)
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        (**self).clone_box()
+    }
+}
+
+pub type Controllers = BTreeMap<PlayerId, Box<dyn PlayerController>>;
+
+// Everything a controller can ask for on its player's turn
+pub enum Decision
+{
+    Move(Move),
+    // Play several identical cards at once, see `GameConfig::play_identical_together`
+    PlayMany(Vec<usize>),
+    CallUno { seat: usize },
+    Catch { target: usize },
+    // The player in `seat` jumps in with their card identical to the top card
+    JumpIn { seat: usize },
+    Join { name: String, controller: Box<dyn PlayerController> },
+    Leave { seat: usize },
+    // Put the current player's hand in order before they move, see `Game::sort_hand`
+    SortHand,
+    // Write the game to `file`, or wherever the narrator keeps saves. Nothing in the game
+    // changes, so the same player is asked again
+    Save { file: Option<String> }
+}
+
+// What happened because of a decision, for the narrator
+#[derive(Debug, Clone)]
+pub enum Report
+{
+    Played(PlayOutcome),
+    WildColorChosen { player: PlayerId, color: Color },
+    Drew { player: PlayerId, drawn: DrawnCard },
+    DrewUntilPlayable { player: PlayerId, outcome: DrawUntilOutcome },
+    KeptDrawn { player: PlayerId, card: Card },
+    PenaltyAccepted(PenaltyOutcome),
+    Challenged(ChallengeOutcome),
+    JumpedIn { player: PlayerId, interrupted: PlayerId },
+    UnoCalled(PlayerId),
+    Caught(CatchOutcome),
+    Joined { player: PlayerId },
+    Left(RemovedPlayer),
+    SaveRequested { file: Option<String> }
+}
+
+// Told about everything that happens while `run_game` runs a game
+pub trait Narrator
+{
+    // The player in `seat` is about to be asked what to do
+    fn turn_starting(&mut self, _game: &Game, _seat: usize) {}
+
+    fn report(&mut self, _game: &Game, _report: &Report) {}
+
+    // Every event the game records, in order
+    fn event(&mut self, _game: &Game, _event: &RecordedEvent) {}
+}
+
+// Runs a game without telling anyone about it
+impl Narrator for () {}
+
+// Tells both, the first first
+impl<A: Narrator, B: Narrator> Narrator for (A, B)
+{
+    fn turn_starting(&mut self, game: &Game, seat: usize)
+    {
+        self.0.turn_starting(game, seat);
+        self.1.turn_starting(game, seat);
+    }
+
+    fn report(&mut self, game: &Game, report: &Report)
+    {
+        self.0.report(game, report);
+        self.1.report(game, report);
+    }
+
+    fn event(&mut self, game: &Game, event: &RecordedEvent)
+    {
+        self.0.event(game, event);
+        self.1.event(game, event);
+    }
+}
+
+// Tells nobody when there's nothing to tell
+impl<N: Narrator> Narrator for Option<N>
+{
+    fn turn_starting(&mut self, game: &Game, seat: usize)
+    {
+        if let Some(narrator) = self {
+            narrator.turn_starting(game, seat);
+        }
+    }
+
+    fn report(&mut self, game: &Game, report: &Report)
+    {
+        if let Some(narrator) = self {
+            narrator.report(game, report);
+        }
+    }
+
+    fn event(&mut self, game: &Game, event: &RecordedEvent)
+    {
+        if let Some(narrator) = self {
+            narrator.event(game, event);
+        }
+    }
+}
+
+// A trivial bot that makes the first legal move and picks the color it holds the most of
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstMoveBot;
+
+impl PlayerController for FirstMoveBot
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let legal = &observation.legal_moves[..];
+        legal[0]
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        strategy::suggest_wild_color(view)
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(*self)
+    }
+}
+
+// Run `game` to the end, asking each player's controller for their decisions. Every
+// player needs a controller, and players who join take theirs from the `Join` decision
+pub fn run_game(game: &mut Game, controllers: &mut Controllers, narrator: &mut dyn Narrator)
+    -> Result<GameEnd, GameError>
+{
+    let mut events_told = 0;
+    loop {
+        for event in &game.events()[events_told..] {
+            narrator.event(game, event);
+        }
+        events_told = game.events().len();
+
+        if let Some(end) = game.end() {
+            return Ok(end);
+        }
+
+        let seat = game.current_seat();
+        let id = game.player().id();
+        let controller = controllers.get_mut(&id).ok_or(GameError::NoController(id))?;
+        narrator.turn_starting(game, seat);
+
+        // A wildcard turned over at the start, or left behind by a player who left,
+        // still needs a color
+        if game.pending_action() == Some(PendingAction::AwaitingWildColor) {
+            let color = controller.choose_color(&game.view_for(seat)?);
+            let outcome = game.set_wildcard_color(color)?;
+            narrator.report(game, &Report::WildColorChosen { player: id, color });
+            if let Some(outcome) = outcome {
+                let outcome = finish_play(game, controllers, narrator, outcome)?;
+                narrator.report(game, &Report::Played(outcome));
+            }
+            continue;
+        }
+
+        let observation = game.observe(seat)?;
+        let decision = controller.decide(&observation);
+        // Whoever jumps in is the one who played
+        let actor = match &decision {
+            Decision::JumpIn { seat } => game.players().get(*seat).map_or(id, |player| player.id()),
+            _ => id
+        };
+        if let Err(error) = apply(game, controllers, narrator, decision) {
+            if let Some(controller) = controllers.get_mut(&id) {
+                controller.rejected(&observation.view, &error);
+            }
+            continue;
+        }
+
+        offer_uno_call(game, controllers, narrator, actor)?;
+    }
+}
+
+fn apply(game: &mut Game, controllers: &mut Controllers, narrator: &mut dyn Narrator, decision: Decision)
+    -> Result<(), GameError>
+{
+    let id = game.player().id();
+    match decision {
+        Decision::Move(Move::Play(index)) => {
+            let hand_size = game.player().number_of_cards();
+            let card = *game.player().cards().get(index)
+                .ok_or(GameError::InvalidCardIndex { given: index, hand_size })?;
+            let outcome = if card.is_wild() {
+                let color = choose_color(game, controllers)?;
+                let outcome = game.play_wild(index, color)?;
+                narrator.report(game, &Report::WildColorChosen { player: id, color });
+                outcome
+            } else {
+                game.play(index)?
+            };
+            report_play(game, controllers, narrator, outcome)
+        },
+        Decision::Move(Move::Draw) if game.config().draw_until_playable => {
+            let outcome = game.draw_until_playable(None)?;
+            let auto_played = outcome.found.as_ref().and_then(|drawn| drawn.auto_played.clone());
+            narrator.report(game, &Report::DrewUntilPlayable { player: id, outcome });
+            match auto_played {
+                Some(outcome) => report_play(game, controllers, narrator, outcome),
+                None => Ok(())
+            }
+        },
+        Decision::Move(Move::Draw) => {
+            let drawn = game.draw_one()?;
+            let auto_played = drawn.auto_played.clone();
+            narrator.report(game, &Report::Drew { player: id, drawn });
+            match auto_played {
+                Some(outcome) => report_play(game, controllers, narrator, outcome),
+                None => Ok(())
+            }
+        },
+        Decision::Move(Move::PlayDrawn) => {
+            let card = game.drawn_card().ok_or(GameError::NoDrawnCard)?;
+            let outcome = if card.is_wild() {
+                let color = choose_color(game, controllers)?;
+                let outcome = game.play_drawn_wild(color)?;
+                narrator.report(game, &Report::WildColorChosen { player: id, color });
+                outcome
+            } else {
+                game.play_drawn()?
+            };
+            report_play(game, controllers, narrator, outcome)
+        },
+        Decision::Move(Move::KeepDrawn) => {
+            let card = game.drawn_card().ok_or(GameError::NoDrawnCard)?;
+            game.keep_drawn()?;
+            narrator.report(game, &Report::KeptDrawn { player: id, card });
+            Ok(())
+        },
+        Decision::Move(Move::AcceptPenalty) => {
+            let outcome = game.accept_penalty()?;
+            narrator.report(game, &Report::PenaltyAccepted(outcome));
+            Ok(())
+        },
+        Decision::Move(Move::ChallengeDrawFour) => {
+            let outcome = game.challenge_draw_four()?;
+            narrator.report(game, &Report::Challenged(outcome));
+            Ok(())
+        },
+        Decision::PlayMany(indices) => {
+            let outcome = game.play_many(&indices)?;
+            report_play(game, controllers, narrator, outcome)
+        },
+        Decision::CallUno { seat } => {
+            game.call_uno(seat)?;
+            narrator.report(game, &Report::UnoCalled(game.players()[seat].id()));
+            Ok(())
+        },
+        Decision::Catch { target } => {
+            let outcome = game.catch_failure_to_call(game.current_seat(), target)?;
+            narrator.report(game, &Report::Caught(outcome));
+            Ok(())
+        },
+        Decision::JumpIn { seat } => {
+            let top = game.top_card();
+            let jumper = game.players().get(seat).ok_or(GameError::InvalidSeat(seat))?;
+            let card_index = jumper.cards().iter().position(|card| *card == top)
+                .ok_or(GameError::NoIdenticalCard { seat, top })?;
+
+            let outcome = game.jump_in(seat, card_index)?;
+            narrator.report(game, &Report::JumpedIn { player: outcome.play.player, interrupted: outcome.interrupted });
+            report_play(game, controllers, narrator, outcome.play)
+        },
+        Decision::Join { name, controller } => {
+            let seat = game.add_player_mid_game(&name)?;
+            let player = game.players()[seat].id();
+            controllers.insert(player, controller);
+            narrator.report(game, &Report::Joined { player });
+            Ok(())
+        },
+        Decision::SortHand => game.sort_hand(),
+        Decision::Save { file } => {
+            narrator.report(game, &Report::SaveRequested { file });
+            Ok(())
+        },
+        Decision::Leave { seat } => {
+            let removed = game.remove_player(seat)?;
+            controllers.remove(&removed.id);
+            narrator.report(game, &Report::Left(removed));
+            Ok(())
+        }
+    }
+}
+
+fn choose_color(game: &Game, controllers: &mut Controllers) -> Result<Color, GameError>
+{
+    let id = game.player().id();
+    let controller = controllers.get_mut(&id).ok_or(GameError::NoController(id))?;
+    Ok(controller.choose_color(&game.view_for(game.current_seat())?))
+}
+
+fn report_play(game: &mut Game, controllers: &mut Controllers, narrator: &mut dyn Narrator, outcome: PlayOutcome)
+    -> Result<(), GameError>
+{
+    let outcome = finish_play(game, controllers, narrator, outcome)?;
+    narrator.report(game, &Report::Played(outcome));
+    Ok(())
+}
+
+// Resolve whatever a play is still waiting on: a color for a wildcard that was drawn
+// and played, or who to swap hands with after a 7
+fn finish_play(game: &mut Game, controllers: &mut Controllers, narrator: &mut dyn Narrator, mut outcome: PlayOutcome)
+    -> Result<PlayOutcome, GameError>
+{
+    let id = game.player().id();
+    if outcome.awaiting_wild_color {
+        let color = choose_color(game, controllers)?;
+        if let Some(resolved) = game.set_wildcard_color(color)? {
+            outcome = resolved;
+        }
+        narrator.report(game, &Report::WildColorChosen { player: id, color });
+    }
+
+    if outcome.awaiting_swap_target {
+        let controller = controllers.get_mut(&id).ok_or(GameError::NoController(id))?;
+        outcome = loop {
+            let view = game.view_for(game.current_seat())?;
+            match game.choose_swap_target(controller.choose_swap_target(&view)) {
+                Ok(outcome) => break outcome,
+                Err(error) => controller.rejected(&view, &error)
+            }
+        };
+    }
+    Ok(outcome)
+}
+
+// Give a player who has just dropped to one card the chance to call Uno
+fn offer_uno_call(game: &mut Game, controllers: &mut Controllers, narrator: &mut dyn Narrator, id: PlayerId)
+    -> Result<(), GameError>
+{
+    let Some(seat) = game.seat_of(id) else {
+        return Ok(());
+    };
+    let player = &game.players()[seat];
+    if game.is_over() || player.number_of_cards() != 1 || player.has_called_uno() {
+        return Ok(());
+    }
+
+    let Some(controller) = controllers.get_mut(&id) else {
+        return Ok(());
+    };
+    if controller.wants_to_call_uno(&game.view_for(seat)?) {
+        game.call_uno(seat)?;
+        narrator.report(game, &Report::UnoCalled(id));
+    }
+    Ok(())
+}
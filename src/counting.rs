@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use crate::game::{Card, CardType, Color, Digit, Game};
+
+const COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+
+// Counts the cards a player hasn't seen yet, meaning those still in the deck or in
+// other players' hands. It starts from a deck composition and is told about every
+// card the player sees: the pile and their own hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCounter
+{
+    unseen: HashMap<Card, usize>
+}
+
+impl CardCounter
+{
+    pub fn new(composition: HashMap<Card, usize>) -> CardCounter
+    {
+        CardCounter { unseen: composition }
+    }
+
+    // The official 108 card deck
+    pub fn standard() -> CardCounter
+    {
+        let mut composition = HashMap::new();
+        for color in COLORS {
+            for digit in Digit::all() {
+                let copies = if digit == Digit::ZERO { 1 } else { 2 };
+                composition.insert(Card::new(CardType::Number(digit), color), copies);
+            }
+            for card_type in [CardType::Skip, CardType::Reverse, CardType::DrawTwo] {
+                composition.insert(Card::new(card_type, color), 2);
+            }
+        }
+        composition.insert(Card::wildcard(), 4);
+        composition.insert(Card::draw_four(), 4);
+        CardCounter::new(composition)
+    }
+
+    // A standard deck counted from the point of view of the player in `seat`
+    pub fn for_player(game: &Game, seat: usize) -> Option<CardCounter>
+    {
+        let player = game.players().get(seat)?;
+        let mut counter = CardCounter::standard();
+        for &card in game.discarded().iter().chain(player.cards()) {
+            counter.see(card);
+        }
+        Some(counter)
+    }
+
+    // Take a card the player has seen out of the unseen count. Seeing more copies
+    // than the composition holds, which an infinite deck allows, stops at zero
+    pub fn see(&mut self, card: Card)
+    {
+        if let Some(count) = self.unseen.get_mut(&card) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    // How many copies of a card are unseen. The color is ignored for wildcards
+    pub fn remaining(&self, card_type: CardType, color: Color) -> usize
+    {
+        self.unseen.get(&Card::new(card_type, color)).copied().unwrap_or(0)
+    }
+
+    // Every card with copies still unseen, in no particular order
+    pub fn unseen(&self) -> impl Iterator<Item = (Card, usize)> + '_
+    {
+        self.unseen.iter().filter(|(_, count)| **count > 0).map(|(card, count)| (*card, *count))
+    }
+
+    pub fn total_remaining(&self) -> usize
+    {
+        self.unseen.values().sum()
+    }
+
+    // The chance that a card drawn from the unseen cards could be played on `top`
+    // while `active_color` is in play
+    pub fn probability_next_draw_playable(&self, top: Card, active_color: Option<Color>) -> f64
+    {
+        let total = self.total_remaining();
+        if total == 0 {
+            return 0.0;
+        }
+
+        let playable: usize = self.unseen.iter()
+            .filter(|(card, _)| card.is_playable_on(top, active_color))
+            .map(|(_, count)| count)
+            .sum();
+        playable as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::config::{GameConfig, StartingPlayer};
+    use crate::game::Lobby;
+
+    fn card(name: &str) -> Card
+    {
+        name.parse().unwrap()
+    }
+
+    // P0 holds red 1, red 5 and blue 2 and P1 holds green 3, yellow 4 and a wildcard,
+    // with a red 5 turned over and P0 to go first
+    fn game(draws: &[&str]) -> Game
+    {
+        let mut lobby = Lobby::with_players(["P0", "P1"]).unwrap();
+        lobby.set_config(GameConfig { starting_player: StartingPlayer::Seat(0), hand_size: Some(3),
+            ..GameConfig::default() });
+        let deck = ["red 5", "red 1", "red 5", "blue 2", "green 3", "yellow 4", "wild"].iter()
+            .chain(draws)
+            .map(|name| card(name))
+            .collect();
+        lobby.start_with_deck(0, deck).unwrap()
+    }
+
+    #[test]
+    fn a_standard_deck_has_108_cards()
+    {
+        let counter = CardCounter::standard();
+        assert_eq!(counter.total_remaining(), 108);
+        assert_eq!(counter.remaining(CardType::Number(Digit::ZERO), Color::Blue), 1);
+        assert_eq!(counter.remaining(CardType::Skip, Color::Yellow), 2);
+        assert_eq!(counter.remaining(CardType::DrawFourWildcard, Color::Green), 4);
+    }
+
+    #[test]
+    fn counts_follow_plays_and_draws()
+    {
+        let mut game = game(&["blue 7", "blue 9"]);
+        let counter = CardCounter::for_player(&game, 0).unwrap();
+        assert_eq!(counter.total_remaining(), 104);
+        assert_eq!(counter.remaining(CardType::Number(Digit::FIVE), Color::Red), 0);
+        assert_eq!(counter.remaining(CardType::Number(Digit::ONE), Color::Red), 1);
+
+        // A card played is still only seen once, and one drawn by someone else stays unseen
+        game.play(0).unwrap();
+        assert!(!game.draw_one().unwrap().playable);
+        let counter = CardCounter::for_player(&game, 0).unwrap();
+        assert_eq!(counter.total_remaining(), 104);
+        assert_eq!(counter.remaining(CardType::Number(Digit::ONE), Color::Red), 1);
+        assert_eq!(counter.remaining(CardType::Number(Digit::SEVEN), Color::Blue), 2);
+
+        // P1 has seen their own draw and both cards on the pile
+        let counter = CardCounter::for_player(&game, 1).unwrap();
+        assert_eq!(counter.total_remaining(), 102);
+        assert_eq!(counter.remaining(CardType::Number(Digit::SEVEN), Color::Blue), 1);
+        assert_eq!(counter.remaining(CardType::Wildcard, Color::Red), 3);
+
+        assert!(!game.draw_one().unwrap().playable);
+        let counter = CardCounter::for_player(&game, 0).unwrap();
+        assert_eq!(counter.total_remaining(), 103);
+        assert_eq!(counter.remaining(CardType::Number(Digit::NINE), Color::Blue), 1);
+    }
+
+    #[test]
+    fn the_chance_of_drawing_something_playable()
+    {
+        let counter = CardCounter::new(HashMap::from([(card("red 1"), 1), (card("blue 2"), 3), (card("wild"), 1)]));
+        assert_eq!(counter.probability_next_draw_playable(card("red 5"), Some(Color::Red)), 0.4);
+        assert_eq!(counter.probability_next_draw_playable(card("blue 5"), Some(Color::Blue)), 0.8);
+
+        let mut counter = counter;
+        for name in ["red 1", "blue 2", "blue 2", "blue 2", "wild", "wild"] {
+            counter.see(card(name));
+        }
+        assert_eq!(counter.total_remaining(), 0);
+        assert_eq!(counter.probability_next_draw_playable(card("red 5"), Some(Color::Red)), 0.0);
+    }
+}
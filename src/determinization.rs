@@ -0,0 +1,213 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::game::{Card, Color, Game};
+use crate::view::BotObservation;
+
+// Guesses at the hidden part of a game for search bots. The cards a player hasn't
+// seen are dealt at random into the other players' hands, at their real sizes, and
+// whatever is left becomes the top of the draw pile. The game's deck is infinite, so
+// hands can hold more copies of a card than the standard deck has; any shortfall is
+// made up from the deck's usual odds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Determinizer
+{
+    // Keep the colors a player seems to be out of away from them, as far as the
+    // unseen cards allow. The opponent model only guesses at these, so it's off by
+    // default
+    pub respect_voids: bool
+}
+
+impl Determinizer
+{
+    pub fn new() -> Determinizer
+    {
+        Determinizer::default()
+    }
+
+    pub fn with_voids() -> Determinizer
+    {
+        Determinizer { respect_voids: true }
+    }
+
+    // A complete game that agrees with everything in the observation, ready to be
+    // played out
+    pub fn sample<R: Rng>(&self, observation: &BotObservation, rng: &mut R) -> Game
+    {
+        let view = &observation.view;
+        let mut pool: Vec<Card> = observation.unseen.iter()
+            .flat_map(|(card, count)| std::iter::repeat_n(*card, *count))
+            .collect();
+        pool.shuffle(rng);
+
+        let mut hands = vec![Vec::new(); view.table.players.len()];
+        let mut opponents: Vec<usize> = view.opponents().map(|(seat, _)| seat).collect();
+        if self.respect_voids {
+            // The players who can take the fewest cards go first, while there's the
+            // most to choose from
+            opponents.sort_by_key(|seat| std::cmp::Reverse(voids(observation, *seat).len()));
+            for &seat in &opponents {
+                let wanted = view.table.players[seat].number_of_cards;
+                let voids = voids(observation, seat);
+                let mut index = 0;
+                while hands[seat].len() < wanted && index < pool.len() {
+                    if pool[index].color.is_some_and(|color| voids.contains(&color)) {
+                        index += 1;
+                    } else {
+                        hands[seat].push(pool.remove(index));
+                    }
+                }
+            }
+        }
+
+        // Anyone still short takes what's left, voids or not
+        for &seat in &opponents {
+            let wanted = view.table.players[seat].number_of_cards;
+            let missing = wanted.saturating_sub(hands[seat].len()).min(pool.len());
+            hands[seat].extend(pool.drain(..missing));
+        }
+
+        Game::determinize_with(view, hands, pool, rng.gen())
+    }
+}
+
+fn voids(observation: &BotObservation, seat: usize) -> &[Color]
+{
+    observation.likely_void_colors.get(seat).map_or(&[], |colors| colors.as_slice())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::game::{Lobby, Move, PendingAction};
+
+    // Three players part way through a seven-zero game, so some hands have changed
+    // owners. Everyone makes the first legal move
+    fn game_under_way(seed: u64, actions: usize) -> Game
+    {
+        let mut config = GameConfig::default();
+        config.set_rule("seven-zero", true).unwrap();
+        let mut lobby = Lobby::with_players(["A", "B", "C"]).unwrap();
+        lobby.set_config(config);
+        let mut game = lobby.start_with_seed(seed).unwrap();
+        for _ in 0..actions {
+            if game.is_over() {
+                break;
+            }
+            let next_seat = (game.current_seat() + 1) % 3;
+            let result = match game.pending_action() {
+                Some(PendingAction::AwaitingWildColor) => game.set_wildcard_color(Color::Blue).map(drop),
+                Some(PendingAction::ChooseSwapTarget) => game.choose_swap_target(next_seat).map(drop),
+                _ => match game.legal_moves()[0] {
+                    Move::Play(index) => game.play(index).map(drop),
+                    Move::Draw => game.draw_one().map(drop),
+                    Move::PlayDrawn => game.play_drawn().map(drop),
+                    Move::KeepDrawn => game.keep_drawn().map(drop),
+                    Move::AcceptPenalty => game.accept_penalty().map(drop),
+                    Move::ChallengeDrawFour => game.challenge_draw_four().map(drop)
+                }
+            };
+            result.unwrap();
+        }
+        game
+    }
+
+    fn counts<'a>(cards: impl Iterator<Item = &'a Card>) -> HashMap<Card, usize>
+    {
+        let mut counts = HashMap::new();
+        for card in cards {
+            *counts.entry(*card).or_default() += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn sampled_games_match_the_observation()
+    {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for seed in 0..50 {
+            let game = game_under_way(seed, seed as usize % 40);
+            if game.is_over() {
+                continue;
+            }
+            let seat = game.current_seat();
+            let observation = game.observe(seat).unwrap();
+            let unseen: HashMap<Card, usize> = observation.unseen.iter().copied().collect();
+
+            for _ in 0..20 {
+                let sampled = Determinizer::new().sample(&observation, &mut rng);
+                assert_eq!(sampled.check_invariants(), Ok(()));
+                assert_eq!(sampled.players()[seat].cards(), observation.hand());
+                for (player, public) in sampled.players().iter().zip(&observation.view.table.players) {
+                    assert_eq!((player.id(), player.number_of_cards()), (public.id, public.number_of_cards));
+                }
+                assert_eq!(sampled.top_card(), observation.top_card());
+                assert_eq!(sampled.active_color(), observation.view.table.active_color);
+                assert_eq!(sampled.current_seat(), seat);
+                assert_eq!(sampled.legal_moves(), observation.legal_moves);
+
+                // The hidden hands are dealt from the unseen cards, never more copies
+                // than are left
+                let hidden = counts(observation.view.opponents()
+                    .flat_map(|(other, _)| sampled.players()[other].cards()));
+                for (card, copies) in hidden {
+                    assert!(copies <= unseen.get(&card).copied().unwrap_or(0), "seed {}: {} x {}", seed, copies, card);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unseen_cards_are_dealt_evenly()
+    {
+        let game = game_under_way(3, 10);
+        let observation = game.observe(game.current_seat()).unwrap();
+        let pool: usize = observation.unseen.iter().map(|(_, count)| count).sum();
+        let held: usize = observation.view.opponents().map(|(_, public)| public.number_of_cards).sum();
+
+        let samples = 4000;
+        let mut rng = SmallRng::seed_from_u64(1);
+        let mut dealt: HashMap<Card, usize> = HashMap::new();
+        for _ in 0..samples {
+            let sampled = Determinizer::new().sample(&observation, &mut rng);
+            for (seat, _) in observation.view.opponents() {
+                for card in sampled.players()[seat].cards() {
+                    *dealt.entry(*card).or_default() += 1;
+                }
+            }
+        }
+
+        // Each unseen copy lands in a hidden hand with the same chance
+        for (card, copies) in &observation.unseen {
+            let expected = (*copies * held) as f64 / pool as f64;
+            let actual = dealt.get(card).copied().unwrap_or(0) as f64 / samples as f64;
+            assert!((actual - expected).abs() < 0.05, "{} was dealt {} times a game, not {}", card, actual, expected);
+        }
+    }
+
+    #[test]
+    fn likely_void_colors_are_kept_away()
+    {
+        let game = game_under_way(5, 6);
+        let seat = game.current_seat();
+        let mut observation = game.observe(seat).unwrap();
+        let opponents: Vec<usize> = observation.view.opponents().map(|(other, _)| other).collect();
+        observation.likely_void_colors[opponents[0]] = vec![Color::Red, Color::Green];
+
+        let mut rng = SmallRng::seed_from_u64(2);
+        for _ in 0..200 {
+            let sampled = Determinizer::with_voids().sample(&observation, &mut rng);
+            let cards = sampled.players()[opponents[0]].cards();
+            assert!(cards.iter().all(|card| !matches!(card.color, Some(Color::Red | Color::Green))), "{:?}", cards);
+            let cards = sampled.players()[opponents[1]].cards();
+            assert_eq!(cards.len(), observation.view.table.players[opponents[1]].number_of_cards);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::config::RULE_NAMES;
+use crate::game::{Card, Color, PendingAction, PlayerId, MIN_PLAYERS};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError
+{
+    Empty,
+    TooLong { max_length: usize },
+    Taken(String),
+    InvalidCharacters
+}
+
+impl fmt::Display for NameError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result 
+    {
+        match self {
+            NameError::Empty => write!(f, "Usernames cannot be empty"),
+            NameError::TooLong { max_length } =>
+                write!(f, "Usernames can be at most {} characters long", max_length),
+            NameError::Taken(name) => write!(f, "Username '{}' is already taken", name),
+            NameError::InvalidCharacters => write!(f, "Usernames cannot contain control characters")
+        }
+    }
+}
+
+impl Error for NameError {}
+
+// A number card's value must be 0 to 9
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDigit(pub u8);
+
+impl fmt::Display for InvalidDigit
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{} is not a digit from 0 to 9", self.0)
+    }
+}
+
+impl Error for InvalidDigit {}
+
+// Text that doesn't name a color or a card, see the `FromStr` impls in `game`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError
+{
+    UnknownColor(String),
+    UnknownCard(String),
+    // Only wildcards can be named without a color
+    NoColor(String)
+}
+
+impl fmt::Display for ParseCardError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            ParseCardError::UnknownColor(text) =>
+                write!(f, "'{}' is not a color. Try red, green, blue or yellow", text),
+            ParseCardError::UnknownCard(text) =>
+                write!(f, "'{}' is not a card. Try something like 'red 5', 'blue skip' or 'wild'", text),
+            ParseCardError::NoColor(text) => write!(f, "'{}' needs a color, like 'red {}'", text, text)
+        }
+    }
+}
+
+impl Error for ParseCardError {}
+
+// A house rule name that isn't one of `config::RULE_NAMES`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownRule(pub String);
+
+impl fmt::Display for UnknownRule
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "'{}' is not a house rule. The rules are: {}", self.0, RULE_NAMES.join(", "))
+    }
+}
+
+impl Error for UnknownRule {}
+
+// Every fallible operation on a Lobby or a Game reports one of these
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError
+{
+    // Lobby errors
+    NotEnoughPlayers,
+    LobbyFull { max_players: usize },
+    InvalidName(NameError),
+    InvalidSeat(usize),
+    PlayerIdTaken(PlayerId),
+
+    // Errors while playing
+    NotYourTurn { seat: usize },
+    InvalidCardIndex { given: usize, hand_size: usize },
+    CardUnplayable { attempted: Card, top: Card },
+    NotAWildcard(Card),
+    MustPlay { playable_indices: Vec<usize> },
+    ActionPending(PendingAction),
+    CannotStack { attempted: Card, top: Card },
+    CannotGoOutOnWild(Card),
+    PenaltyLimitReached { max_penalty: u8 },
+    NoPenaltyPending,
+    NoDrawnCard,
+    DrawUntilPlayableDisabled,
+    PlayManyDisabled,
+    NoCardsSelected,
+    DuplicateCardIndex(usize),
+    CardsNotIdentical { first: Card, other: Card },
+    CannotPlayTogether(Card),
+    NoChallengeAvailable,
+    GameOver,
+
+    CannotCallUno,
+    FalseUnoAccusation,
+    InvalidSwapTarget(usize),
+    NoSwapPending,
+
+    // Errors while jumping in
+    JumpInDisabled,
+    NotIdentical { attempted: Card, top: Card },
+    JumpInTooLate,
+    NoIdenticalCard { seat: usize, top: Card },
+
+    // Errors while choosing a wildcard color
+    TopCardNotWild,
+    NoWildColorPending,
+
+    // Errors while running a game with `controller::run_game`
+    NoController(PlayerId)
+}
+
+// The separate error types have been folded into GameError
+pub type LobbyError = GameError;
+pub type PlayError = GameError;
+pub type WildColorError = GameError;
+pub type JumpInError = GameError;
+
+impl fmt::Display for GameError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result 
+    {
+        match self {
+            GameError::NotEnoughPlayers =>
+                write!(f, "You cannot start the game until you have at least {} players!", MIN_PLAYERS),
+            GameError::LobbyFull { max_players } =>
+                write!(f, "The lobby is full! A game can have at most {} players", max_players),
+            GameError::InvalidName(error) => write!(f, "{}", error),
+            GameError::InvalidSeat(seat) =>
+                write!(f, "There is no seat number {} in the lobby", seat + 1),
+            GameError::PlayerIdTaken(id) => write!(f, "Player id {} is already taken", id),
+            GameError::NotYourTurn { seat } =>
+                write!(f, "The player in seat {} has to wait for their turn", seat + 1),
+            GameError::InvalidCardIndex { given, hand_size } =>
+                write!(f, "Card index {} is out of range for a hand of {} cards", given, hand_size),
+            GameError::CardUnplayable { attempted, top } =>
+                write!(f, "A {} cannot be played on a {}", attempted, top),
+            GameError::NotAWildcard(card) => write!(f, "A {} is not a wildcard", card),
+            GameError::CannotStack { attempted, top } =>
+                write!(f, "A {} cannot be stacked on a {} in this game", attempted, top),
+            GameError::CannotGoOutOnWild(card) =>
+                write!(f, "A {} can't be the last card played in this game", card),
+            GameError::PenaltyLimitReached { max_penalty } =>
+                write!(f, "The penalty can't be raised above {} cards", max_penalty),
+            GameError::PlayManyDisabled => write!(f, "Playing several cards at once is not allowed in this game"),
+            GameError::NoCardsSelected => write!(f, "No cards were chosen to play"),
+            GameError::DuplicateCardIndex(index) => write!(f, "Card {} was chosen more than once", index + 1),
+            GameError::CardsNotIdentical { first, other } =>
+                write!(f, "Only identical cards can be played together, and a {} is not a {}", other, first),
+            GameError::CannotPlayTogether(card) => write!(f, "A {} has to be played on its own", card),
+            GameError::MustPlay { .. } => write!(f, "You must play a card instead of drawing when you are able to"),
+            GameError::ActionPending(action) => write!(f, "{}", action),
+            GameError::NoPenaltyPending => write!(f, "There is no draw penalty to accept"),
+            GameError::NoDrawnCard => write!(f, "There is no drawn card waiting to be played or kept"),
+            GameError::DrawUntilPlayableDisabled => write!(f, "Drawing until a playable card turns up is not allowed in this game"),
+            GameError::NoChallengeAvailable => write!(f, "There is no Draw Four that can be challenged"),
+            GameError::GameOver => write!(f, "The game is already over"),
+            GameError::CannotCallUno =>
+                write!(f, "Uno can only be called with one card left, or two cards on your own turn"),
+            GameError::FalseUnoAccusation => write!(f, "That player doesn't need to call Uno"),
+            GameError::InvalidSwapTarget(seat) =>
+                write!(f, "The player in seat {} can't be chosen to swap hands with", seat + 1),
+            GameError::NoSwapPending => write!(f, "There is no hand swap waiting for a target"),
+            GameError::JumpInDisabled => write!(f, "Jumping in is not allowed in this game"),
+            GameError::JumpInTooLate => write!(f, "You can only jump in right after a card is played"),
+            GameError::NotIdentical { attempted, top } =>
+                write!(f, "You can only jump in with a card identical to the top card, and a {} is not a {}", attempted, top),
+            GameError::NoIdenticalCard { seat, top } =>
+                write!(f, "The player in seat {} has no card identical to the {}", seat + 1, top),
+            GameError::TopCardNotWild => write!(f, "The top card is not a wildcard"),
+            GameError::NoWildColorPending => write!(f, "The wildcard on top already has its color"),
+            GameError::NoController(player) => write!(f, "Nobody is controlling player {}", player)
+        }
+    }
+}
+
+impl Error for GameError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self {
+            GameError::InvalidName(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+impl From<NameError> for GameError
+{
+    fn from(error: NameError) -> Self
+    {
+        GameError::InvalidName(error)
+    }
+}
+
+// Something about a game's state that should never happen. Returned by
+// `Game::check_invariants`, which debug builds run after every change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation
+{
+    NoPlayers,
+    CurrentSeatOutOfRange { seat: usize, number_of_players: usize },
+    DuplicatePlayerId(PlayerId),
+    // An id so large that no bigger one is left for the next player to join
+    PlayerIdOutOfRange(PlayerId),
+    // A colored card without a color, or a wildcard with one
+    MiscoloredCard { card: Card, in_hand: bool },
+    TopCardNotOnPile { top: Card, last_discarded: Option<Card> },
+    // The active color differs from the color of the non-wild card on top
+    ActiveColorMismatch { top: Card, active_color: Option<Color> },
+    UncoloredWildWithoutPendingAction,
+    PendingWildColorWithoutWild(Card),
+    DrawnCardOutOfRange { index: usize, hand_size: usize },
+    DrawnCardWithoutPendingAction,
+    PendingActionWithoutDrawnCard,
+    ActionPendingAfterGameOver(PendingAction)
+}
+
+impl fmt::Display for InvariantViolation
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            InvariantViolation::NoPlayers => write!(f, "The game has no players"),
+            InvariantViolation::CurrentSeatOutOfRange { seat, number_of_players } =>
+                write!(f, "The current seat {} is out of range for {} players", seat, number_of_players),
+            InvariantViolation::DuplicatePlayerId(id) => write!(f, "Player id {} is used more than once", id),
+            InvariantViolation::PlayerIdOutOfRange(id) =>
+                write!(f, "Player id {} leaves no id for the next player to join", id),
+            InvariantViolation::MiscoloredCard { card, in_hand: true } =>
+                write!(f, "A {} in a player's hand has the wrong color", card),
+            InvariantViolation::MiscoloredCard { card, in_hand: false } =>
+                write!(f, "A {} on the pile has the wrong color", card),
+            InvariantViolation::TopCardNotOnPile { top, last_discarded: Some(last) } =>
+                write!(f, "The top card is a {} but the last card on the pile is a {}", top, last),
+            InvariantViolation::TopCardNotOnPile { top, last_discarded: None } =>
+                write!(f, "The top card is a {} but the pile is empty", top),
+            InvariantViolation::ActiveColorMismatch { top, active_color: Some(color) } =>
+                write!(f, "The top card is a {} but the active color is {}", top, color),
+            InvariantViolation::ActiveColorMismatch { top, active_color: None } =>
+                write!(f, "The top card is a {} but there is no active color", top),
+            InvariantViolation::UncoloredWildWithoutPendingAction =>
+                write!(f, "The wildcard on top has no color and nobody has been asked to choose one"),
+            InvariantViolation::PendingWildColorWithoutWild(top) =>
+                write!(f, "A color for the wildcard has been asked for but the top card is a {}", top),
+            InvariantViolation::DrawnCardOutOfRange { index, hand_size } =>
+                write!(f, "The drawn card is at index {} of a hand of {} cards", index, hand_size),
+            InvariantViolation::DrawnCardWithoutPendingAction =>
+                write!(f, "A drawn card is waiting but the player hasn't been asked to play or keep it"),
+            InvariantViolation::PendingActionWithoutDrawnCard =>
+                write!(f, "The player has been asked to play or keep a drawn card but there isn't one"),
+            InvariantViolation::ActionPendingAfterGameOver(action) =>
+                write!(f, "The game is over but an action is still pending: {}", action)
+        }
+    }
+}
+
+impl Error for InvariantViolation {}
@@ -0,0 +1,40 @@
+use crate::game::{Card, Color, Direction, PlayerId};
+
+// Everything that happens in a game is recorded as an event. Players are
+// referred to by id rather than by seat or name so the log stays meaningful
+// after players leave or are renamed
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent
+{
+    CardDealt { player: PlayerId, card: Card },
+    // A card dealt face up to choose the first player, see `StartingPlayer::DrawForDeal`
+    DrewForDeal { player: PlayerId, card: Card },
+    GameStarted { players: Vec<PlayerId>, top_card: Card },
+    TurnStarted { player: PlayerId },
+    CardPlayed { player: PlayerId, card: Card },
+    CardDrawn { player: PlayerId, card: Card },
+    WildColorChosen { player: PlayerId, color: Color },
+    TurnSkipped { player: PlayerId },
+    JumpedIn { player: PlayerId, interrupted: PlayerId },
+    HandsSwapped { player: PlayerId, target: PlayerId },
+    HandsRotated,
+    PlayerJoined { player: PlayerId },
+    PlayerLeft { player: PlayerId },
+    UnoCalled { player: PlayerId },
+    UnoCaught { accuser: PlayerId, target: PlayerId, caught: bool },
+    DrawFourChallenged { challenger: PlayerId, offender: PlayerId, offender_guilty: bool },
+    // Carries the direction of play after the reverse
+    DirectionReversed { direction: Direction },
+    TurnLimitReached
+}
+
+// An event along with when it happened
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedEvent
+{
+    pub turn: u32,
+    pub action: u32,
+    pub event: GameEvent
+}
@@ -1,13 +1,29 @@
+use std::collections::VecDeque;
 use std::fmt;
+use std::str::FromStr;
 
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
 use rand::distributions::{Distribution, Uniform};
+use rand::seq::SliceRandom;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::config::{GameConfig, StalematePolicy, StartingPlayer};
+use crate::controller::{Controllers, PlayerController};
+use crate::counting::CardCounter;
+use crate::error::{GameError, InvalidDigit, InvariantViolation, JumpInError, NameError, ParseCardError};
+use crate::events::{GameEvent, RecordedEvent};
+use crate::history::{ActionKind, ActionRecord, TurnAction, TurnRecord};
+use crate::names::{BotNames, NamePack};
+use crate::opponents::{OpponentModel, OpponentProfile};
+use crate::rules::{RuleEffect, RuleSet, StrictColorMatching};
+use crate::stats::{GameStats, PlayerStats};
+use crate::view::{BotObservation, PlayerView, PublicPlayer, SpectatorView};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Color
 {
-    Red, Green, Blue, Yellow, Unpicked
+    Red, Green, Blue, Yellow
 }
 
 impl fmt::Display for Color
@@ -18,16 +34,137 @@ impl fmt::Display for Color
             Color::Red => "Red",
             Color::Green => "Green",
             Color::Blue => "Blue",
-            Color::Yellow => "Yellow",
-            Color::Unpicked => "Unpicked"
+            Color::Yellow => "Yellow"
+        })
+    }
+}
+
+// Ignores case, and takes the first letter on its own, like "r"
+impl FromStr for Color
+{
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Color, ParseCardError>
+    {
+        match text.trim().to_lowercase().as_str() {
+            "red" | "r" => Ok(Color::Red),
+            "green" | "g" => Ok(Color::Green),
+            "blue" | "b" => Ok(Color::Blue),
+            "yellow" | "y" => Ok(Color::Yellow),
+            _ => Err(ParseCardError::UnknownColor(text.trim().to_owned()))
+        }
+    }
+}
+
+// The direction of play around the table. Clockwise moves to the next seat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction
+{
+    #[default]
+    Clockwise,
+    CounterClockwise
+}
+
+impl Direction
+{
+    pub fn reversed(self) -> Direction
+    {
+        match self {
+            Direction::Clockwise => Direction::CounterClockwise,
+            Direction::CounterClockwise => Direction::Clockwise
+        }
+    }
+
+    // e.g. "↻ clockwise", for the end of a turn order
+    pub fn arrow(self) -> &'static str
+    {
+        match self {
+            Direction::Clockwise => "↻ clockwise",
+            Direction::CounterClockwise => "↺ counter-clockwise"
+        }
+    }
+}
+
+impl fmt::Display for Direction
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", match *self {
+            Direction::Clockwise => "Clockwise",
+            Direction::CounterClockwise => "Counter Clockwise"
         })
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+// The value of a number card, which is always 0 to 9. Build one with `Digit::new`,
+// `Digit::try_from` or one of the constants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u8", into = "u8"))]
+pub struct Digit(u8);
+
+impl Digit
+{
+    pub const ZERO: Digit = Digit(0);
+    pub const ONE: Digit = Digit(1);
+    pub const TWO: Digit = Digit(2);
+    pub const THREE: Digit = Digit(3);
+    pub const FOUR: Digit = Digit(4);
+    pub const FIVE: Digit = Digit(5);
+    pub const SIX: Digit = Digit(6);
+    pub const SEVEN: Digit = Digit(7);
+    pub const EIGHT: Digit = Digit(8);
+    pub const NINE: Digit = Digit(9);
+
+    pub fn new(value: u8) -> Result<Digit, InvalidDigit>
+    {
+        if value <= 9 { Ok(Digit(value)) } else { Err(InvalidDigit(value)) }
+    }
+
+    pub fn value(self) -> u8
+    {
+        self.0
+    }
+
+    // Every digit from 0 to 9
+    pub fn all() -> impl Iterator<Item = Digit>
+    {
+        (0..=9).map(Digit)
+    }
+}
+
+impl TryFrom<u8> for Digit
+{
+    type Error = InvalidDigit;
+
+    fn try_from(value: u8) -> Result<Digit, InvalidDigit>
+    {
+        Digit::new(value)
+    }
+}
+
+impl From<Digit> for u8
+{
+    fn from(digit: Digit) -> u8
+    {
+        digit.0
+    }
+}
+
+impl fmt::Display for Digit
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CardType
 {
-    Number(u8), Skip, Reverse, DrawTwo,
+    Number(Digit), Skip, Reverse, DrawTwo,
     Wildcard, DrawFourWildcard
 }
 
@@ -63,23 +200,95 @@ impl fmt::Display for CardType
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+// What's written on a card without its color, the way it's displayed or the way people
+// say it: "draw 2", "draw two" and "+2" are all a Draw 2
+impl FromStr for CardType
+{
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<CardType, ParseCardError>
+    {
+        let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+        match words.join(" ").as_str() {
+            "skip" => Ok(CardType::Skip),
+            "reverse" | "rev" => Ok(CardType::Reverse),
+            "draw 2" | "draw two" | "draw2" | "+2" => Ok(CardType::DrawTwo),
+            "wildcard" | "wild" => Ok(CardType::Wildcard),
+            "draw 4 wildcard" | "draw 4 wild" | "wild draw 4" | "wild draw four" | "draw 4" | "draw four" | "draw4"
+                | "+4" | "wild +4" => Ok(CardType::DrawFourWildcard),
+            number => number.parse::<u8>().ok()
+                .filter(|_| number.len() == 1)
+                .and_then(|number| Digit::new(number).ok())
+                .map(CardType::Number)
+                .ok_or(ParseCardError::UnknownCard(text.trim().to_owned()))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Card
 {
     pub card_type: CardType,
-    pub color: Color
+    // Wildcards never have a color, the color chosen for one is the game's active color
+    pub color: Option<Color>
 }
 
 impl Card
 {
+    // The color is dropped for wildcards
     pub fn new(card_type: CardType, color: Color) -> Card 
     {
-        Card { card_type, color }
+        match card_type {
+            CardType::Wildcard | CardType::DrawFourWildcard => Card { card_type, color: None },
+            _ => Card { card_type, color: Some(color) }
+        }
+    }
+
+    pub fn wildcard() -> Card
+    {
+        Card { card_type: CardType::Wildcard, color: None }
+    }
+
+    pub fn draw_four() -> Card
+    {
+        Card { card_type: CardType::DrawFourWildcard, color: None }
+    }
+
+    pub fn is_wild(&self) -> bool
+    {
+        matches!(self.card_type, CardType::Wildcard | CardType::DrawFourWildcard)
+    }
+
+    // Where the card goes in a sorted hand: grouped by color in the order the colors
+    // are declared, numbers in order and then action cards, with wildcards last
+    pub fn sort_key(&self) -> (u8, u8)
+    {
+        let rank = match self.card_type {
+            CardType::Number(value) => value.value(),
+            CardType::Skip => 10,
+            CardType::Reverse => 11,
+            CardType::DrawTwo => 12,
+            CardType::Wildcard => 13,
+            CardType::DrawFourWildcard => 14
+        };
+        (self.color.map_or(4, |color| color as u8), rank)
+    }
+
+    // What the card is worth when scoring a hand
+    pub fn points(&self) -> u32
+    {
+        match self.card_type {
+            CardType::Number(value) => value.value() as u32,
+            CardType::Skip | CardType::Reverse | CardType::DrawTwo => 20,
+            CardType::Wildcard | CardType::DrawFourWildcard => 50
+        }
     }
 
-    pub fn is_playable_on(&self, card: Card) -> bool
+    // Whether this card can go on `top` when the active color is `active_color`
+    pub fn is_playable_on(&self, top: Card, active_color: Option<Color>) -> bool
     {
-        match (self.card_type, card.card_type) {
+        match (self.card_type, top.card_type) {
             // You can play a wildcard on any other card
             (CardType::Wildcard, _) | (CardType::DrawFourWildcard, _) |
 
@@ -91,8 +300,8 @@ impl Card
             // If both cards are of type Number and they have the same value
             (CardType::Number(value1), CardType::Number(value2)) if value1 == value2 => true,
 
-            // If the colors of the cards match
-            _ => self.color == card.color
+            // If the card matches the active color
+            _ => self.color.is_some() && self.color == active_color
         }
     }
 }
@@ -100,37 +309,73 @@ impl Card
 impl fmt::Display for Card
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.color == Color::Unpicked {
-            write!(f, "{}", self.card_type)
-        } else {
-            write!(f, "{} {}", self.color, self.card_type)
-        }   
+        match self.color {
+            Some(color) => write!(f, "{} {}", color, self.card_type),
+            None => write!(f, "{}", self.card_type)
+        }
+    }
+}
+
+// A color and what's on the card in either order, like "red 5" or "skip blue". A color
+// given with a wildcard is dropped, as it is by `Card::new`
+impl FromStr for Card
+{
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Card, ParseCardError>
+    {
+        let (colors, rest): (Vec<&str>, Vec<&str>) =
+            text.split_whitespace().partition(|word| word.parse::<Color>().is_ok());
+        let card_type: CardType = rest.join(" ").parse()
+            .map_err(|_| ParseCardError::UnknownCard(text.trim().to_owned()))?;
+        match (colors.as_slice(), card_type) {
+            (_, CardType::Wildcard | CardType::DrawFourWildcard) => Ok(Card { card_type, color: None }),
+            ([color], card_type) => Ok(Card::new(card_type, color.parse()?)),
+            ([], _) => Err(ParseCardError::NoColor(rest.join(" "))),
+            _ => Err(ParseCardError::UnknownCard(text.trim().to_owned()))
+        }
     }
 }
 
-struct InfiniteDeck
+// Draws every card with the odds it has in a full 108 card deck, without ever running
+// out, so drawn cards are never put back
+#[derive(Clone)]
+pub struct InfiniteDeck
 {
     rng: SmallRng,
-    uniform: Uniform<u8>
+    uniform: Uniform<u8>,
+    // Cards to hand out before any random ones, the last first
+    stacked: Vec<Card>
 }
 
 impl InfiniteDeck
 {
-    fn new() -> InfiniteDeck 
+    pub fn seeded(seed: u64) -> InfiniteDeck
     {
         Self {
-            rng: SmallRng::from_entropy(),
-            uniform: Uniform::new_inclusive(0, 107)
+            rng: SmallRng::seed_from_u64(seed),
+            uniform: Uniform::new_inclusive(0, 107),
+            stacked: Vec::new()
         }
     }
 
-    fn draw(&mut self) -> Card 
+    // Swap in fresh randomness so the deck no longer follows the sequence it was on
+    fn reseed(&mut self, seed: u64)
     {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
+    pub fn draw(&mut self) -> Card 
+    {
+        if let Some(card) = self.stacked.pop() {
+            return card;
+        }
+
         let card_seed = self.uniform.sample(&mut self.rng);
         let card_type = match card_seed % 27 {
-            0 => CardType::Number(0),
-            seed @ 1..=9 => CardType::Number(seed),
-            seed @ 10..=18 => CardType::Number(seed-9),
+            0 => CardType::Number(Digit::ZERO),
+            seed @ 1..=9 => CardType::Number(Digit(seed)),
+            seed @ 10..=18 => CardType::Number(Digit(seed-9)),
             19..=20 => CardType::Skip,
             21..=22 => CardType::Reverse,
             23..=24 => CardType::DrawTwo,
@@ -139,31 +384,52 @@ impl InfiniteDeck
             _ => unreachable!()
         };
 
-        let color;
-        if let CardType::Wildcard | CardType::DrawFourWildcard = card_type {
-            color = Color::Unpicked;
-        } else {
-            color = match card_seed / 27 {
-                0 => Color::Red,
-                1 => Color::Green,
-                2 => Color::Blue,
-                3 => Color::Yellow,
-                _ => unreachable!()
-            };
-        }
+        // Wildcards end up dropping the color
+        let color = match card_seed / 27 {
+            0 => Color::Red,
+            1 => Color::Green,
+            2 => Color::Blue,
+            3 => Color::Yellow,
+            _ => unreachable!()
+        };
 
         Card::new(card_type, color)
     }
 }
 
+// Identifies a player for the lifetime of a game, independently of their seat or name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerId(u32);
+
+impl fmt::Display for PlayerId
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result 
+    {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(Clone)]
 pub struct Player
 {
+    id: PlayerId,
     name: String,
-    cards: Vec<Card>
+    cards: Vec<Card>,
+    // Whether the player has called Uno for their current one card hand
+    called_uno: bool,
+    // Set when the player dropped to one card without calling Uno. It stays set until
+    // the next play or draw, and during that time anyone can catch them
+    catchable: bool
 }
 
 impl Player
 {
+    pub fn id(&self) -> PlayerId
+    {
+        self.id
+    }
+
     pub fn name(&self) -> &String 
     {
         &self.name
@@ -173,135 +439,235 @@ impl Player
     {
         self.cards.len()
     }
+
+    pub fn cards(&self) -> &[Card]
+    {
+        &self.cards
+    }
+
+    pub fn has_called_uno(&self) -> bool
+    {
+        self.called_uno
+    }
+
+    // The total points of the cards in this player's hand
+    pub fn hand_points(&self) -> u32
+    {
+        self.cards.iter().map(|card| card.points()).sum()
+    }
 }
 
+// Shows the whole hand, so only use it where the player themselves is looking.
+// `Game::view_for` and `Game::spectator_view` are safe to send anywhere else
 impl fmt::Display for Player
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}'s Cards:\n", self.name)?;
+        writeln!(f, "{}'s Cards:", self.name)?;
         for (index, card) in self.cards.iter().enumerate() {
-            write!(f, "{}. {}\n", index + 1, card)?;
+            writeln!(f, "{}. {}", index + 1, card)?;
         }
         Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct NotEnoughPlayers;
+// Official Uno rules allow between 2 and 10 players
+pub const MIN_PLAYERS: usize = 2;
+pub const DEFAULT_MAX_PLAYERS: usize = 10;
+
+pub const MAX_NAME_LENGTH: usize = 20;
+
+pub const STARTING_HAND_SIZE: usize = 7;
+
+// How many actions `Game::recent_actions` remembers
+pub const RECENT_ACTIONS: usize = 8;
 
-impl fmt::Display for NotEnoughPlayers
+// How many turns of history the views include
+pub const RECENT_TURNS: usize = 20;
+
+// How many of the most recently played cards the views include
+pub const RECENTLY_PLAYED: usize = 3;
+
+// Check a username against the names already in use, returning the trimmed name
+// that should be stored. Duplicates are compared ignoring case unless `case_sensitive` is set
+fn validate_name<'a, I>(name: &str, existing: I, case_sensitive: bool) -> Result<String, NameError>
+where
+    I: IntoIterator<Item = &'a str>
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result 
-    {
-        write!(f, "You cannot start the game until you have at least 2 players!")
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(NameError::TooLong { max_length: MAX_NAME_LENGTH });
     }
+    if name.chars().any(char::is_control) {
+        return Err(NameError::InvalidCharacters);
+    }
+
+    let taken = existing.into_iter().any(|other| {
+        if case_sensitive { other == name } else { other.to_lowercase() == name.to_lowercase() }
+    });
+    if taken {
+        return Err(NameError::Taken(String::from(name)));
+    }
+
+    Ok(String::from(name))
 }
 
+#[derive(Clone)]
 pub struct Lobby
 {
-    players: Vec<Player>
+    config: GameConfig,
+    players: Vec<Player>,
+    next_player_id: u32,
+    max_players: usize,
+    case_sensitive_names: bool,
+    rules: Vec<Box<dyn RuleSet>>,
+    // The controllers of the seats played by bots, by player
+    bots: Controllers,
+    bot_names: NamePack
 }
 
 impl Lobby
 {
-    // Return false if the username is already taken
-    pub fn add_player(&mut self, username: &str) -> bool
+    pub fn new() -> Lobby
     {
-        let username_available = !self.players.iter().any(|player| player.name == username);
-        if username_available {
-            self.players.push(Player { name: String::from(username), cards: Vec::with_capacity(7) });
-        }
-        username_available
+        Lobby::with_max_players(DEFAULT_MAX_PLAYERS)
     }
 
-    pub fn number_of_players(&self) -> usize
+    pub fn with_max_players(max_players: usize) -> Lobby
     {
-        self.players.len()
-    } 
+        Lobby {
+            config: GameConfig::default(),
+            players: Vec::with_capacity(MIN_PLAYERS),
+            next_player_id: 0,
+            max_players,
+            case_sensitive_names: false,
+            rules: Vec::new(),
+            bots: Controllers::new(),
+            bot_names: NamePack::default()
+        }
+    }
 
-    // Return false if there are not at least two players
-    pub fn start(self) -> Result<Game, NotEnoughPlayers>
+    // Build a lobby from a list of names, failing on the first duplicate name
+    // or if there are more names than the default player cap allows
+    pub fn with_players<I, S>(names: I) -> Result<Lobby, GameError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>
     {
-        if self.players.len() < 2 {
-            Err(NotEnoughPlayers {})
-        } else {
-            let mut game = Game {
-                players: self.players,
-                current_player_idx: 0,
-                turn_direction_reversed: false,
-    
-                deck: InfiniteDeck::new(),
-                top_card: None,
-            };
+        let mut lobby = Lobby::new();
+        for name in names {
+            lobby.add_player(&name.into())?;
+        }
+        Ok(lobby)
+    }
 
-            game.start();
-            Ok(game)
+    // These players, seated in this order under these ids, like the lobby a recorded game
+    // was dealt from, see `replay::Replay`. The names and the number of players are held to
+    // the same rules as `add_player`, and no two players can share an id
+    pub fn seated(config: GameConfig, seating: &[(PlayerId, String)]) -> Result<Lobby, GameError>
+    {
+        let mut lobby = Lobby { config, ..Lobby::new() };
+        for (id, name) in seating {
+            if lobby.is_full() {
+                return Err(GameError::LobbyFull { max_players: lobby.max_players });
+            }
+            if lobby.players.iter().any(|player| player.id == *id) {
+                return Err(GameError::PlayerIdTaken(*id));
+            }
+            let name = validate_name(name,
+                lobby.players.iter().map(|player| player.name.as_str()), lobby.case_sensitive_names)?;
+            lobby.players.push(Player { id: *id, name, cards: Vec::with_capacity(STARTING_HAND_SIZE), called_uno: false,
+                catchable: false });
         }
+        if lobby.players.len() < MIN_PLAYERS {
+            return Err(GameError::NotEnoughPlayers);
+        }
+        lobby.next_player_id = lobby.players.iter().map(|player| player.id.0.saturating_add(1)).max().unwrap_or(0);
+        Ok(lobby)
     }
-}
 
-fn array_next_index(index: usize, length: usize, reversed: bool) -> usize {
-    if reversed {
-        if index == 0 { length - 1 } else { index - 1 }
-    } else {
-        if index == length - 1 { 0 } else { index + 1 }
+    // The players still in `game`, in the same seats under the same ids, with `bots`
+    // playing for any of them, ready to deal them another round. Custom rules added with
+    // `add_rule` aren't kept, only the config. Ids of players who left aren't given out again
+    pub fn from_game(game: &Game, bots: Controllers) -> Lobby
+    {
+        let players: Vec<Player> = game.players.iter()
+            .map(|player| Player { cards: Vec::with_capacity(STARTING_HAND_SIZE), called_uno: false, catchable: false,
+                ..player.clone() })
+            .collect();
+        Lobby {
+            config: game.config.clone(), players, next_player_id: game.next_player_id,
+            case_sensitive_names: game.case_sensitive_names, bots,
+            ..Lobby::with_max_players(game.max_players)
+        }
     }
-}
 
-pub struct Game
-{
-    players: Vec<Player>,
-    current_player_idx: usize,
-    turn_direction_reversed: bool,
+    pub fn add_player(&mut self, username: &str) -> Result<(), GameError>
+    {
+        if self.is_full() {
+            return Err(GameError::LobbyFull { max_players: self.max_players });
+        }
 
-    deck: InfiniteDeck,
-    top_card: Option<Card>
-}
+        let name = validate_name(username,
+            self.players.iter().map(|player| player.name.as_str()), self.case_sensitive_names)?;
 
-impl fmt::Display for Game
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let length = self.players.len();
-        let mut index = self.current_player_idx;
-        let reversed = self.turn_direction_reversed;
+        let id = PlayerId(self.next_player_id);
+        self.next_player_id += 1;
 
-        if reversed {
-            index = array_next_index(index, length, false);
-        }
+        self.players.push(Player { id, name, cards: Vec::with_capacity(STARTING_HAND_SIZE), called_uno: false, catchable: false });
+        Ok(())
+    }
 
-        for i in 1..=length {
-            if reversed {
-                match i {
-                    1 => write!(f, "{} <- ", self.players[index].name())?,
-                    i if i == length => write!(f, "[{}]", self.players[index].name())?,
-                    _ => write!(f, "{} <- ", self.players[index].name())?
-                }
-            } else {
-                match i {
-                    1 => write!(f, "[{}] -> ", self.players[index].name())?,
-                    i if i == length => write!(f, "{}", self.players[index].name())?,
-                    _ => write!(f, "{} -> ", self.players[index].name())?
-                }
-            }
-            index = array_next_index(index, length, false);
+    // Seat a computer player, under the same naming rules as `add_player`. An empty
+    // name is replaced by one from the lobby's name pack. Returns false, leaving the
+    // lobby as it was, if the name can't be used or the lobby is full
+    pub fn add_bot(&mut self, name: &str, controller: Box<dyn PlayerController>) -> bool
+    {
+        let name = match name.trim() {
+            "" => {
+                let existing: Vec<String> = self.players.iter().map(|player| player.name.clone()).collect();
+                BotNames::next(self.bot_names, &existing)
+            },
+            name => name.to_owned()
+        };
+        if self.add_player(&name).is_err() {
+            return false;
         }
+        let id = self.players.last().expect("A player was just added").id;
+        self.bots.insert(id, controller);
+        true
+    }
 
-        Ok(())
+    // Where the names of bots added without one come from
+    pub fn set_bot_names(&mut self, pack: NamePack)
+    {
+        self.bot_names = pack;
     }
-}
 
-#[derive(Debug, Clone)]
-pub enum PlayError
-{
-    InvalidCardIndex,
-    CardUnplayable
-}
+    pub fn bot_names(&self) -> NamePack
+    {
+        self.bot_names
+    }
 
-impl Game
-{
-    pub fn new() -> Lobby
+    pub fn is_bot(&self, id: PlayerId) -> bool
     {
-        Lobby { players: Vec::with_capacity(2) }
+        self.bots.contains_key(&id)
+    }
+
+    // The controllers of the bot seats, for the game driver. Everyone else is left to
+    // whoever runs the game
+    pub fn bots(&self) -> &Controllers
+    {
+        &self.bots
+    }
+
+    // By default "alice" and "Alice" count as the same name; callers that want
+    // exact matching can turn that off
+    pub fn set_case_sensitive_names(&mut self, case_sensitive: bool)
+    {
+        self.case_sensitive_names = case_sensitive;
     }
 
     pub fn number_of_players(&self) -> usize
@@ -309,95 +675,3575 @@ impl Game
         self.players.len()
     } 
 
-    pub fn player(&self) -> &Player
+    pub fn config(&self) -> &GameConfig
     {
-        &self.players[self.current_player_idx]
+        &self.config
     }
 
-    pub fn next_turn(&mut self)
+    pub fn set_config(&mut self, config: GameConfig)
     {
-        self.current_player_idx = array_next_index(self.current_player_idx, 
-            self.players.len(), self.turn_direction_reversed);
+        self.config = config;
     }
 
-    pub fn turn_direction(&self) -> &str
+    // Add a custom house rule. Rules are consulted in the order they were added, after
+    // the built-in rules turned on in the config
+    pub fn add_rule(&mut self, rule: impl RuleSet + 'static)
     {
-        if self.turn_direction_reversed { "Counter Clockwise" } else { "Clockwise" }
+        self.rules.push(Box::new(rule));
     }
 
-    pub fn reverse(&mut self) 
+    pub fn max_players(&self) -> usize
     {
-        self.turn_direction_reversed = !self.turn_direction_reversed;
+        self.max_players
     }
 
-    pub fn top_card(&self) -> Card
+    pub fn is_full(&self) -> bool
     {
-        self.top_card.unwrap()
+        self.players.len() >= self.max_players
     }
 
-    pub fn play(&mut self, card_index: usize) -> Result<(), PlayError>
+    // Players in seating order, which becomes the turn order once the game starts
+    pub fn seating(&self) -> &[Player]
     {
-        let player = &mut self.players[self.current_player_idx];
-        player.cards.get(card_index)
-                    .ok_or(PlayError::InvalidCardIndex)
-                    .and_then(|card| {
-                        if card.is_playable_on(self.top_card.unwrap()) {
-                            self.top_card = Some(*card);
-                            Ok(())
-                        } else {
-                            Err(PlayError::CardUnplayable)
-                        }
-                    })?;
-
-        player.cards.remove(card_index);
-        Ok(())
+        &self.players
     }
 
-    pub fn draw_one(&mut self) -> Option<Card>
+    // Move the player in seat `from` to seat `to`, shifting everyone in between
+    pub fn move_player(&mut self, from: usize, to: usize) -> Result<(), GameError>
     {
-        let card = self.deck.draw();
-        if card.is_playable_on(self.top_card()) {
-            // The card is playable so play it immediately
-            self.top_card = Some(card);
-            None
-        } else {
-            // The card is not playable so give it to the player
-            self.players[self.current_player_idx].cards.push(card);
-            Some(card)
+        let length = self.players.len();
+        if from >= length {
+            return Err(GameError::InvalidSeat(from));
+        }
+        if to >= length {
+            return Err(GameError::InvalidSeat(to));
         }
+
+        let player = self.players.remove(from);
+        self.players.insert(to, player);
+        Ok(())
     }
 
-    pub fn draw_multiple(&mut self, number_of_cards: u8)
+    pub fn shuffle_seating<R: Rng + ?Sized>(&mut self, rng: &mut R)
     {
-        let player = &mut self.players[self.current_player_idx];
+        self.players.shuffle(rng);
+    }
 
-        for _ in 0..number_of_cards {
-            player.cards.push(self.deck.draw());
-        }
+    // Return an error if there are not at least two players
+    pub fn start(self) -> Result<Game, GameError>
+    {
+        self.start_with_seed(rand::random())
     }
 
-    pub fn set_wildcard_color(&mut self, color: Color) {
-        if let Some(Card { card_type: x @ CardType::Wildcard | 
-                                      x @ CardType::DrawFourWildcard, .. }) = self.top_card {
-            self.top_card = Some(Card { card_type: x, color: color });
-        }
+    // Start a game whose deal, starting player and draws all follow from `seed`, so
+    // the same lobby and seed always produce the same game
+    pub fn start_with_seed(self, seed: u64) -> Result<Game, GameError>
+    {
+        self.start_with_deck(seed, Vec::new())
     }
 
-    fn start(&mut self)
+    // Like `start_with_seed`, but the deck hands out `draw_pile` before any random cards,
+    // top first: the cards drawn for the deal, the card turned over, then everyone's hand
+    // in seating order, and then anything drawn during the game. For setting up a
+    // particular game, like a tutorial
+    pub fn start_with_deck(self, seed: u64, draw_pile: Vec<Card>) -> Result<Game, GameError>
     {
-        // Deal 7 cards to each player
-        for player in self.players.iter_mut() {
-            for _ in 0..7 {
-                player.cards.push(self.deck.draw());
+        if self.players.len() < MIN_PLAYERS {
+            Err(GameError::NotEnoughPlayers)
+        } else {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let mut deck = InfiniteDeck::seeded(rng.gen());
+            deck.stacked = draw_pile.into_iter().rev().collect();
+
+            // Choose the starting player, who takes the first turn
+            let mut draw_for_deal = Vec::new();
+            let current_player_idx = match self.config.starting_player {
+                StartingPlayer::Random => rng.gen_range(0..self.players.len()),
+                StartingPlayer::DrawForDeal => self.draw_for_deal(&mut deck, &mut draw_for_deal),
+                StartingPlayer::Seat(seat) if seat < self.players.len() => seat,
+                StartingPlayer::Seat(seat) => return Err(GameError::InvalidSeat(seat))
+            };
+
+            // Grab a top card from the deck, but make sure it isn't a draw four wildcard
+            let mut redrawn = Vec::new();
+            let top_card = loop {
+                let card = deck.draw();
+                if card.card_type != CardType::DrawFourWildcard {
+                    break card;
+                }
+                redrawn.push(card);
+            };
+
+            let mut rules: Vec<Box<dyn RuleSet>> = Vec::new();
+            if self.config.strict_color_matching {
+                rules.push(Box::new(StrictColorMatching));
             }
-        }
+            rules.extend(self.rules);
+            let first_player = self.players[current_player_idx].id;
+
+            let mut game = Game {
+                config: self.config,
+                rules,
+                players: self.players,
+                next_player_id: self.next_player_id,
+                max_players: self.max_players,
+                case_sensitive_names: self.case_sensitive_names,
+                current_player_idx,
+                direction: Direction::Clockwise,
+    
+                deck,
+                top_card,
+                active_color: top_card.color,
+                discarded: Vec::new(),
+                pending_action: None,
+                unresolved_play: None,
+                pending_penalty: 0,
+                challengeable_draw_four: None,
+                drawn_card: None,
+
+                events: Vec::new(),
+                turn_number: 0,
+                turn_limit_reached: false,
+                action_number: 0,
+                recent_actions: VecDeque::with_capacity(RECENT_ACTIONS),
+                history: VecDeque::new(),
+                departed: Vec::new(),
+                current_turn: TurnSummary::default(),
+                stats: GameStats::default(),
+                opponents: OpponentModel::default(),
+                start_effect: StartEffect {
+                    top_card,
+                    redrawn,
+                    draw_for_deal,
+                    first_player,
+                    reversed: false,
+                    skipped: None,
+                    penalty: None,
+                    awaiting_wild_color: false,
+                    starting_player: first_player
+                }
+            };
 
-        // Choose the starting player
-        self.current_player_idx = rand::thread_rng().gen_range(0..self.players.len());
+            game.start();
+            Ok(game)
+        }
+    }
+}
 
-        // Grab a top card from the deck, but make sure it isn't a draw four wildcard
-        while let None | Some(Card { card_type: CardType::DrawFourWildcard, .. }) = self.top_card {
-            self.top_card = Some(self.deck.draw());
+impl Lobby
+{
+    // Deal everyone still in the running a card face up until one player has the
+    // highest number, returning their seat. The deck is infinite, so there are no
+    // cards to put back before the real deal
+    fn draw_for_deal(&self, deck: &mut InfiniteDeck, drawn: &mut Vec<(PlayerId, Card)>) -> usize
+    {
+        let mut contenders: Vec<usize> = (0..self.players.len()).collect();
+        while contenders.len() > 1 {
+            let values: Vec<u32> = contenders.iter()
+                .map(|&seat| {
+                    let card = deck.draw();
+                    drawn.push((self.players[seat].id, card));
+                    match card.card_type {
+                        CardType::Number(digit) => digit.value() as u32,
+                        _ => 0
+                    }
+                })
+                .collect();
+            let highest = values.iter().copied().max().unwrap_or(0);
+            contenders = contenders.into_iter()
+                .zip(values)
+                .filter(|&(_, value)| value == highest)
+                .map(|(seat, _)| seat)
+                .collect();
         }
+        contenders[0]
+    }
+}
+
+impl Default for Lobby
+{
+    fn default() -> Self
+    {
+        Lobby::new()
+    }
+}
+
+// Something that has to be resolved before play can continue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PendingAction
+{
+    AwaitingWildColor,
+    // A 7 was played and the player must pick someone to swap hands with
+    ChooseSwapTarget,
+    // The player drew a playable card and must choose whether to play or keep it
+    PlayOrKeepDrawnCard,
+    // The current player must stack a draw card or accept the penalty
+    DrawPenalty(u8)
+}
+
+impl fmt::Display for PendingAction
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result 
+    {
+        match self {
+            PendingAction::AwaitingWildColor => write!(f, "A color must be chosen for the wildcard first"),
+            PendingAction::ChooseSwapTarget => write!(f, "A player to swap hands with must be chosen first"),
+            PendingAction::PlayOrKeepDrawnCard => write!(f, "The drawn card must be played or kept first"),
+            PendingAction::DrawPenalty(cards) =>
+                write!(f, "You must stack a draw card or accept the {} card penalty", cards)
+        }
+    }
+}
+
+// Describes everything that happened as a result of playing a card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayOutcome
+{
+    pub player: PlayerId,
+    pub card: Card,
+    // How many identical cards were played together, see `Game::play_many`
+    pub copies: usize,
+    // Whether the direction of play ended up flipped
+    pub reversed: bool,
+    // Every player who lost their turn, in order
+    pub skipped: Vec<PlayerId>,
+    // Who drew a penalty and the cards they drew
+    pub penalty: Option<(PlayerId, Vec<Card>)>,
+    // What the game's custom rules did, see `RuleSet::on_card_played`
+    pub rule_effects: Vec<RuleEffect>,
+    // With stacking enabled, the penalty now waiting on the next player
+    pub pending_penalty: u8,
+    pub winner: Option<PlayerId>,
+    // When set the card's effects haven't been applied yet, they will be once
+    // a color is chosen with `Game::set_wildcard_color`
+    pub awaiting_wild_color: bool,
+    // Likewise for a 7 under the seven-zero rule, see `Game::choose_swap_target`
+    pub awaiting_swap_target: bool,
+    pub swapped_with: Option<PlayerId>,
+    pub hands_rotated: bool,
+    pub next_player: PlayerId
+}
+
+impl PlayOutcome
+{
+    fn new(player: PlayerId, card: Card) -> PlayOutcome
+    {
+        PlayOutcome {
+            player, card,
+            copies: 1,
+            reversed: false,
+            skipped: Vec::new(),
+            penalty: None,
+            rule_effects: Vec::new(),
+            pending_penalty: 0,
+            winner: None,
+            awaiting_wild_color: false,
+            awaiting_swap_target: false,
+            swapped_with: None,
+            hands_rotated: false,
+            next_player: player
+        }
+    }
+}
+
+// What happened when the current player accepted a draw penalty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PenaltyOutcome
+{
+    pub player: PlayerId,
+    pub cards_drawn: Vec<Card>,
+    pub next_player: PlayerId
+}
+
+// A move the current player can make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Move
+{
+    Play(usize),
+    Draw,
+    PlayDrawn,
+    KeepDrawn,
+    AcceptPenalty,
+    ChallengeDrawFour
+}
+
+// The state of the game when a Draw Four was played, kept so it can be challenged
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DrawFourPlay
+{
+    player: PlayerId,
+    hand: Vec<Card>,
+    active_color: Option<Color>
+}
+
+impl DrawFourPlay
+{
+    fn was_legal(&self) -> bool
+    {
+        !self.hand.iter().any(|card| card.color.is_some() && card.color == self.active_color)
+    }
+}
+
+// The result of challenging a Draw Four
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChallengeOutcome
+{
+    pub challenger: PlayerId,
+    pub offender: PlayerId,
+    // True if the Draw Four was played while holding a card of the active color
+    pub offender_guilty: bool,
+    pub penalized: PlayerId,
+    pub cards_drawn: Vec<Card>,
+    pub next_player: PlayerId
+}
+
+// The result of a player jumping in out of turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpInOutcome
+{
+    // Whose turn it was before the jump in
+    pub interrupted: PlayerId,
+    pub play: PlayOutcome
+}
+
+// The result of accusing a player of not calling Uno
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatchOutcome
+{
+    pub accuser: PlayerId,
+    pub target: PlayerId,
+    // False when the accusation was wrong and the accuser was penalized instead
+    pub caught: bool,
+    pub penalized: PlayerId,
+    pub cards_drawn: Vec<Card>
+}
+
+// The card the current player drew. If it isn't playable it was kept and their
+// turn is over, otherwise they choose between `Game::play_drawn` and `Game::keep_drawn`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawnCard
+{
+    pub card: Card,
+    pub playable: bool,
+    // Set when the `auto_play_drawn_cards` rule played the card immediately
+    pub auto_played: Option<PlayOutcome>
+}
+
+// Why a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEnd
+{
+    // A player played their last card
+    Won(PlayerId),
+    // Everyone else left the game
+    LastPlayerStanding(PlayerId),
+    // `GameConfig::max_turns` ran out, the winner is picked by the stalemate policy
+    TurnLimitReached { winner: Option<PlayerId> }
+}
+
+impl GameEnd
+{
+    pub fn winner(&self) -> Option<PlayerId>
+    {
+        match *self {
+            GameEnd::Won(player) | GameEnd::LastPlayerStanding(player) => Some(player),
+            GameEnd::TurnLimitReached { winner } => winner
+        }
+    }
+}
+
+// A player who left the game part way through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedPlayer
+{
+    pub id: PlayerId,
+    pub name: String,
+    // The hand they left with, which is out of play now
+    pub cards: Vec<Card>,
+    // True if it was their turn, in which case it has passed to the next player
+    pub was_current: bool,
+    // Set when only one player is left
+    pub winner: Option<PlayerId>
+}
+
+// Everything needed to pick a game up again later, see `Game::save`. The deck is
+// infinite, so there's no draw pile to keep
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedGame
+{
+    pub table: SpectatorView,
+    // Every hand, in seating order
+    pub hands: Vec<Vec<Card>>,
+    // Index in the current player's hand of a playable card they just drew
+    pub drawn_card: Option<usize>,
+    // Those who left, with the points they left holding, see `Game::departed`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub departed: Vec<(PlayerId, u32)>
+}
+
+// What the first card turned over did before anyone took a turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartEffect
+{
+    pub top_card: Card,
+    // Draw Fours that were turned over and put back before the top card was found
+    pub redrawn: Vec<Card>,
+    // Every card dealt to choose the first player, in order, when drawing for the deal
+    pub draw_for_deal: Vec<(PlayerId, Card)>,
+    // The player chosen to go first, before the top card had any effect
+    pub first_player: PlayerId,
+    pub reversed: bool,
+    pub skipped: Option<PlayerId>,
+    pub penalty: Option<(PlayerId, Vec<Card>)>,
+    // The first player must choose a color for the wildcard with `Game::set_wildcard_color`
+    pub awaiting_wild_color: bool,
+    pub starting_player: PlayerId
+}
+
+// The result of drawing until a playable card turned up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawUntilOutcome
+{
+    pub cards_drawn: usize,
+    // The playable card that ended the draw. None if the limit was reached first,
+    // in which case the turn is over
+    pub found: Option<DrawnCard>
+}
+
+// What the current player has done so far this turn
+#[derive(Debug, Clone, Default)]
+struct TurnSummary
+{
+    played: Option<Card>,
+    drawn: usize,
+    penalty: usize
+}
+
+impl TurnSummary
+{
+    fn action(&self) -> TurnAction
+    {
+        match *self {
+            TurnSummary { penalty, .. } if penalty > 0 => TurnAction::Penalized(penalty),
+            TurnSummary { played: Some(card), drawn: 0, .. } => TurnAction::Played(card),
+            TurnSummary { played: Some(card), drawn, .. } => TurnAction::DrewAndPlayed { drawn, card },
+            TurnSummary { drawn, .. } if drawn > 0 => TurnAction::Drew(drawn),
+            _ => TurnAction::Skipped
+        }
+    }
+}
+
+fn array_next_index(index: usize, length: usize, direction: Direction) -> usize {
+    if length == 0 {
+        0
+    } else if direction == Direction::CounterClockwise {
+        if index == 0 { length - 1 } else { index - 1 }
+    } else {
+        if index + 1 >= length { 0 } else { index + 1 }
+    }
+}
+
+// Cloning a game reproduces it exactly, including the cards the deck will deal next.
+// Use `Game::fork` for a copy whose future draws are independent
+#[derive(Clone)]
+pub struct Game
+{
+    config: GameConfig,
+    // House rules beyond the config, including the built-in ones it turns on
+    rules: Vec<Box<dyn RuleSet>>,
+    players: Vec<Player>,
+    // Carried over from the lobby so players can join part way through
+    next_player_id: u32,
+    max_players: usize,
+    case_sensitive_names: bool,
+    current_player_idx: usize,
+    // Flipped by every Reverse, even with two players where the direction has no
+    // visible effect. This keeps the turn order right if a third player joins
+    direction: Direction,
+
+    deck: InfiniteDeck,
+    top_card: Card,
+    // The color the next card must match. None while a wildcard on top is waiting for
+    // its color to be chosen
+    active_color: Option<Color>,
+    // Every card that has been on top of the pile, oldest first
+    discarded: Vec<Card>,
+    pending_action: Option<PendingAction>,
+    // Set while a played wildcard is waiting for its color before its effects apply,
+    // along with how many copies were played
+    unresolved_play: Option<(PlayerId, usize)>,
+    // Cards the current player must draw unless they stack another draw card
+    pending_penalty: u8,
+    // The Draw Four the current player may still challenge
+    challengeable_draw_four: Option<DrawFourPlay>,
+    // Index in the current player's hand of a playable card they just drew
+    drawn_card: Option<usize>,
+
+    events: Vec<RecordedEvent>,
+    // Every time the turn passes, including to a player who is skipped
+    turn_number: u32,
+    turn_limit_reached: bool,
+    // Every card played and every draw from the deck
+    action_number: u32,
+    recent_actions: VecDeque<ActionRecord>,
+    history: VecDeque<TurnRecord>,
+    // Players who left partway through, with the points in the hand they left holding
+    departed: Vec<(PlayerId, u32)>,
+    current_turn: TurnSummary,
+    stats: GameStats,
+    opponents: OpponentModel,
+    start_effect: StartEffect
+}
+
+// e.g. "[Alice] -> Bob -> Carol ↻ clockwise": the current player first and then
+// everyone in the order they'll play. With two players the direction makes no
+// difference, so it's left off
+impl fmt::Display for Game
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, player) in self.turn_order().enumerate() {
+            if i == 0 {
+                write!(f, "[{}]", player.name())?;
+            } else {
+                write!(f, " -> {}", player.name())?;
+            }
+        }
+
+        if self.players.len() > 2 {
+            write!(f, " {}", self.direction.arrow())?;
+        }
+        Ok(())
+    }
+}
+
+impl Game
+{
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> Lobby
+    {
+        Lobby::new()
+    }
+
+    pub fn number_of_players(&self) -> usize
+    {
+        self.players.len()
+    } 
+
+    pub fn player(&self) -> &Player
+    {
+        &self.players[self.current_player_idx]
+    }
+
+    pub fn current_seat(&self) -> usize
+    {
+        self.current_player_idx
+    }
+
+    // All players in seating order
+    pub fn players(&self) -> &[Player]
+    {
+        &self.players
+    }
+
+    pub fn player_by_id(&self, id: PlayerId) -> Option<&Player>
+    {
+        self.players.iter().find(|player| player.id == id)
+    }
+
+    // The seat a player currently occupies, which can change as players leave or join
+    pub fn seat_of(&self, id: PlayerId) -> Option<usize>
+    {
+        self.players.iter().position(|player| player.id == id)
+    }
+
+    pub fn events(&self) -> &[RecordedEvent]
+    {
+        &self.events
+    }
+
+    fn emit(&mut self, event: GameEvent)
+    {
+        self.stats.record(&event);
+        self.opponents.record(&event);
+        self.events.push(RecordedEvent { turn: self.turn_number, action: self.action_number, event });
+    }
+
+    pub fn stats(&self) -> &GameStats
+    {
+        &self.stats
+    }
+
+    // Everyone who left or conceded partway through, with the points of the hand they
+    // left holding. When a round is scored those points still count against them
+    pub fn departed(&self) -> &[(PlayerId, u32)]
+    {
+        &self.departed
+    }
+
+    // What the winner scores: the points of every card left in the other players'
+    // hands, and in the hands of those who left. Nothing until somebody has won
+    pub fn round_points(&self) -> u32
+    {
+        let Some(winner) = self.winner() else {
+            return 0;
+        };
+        let held: u32 = self.players.iter().filter(|player| player.id != winner.id).map(Player::hand_points).sum();
+        held + self.departed.iter().map(|(_, points)| points).sum::<u32>()
+    }
+
+    // What the other players can tell about the player in `seat` from what they
+    // have done in public
+    pub fn opponent_model(&self, seat: usize) -> Result<&OpponentProfile, GameError>
+    {
+        let player = self.players.get(seat).ok_or(GameError::InvalidSeat(seat))?;
+        self.opponents.profile(player.id).ok_or(GameError::InvalidSeat(seat))
+    }
+
+    // Statistics for the player in `seat`
+    pub fn player_stats(&self, seat: usize) -> Option<&PlayerStats>
+    {
+        self.players.get(seat).and_then(|player| self.stats.player(player.id))
+    }
+
+    fn record_action(&mut self, player: PlayerId, kind: ActionKind)
+    {
+        self.action_number += 1;
+        if self.recent_actions.len() == RECENT_ACTIONS {
+            self.recent_actions.pop_front();
+        }
+        self.recent_actions.push_back(ActionRecord { action: self.action_number, turn: self.turn_number, player, kind });
+    }
+
+    pub fn last_action(&self) -> Option<&ActionRecord>
+    {
+        self.recent_actions.back()
+    }
+
+    // Up to `RECENT_ACTIONS` of the latest actions, oldest first
+    pub fn recent_actions(&self) -> impl Iterator<Item = &ActionRecord>
+    {
+        self.recent_actions.iter()
+    }
+
+    // The card that was on top of the pile before the current top card
+    pub fn previous_top_card(&self) -> Option<Card>
+    {
+        self.discarded.len().checked_sub(2).map(|index| self.discarded[index])
+    }
+
+    // A copy of the game with the same visible state but its own randomness, so moves
+    // can be tried out on it without peeking at the real game's upcoming draws
+    pub fn fork(&self, seed: u64) -> Game
+    {
+        let mut game = self.clone();
+        game.deck.reseed(seed);
+        game
+    }
+
+    // A game rebuilt from what one player can see, with everyone else's hand dealt
+    // at random from a deck seeded with `seed`. The deck is infinite, so every card the
+    // player can't see turns up with the deck's odds and a fresh deal is as good a guess
+    // as any. Custom rules aren't part of the view, so only the config's rules apply
+    pub fn determinize(view: &PlayerView, seed: u64) -> Game
+    {
+        Game::determinize_with(view, Vec::new(), Vec::new(), seed)
+    }
+
+    // Like `determinize`, but with the hidden hands given in seating order and the
+    // draw pile given top first. Hands that are missing or short of the player's card
+    // count are topped up from the deck, which is infinite beyond the draw pile
+    pub fn determinize_with(view: &PlayerView, mut hands: Vec<Vec<Card>>, draw_pile: Vec<Card>, seed: u64) -> Game
+    {
+        let mut deck = InfiniteDeck::seeded(seed);
+        hands.resize(view.table.players.len(), Vec::new());
+
+        let table = &view.table;
+        let players: Vec<Player> = table.players.iter().zip(hands).enumerate()
+            .map(|(seat, (player, mut cards))| Player {
+                id: player.id,
+                name: player.name.clone(),
+                cards: if seat == view.seat {
+                    view.hand.clone()
+                } else {
+                    cards.truncate(player.number_of_cards);
+                    while cards.len() < player.number_of_cards {
+                        cards.push(deck.draw());
+                    }
+                    cards
+                },
+                called_uno: player.called_uno,
+                catchable: false
+            })
+            .collect();
+        deck.stacked = draw_pile.into_iter().rev().collect();
+
+        let mut rules: Vec<Box<dyn RuleSet>> = Vec::new();
+        if table.config.strict_color_matching {
+            rules.push(Box::new(StrictColorMatching));
+        }
+        let (pending_action, pending_penalty) = match table.pending_action {
+            Some(PendingAction::DrawPenalty(cards)) => (None, cards),
+            action => (action, 0)
+        };
+        let current = table.players[table.current_seat].id;
+        // Whether the Draw Four was played fairly comes down to the guess at its
+        // player's hand, which is everything they held then apart from the Draw Four
+        let challengeable_draw_four = table.challengeable_draw_four.and_then(|(player, active_color)| {
+            let cards = &players.iter().find(|each| each.id == player)?.cards;
+            let hand = cards.iter().copied().chain([Card::draw_four()]).collect();
+            Some(DrawFourPlay { player, hand, active_color })
+        });
+
+        Game {
+            config: table.config.clone(),
+            rules,
+            next_player_id: players.iter().map(|player| player.id.0.saturating_add(1)).max().unwrap_or(0),
+            players,
+            max_players: DEFAULT_MAX_PLAYERS,
+            case_sensitive_names: false,
+            current_player_idx: table.current_seat,
+            direction: table.direction,
+
+            deck,
+            top_card: table.top_card,
+            active_color: table.active_color,
+            // Views saved before the whole pile was kept only have the last few cards
+            discarded: if table.discarded.is_empty() { table.recently_played.clone() } else { table.discarded.clone() },
+            pending_action,
+            unresolved_play: None,
+            pending_penalty,
+            challengeable_draw_four,
+            drawn_card: if table.current_seat == view.seat { view.drawn_card } else { None },
+
+            events: Vec::new(),
+            turn_number: table.turn_number,
+            turn_limit_reached: false,
+            action_number: 0,
+            recent_actions: VecDeque::with_capacity(RECENT_ACTIONS),
+            history: VecDeque::new(),
+            departed: Vec::new(),
+            current_turn: TurnSummary::default(),
+            stats: GameStats::default(),
+            opponents: OpponentModel::default(),
+            start_effect: StartEffect {
+                top_card: table.top_card,
+                redrawn: Vec::new(),
+                draw_for_deal: Vec::new(),
+                first_player: current,
+                reversed: false,
+                skipped: None,
+                penalty: None,
+                awaiting_wild_color: false,
+                starting_player: current
+            }
+        }
+    }
+
+    // The game as it stands, to be carried on with `Game::resume`
+    pub fn save(&self) -> SavedGame
+    {
+        SavedGame {
+            table: self.spectator_view(),
+            hands: self.players.iter().map(|player| player.cards.clone()).collect(),
+            drawn_card: self.drawn_card,
+            departed: self.departed.clone()
+        }
+    }
+
+    // Carry on a saved game, drawing from a deck seeded with `seed`. The last few turns
+    // of history come back, but stats and events start over. Missing hands are dealt
+    // afresh, and a save that doesn't hold together otherwise is turned down
+    pub fn resume(saved: &SavedGame, seed: u64) -> Result<Game, InvariantViolation>
+    {
+        let mut table = saved.table.clone();
+        if table.players.is_empty() {
+            return Err(InvariantViolation::NoPlayers);
+        }
+        let seat = table.current_seat;
+        if seat >= table.players.len() {
+            return Err(InvariantViolation::CurrentSeatOutOfRange { seat, number_of_players: table.players.len() });
+        }
+        for (player, hand) in table.players.iter_mut().zip(&saved.hands) {
+            player.number_of_cards = hand.len();
+        }
+
+        let view = PlayerView {
+            seat,
+            id: table.players[seat].id,
+            hand: saved.hands.get(seat).cloned().unwrap_or_default(),
+            drawn_card: saved.drawn_card,
+            new_cards: Vec::new(),
+            table
+        };
+        let mut game = Game::determinize_with(&view, saved.hands.clone(), Vec::new(), seed);
+        game.history = view.table.recent_turns.iter().cloned().collect();
+        // Nobody who joins later gets the id of someone who left
+        game.departed = saved.departed.clone();
+        let after_departed = game.departed.iter().map(|(id, _)| id.0.saturating_add(1)).max().unwrap_or(0);
+        game.next_player_id = game.next_player_id.max(after_departed);
+        game.check_invariants()?;
+        Ok(game)
+    }
+
+    // What the player in `seat` is allowed to see: their own hand and the public state
+    pub fn view_for(&self, seat: usize) -> Result<PlayerView, GameError>
+    {
+        let player = self.players.get(seat).ok_or(GameError::InvalidSeat(seat))?;
+        Ok(PlayerView {
+            seat,
+            id: player.id,
+            hand: player.cards.clone(),
+            drawn_card: if seat == self.current_player_idx { self.drawn_card } else { None },
+            new_cards: self.new_cards(player),
+            table: self.spectator_view()
+        })
+    }
+
+    // The cards `player` drew from the start of their previous turn on that they still
+    // hold. Dealt cards and cards that came from another player's hand don't count
+    fn new_cards(&self, player: &Player) -> Vec<Card>
+    {
+        let mut turns_started = 0;
+        let since = self.events.iter()
+            .rposition(|recorded| {
+                if recorded.event == (GameEvent::TurnStarted { player: player.id }) {
+                    turns_started += 1;
+                }
+                turns_started == 2
+            })
+            .unwrap_or(0);
+
+        let mut held = player.cards.clone();
+        self.events[since..].iter()
+            .filter_map(|recorded| match recorded.event {
+                GameEvent::CardDrawn { player: id, card } if id == player.id => Some(card),
+                _ => None
+            })
+            .filter(|card| match held.iter().position(|each| each == card) {
+                Some(found) => {
+                    held.swap_remove(found);
+                    true
+                },
+                None => false
+            })
+            .collect()
+    }
+
+    // What the bot in `seat` gets to decide its move with
+    pub fn observe(&self, seat: usize) -> Result<BotObservation, GameError>
+    {
+        let view = self.view_for(seat)?;
+        let legal_moves = if seat == self.current_player_idx { self.legal_moves() } else { Vec::new() };
+        let mut unseen: Vec<(Card, usize)> = CardCounter::for_player(self, seat)
+            .map_or_else(Vec::new, |counter| counter.unseen().collect());
+        unseen.sort_by_key(|(card, _)| card.sort_key());
+        let likely_void_colors = self.players.iter()
+            .map(|player| self.opponents.profile(player.id).map_or_else(Vec::new, |profile| profile.likely_void_colors.clone()))
+            .collect();
+
+        Ok(BotObservation {
+            view,
+            legal_moves,
+            unseen,
+            recent_actions: self.recent_actions.iter().copied().collect(),
+            likely_void_colors
+        })
+    }
+
+    // Everything that is public, without anyone's hand
+    pub fn spectator_view(&self) -> SpectatorView
+    {
+        SpectatorView {
+            players: self.players.iter().map(|player| PublicPlayer {
+                id: player.id,
+                name: player.name.clone(),
+                number_of_cards: player.cards.len(),
+                called_uno: player.called_uno
+            }).collect(),
+            top_card: self.top_card(),
+            recently_played: self.discarded.iter().rev().take(RECENTLY_PLAYED).rev().copied().collect(),
+            discarded: self.discarded.clone(),
+            active_color: self.active_color,
+            direction: self.direction,
+            current_seat: self.current_player_idx,
+            pending_action: self.pending_action(),
+            challengeable_draw_four: self.challengeable_draw_four.as_ref()
+                .filter(|_| self.can_challenge_draw_four())
+                .map(|play| (play.player, play.active_color)),
+            turn_number: self.turn_number,
+            recent_turns: self.history(RECENT_TURNS).cloned().collect(),
+            config: self.config.clone(),
+            winner: self.winner().map(|winner| winner.id)
+        }
+    }
+
+    // The playable card the current player just drew, while they decide whether to
+    // play or keep it
+    pub fn drawn_card(&self) -> Option<Card>
+    {
+        self.drawn_card.map(|index| self.player().cards[index])
+    }
+
+    // Starts at 1 for the first turn of the game
+    pub fn turn_number(&self) -> u32
+    {
+        self.turn_number
+    }
+
+    // The number of cards played and draws made so far
+    pub fn action_number(&self) -> u32
+    {
+        self.action_number
+    }
+
+    pub fn config(&self) -> &GameConfig
+    {
+        &self.config
+    }
+
+    pub fn pending_action(&self) -> Option<PendingAction>
+    {
+        self.pending_action.or(match self.pending_penalty {
+            0 => None,
+            cards => Some(PendingAction::DrawPenalty(cards))
+        })
+    }
+
+    pub fn pending_penalty(&self) -> u8
+    {
+        self.pending_penalty
+    }
+
+    fn check_can_act(&self) -> Result<(), GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        match self.pending_action() {
+            Some(action) => Err(GameError::ActionPending(action)),
+            None => Ok(())
+        }
+    }
+
+    // Check whether the current player may play `card` right now
+    fn check_can_play(&self, card: Card) -> Result<(), GameError>
+    {
+        self.check_can_play_copies(card, 1)
+    }
+
+    fn check_can_play_copies(&self, card: Card, copies: usize) -> Result<(), GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if let Some(action) = self.pending_action {
+            return Err(GameError::ActionPending(action));
+        }
+        if self.config.no_going_out_on_wild && card.is_wild() && self.player().cards.len() <= copies {
+            return Err(GameError::CannotGoOutOnWild(card));
+        }
+
+        let top = self.top_card();
+        if self.pending_penalty > 0 {
+            self.check_can_stack(card, copies)?;
+        } else if !self.is_playable_on_top(card) {
+            return Err(GameError::CardUnplayable { attempted: card, top });
+        }
+        Ok(())
+    }
+
+    // Only draw cards can go on a pending penalty, and only the ones the stacking
+    // policy allows. Without stacking a penalty is only left pending so a Draw Four can
+    // be challenged, and nothing can go on it
+    fn check_can_stack(&self, card: Card, copies: usize) -> Result<(), GameError>
+    {
+        let policy = self.config.stacking_policy;
+        let top = self.top_card();
+        let allowed = match (top.card_type, card.card_type) {
+            (CardType::DrawTwo, CardType::DrawTwo) =>
+                policy.allow_two_on_two && (policy.allow_color_change || card.color == self.active_color),
+            (CardType::DrawTwo, CardType::DrawFourWildcard) => policy.allow_four_on_two,
+            (CardType::DrawFourWildcard, CardType::DrawTwo) =>
+                policy.allow_two_on_four && (policy.allow_color_change || card.color == self.active_color),
+            (CardType::DrawFourWildcard, CardType::DrawFourWildcard) => policy.allow_four_on_four,
+            (_, CardType::DrawTwo | CardType::DrawFourWildcard) => false,
+            _ => return Err(GameError::ActionPending(PendingAction::DrawPenalty(self.pending_penalty)))
+        };
+        if !allowed || !self.config.stacking {
+            return Err(GameError::CannotStack { attempted: card, top });
+        }
+
+        let added = if card.card_type == CardType::DrawTwo { 2 } else { 4 } * copies;
+        if let Some(max_penalty) = policy.max_penalty {
+            if self.pending_penalty as usize + added > max_penalty as usize {
+                return Err(GameError::PenaltyLimitReached { max_penalty });
+            }
+        }
+        Ok(())
+    }
+
+    // Whether `card` matches the top card under this game's rules, ignoring any
+    // pending penalty
+    pub fn is_playable_on_top(&self, card: Card) -> bool
+    {
+        let top = self.top_card();
+        self.rules.iter()
+            .find_map(|rule| rule.is_playable(card, top, self.active_color))
+            .unwrap_or_else(|| card.is_playable_on(top, self.active_color))
+    }
+
+    // The names of the house rules in play beyond the config's own settings
+    pub fn rule_names(&self) -> impl Iterator<Item = &str>
+    {
+        self.rules.iter().map(|rule| rule.name())
+    }
+
+    // Indices of the cards in the current player's hand that can be played right now
+    pub fn playable_card_indices(&self) -> Vec<usize>
+    {
+        self.player().cards.iter()
+            .enumerate()
+            .filter(|(_, card)| self.check_can_play(**card).is_ok())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    // Under the forced play rule, the cards that stop the current player from drawing.
+    // A Draw Four that could be successfully challenged doesn't count
+    fn forced_play_indices(&self) -> Vec<usize>
+    {
+        if !self.config.forced_play || self.pending_penalty > 0 {
+            return Vec::new();
+        }
+
+        let legal_draw_four = !self.config.draw_four_challenge ||
+            self.can_legally_play_draw_four(self.current_player_idx);
+        self.playable_card_indices().into_iter()
+            .filter(|&index| legal_draw_four || self.player().cards[index].card_type != CardType::DrawFourWildcard)
+            .collect()
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move>
+    {
+        if self.is_over() {
+            return Vec::new();
+        }
+        match self.pending_action {
+            Some(PendingAction::PlayOrKeepDrawnCard) if self.config.forced_play => return vec![Move::PlayDrawn],
+            Some(PendingAction::PlayOrKeepDrawnCard) => return vec![Move::PlayDrawn, Move::KeepDrawn],
+            Some(_) => return Vec::new(),
+            None => ()
+        }
+
+        let mut moves: Vec<Move> = self.playable_card_indices().into_iter().map(Move::Play).collect();
+        if self.pending_penalty > 0 {
+            moves.push(Move::AcceptPenalty);
+        } else if self.forced_play_indices().is_empty() {
+            moves.push(Move::Draw);
+        }
+        if self.can_challenge_draw_four() {
+            moves.push(Move::ChallengeDrawFour);
+        }
+        if !self.rules.is_empty() {
+            let view = self.spectator_view();
+            for rule in &self.rules {
+                rule.modify_legal_moves(&view, &mut moves);
+            }
+        }
+        moves
+    }
+
+    // Place `copies` of a played card on the pile. Its effects are applied straight
+    // away unless it's a wildcard, in which case they wait for a color to be chosen
+    fn place_on_pile(&mut self, player: PlayerId, card: Card, copies: usize) -> PlayOutcome
+    {
+        for _ in 0..copies {
+            self.record_action(player, ActionKind::Played(card));
+            self.discarded.push(card);
+        }
+        self.current_turn.played = Some(card);
+        self.top_card = card;
+        self.active_color = card.color;
+        if self.is_over() {
+            return self.resolve_play(player, card, copies);
+        }
+
+        let mut outcome = PlayOutcome::new(player, card);
+        outcome.copies = copies;
+        if self.needs_wild_color() {
+            self.pending_action = Some(PendingAction::AwaitingWildColor);
+            outcome.awaiting_wild_color = true;
+        } else if self.config.seven_zero && card.card_type == CardType::Number(Digit::SEVEN) {
+            self.pending_action = Some(PendingAction::ChooseSwapTarget);
+            outcome.awaiting_swap_target = true;
+        } else {
+            return self.resolve_play(player, card, copies);
+        }
+
+        self.unresolved_play = Some((player, copies));
+        outcome
+    }
+
+    // Apply the effects of the card that was just played and pass the turn on. Each
+    // copy of a card played together applies its effect again: two Skips skip two
+    // players, two Reverses cancel out and two Draw Twos make the next player draw 4.
+    // A 7 still swaps hands only once
+    fn resolve_play(&mut self, player: PlayerId, card: Card, copies: usize) -> PlayOutcome
+    {
+        let mut outcome = PlayOutcome::new(player, card);
+        outcome.copies = copies;
+
+        if self.is_over() {
+            self.record_turn();
+            outcome.winner = Some(player);
+            return outcome;
+        }
+
+        if self.config.seven_zero && card.card_type == CardType::Number(Digit::ZERO) {
+            for _ in 0..copies {
+                self.pass_hands_on();
+            }
+            outcome.hands_rotated = true;
+        }
+
+        if let CardType::Reverse = card.card_type {
+            for _ in 0..copies {
+                self.reverse();
+            }
+            outcome.reversed = copies % 2 == 1;
+        }
+
+        let rule_skips = self.apply_rule_effects(player, card, &mut outcome);
+        self.advance_turn();
+
+        let penalty = match card.card_type {
+            CardType::DrawTwo => Some(2),
+            CardType::DrawFourWildcard => Some(4),
+            _ => None
+        };
+        // Penalties are left pending when the next player gets a chance to respond to them
+        let deferred = self.config.stacking ||
+            (self.config.draw_four_challenge && card.card_type == CardType::DrawFourWildcard);
+
+        if let Some(number_of_cards) = penalty {
+            let number_of_cards = number_of_cards * copies as u8;
+            self.stats.player_mut(player).penalty_cards_dealt += number_of_cards as usize;
+            if deferred {
+                // The next player gets a chance to respond instead of drawing straight away
+                self.pending_penalty = self.pending_penalty.saturating_add(number_of_cards);
+                outcome.pending_penalty = self.pending_penalty;
+            } else {
+                let cards = self.draw_multiple(number_of_cards);
+                self.current_turn.penalty += cards.len();
+                outcome.penalty = Some((self.player().id, cards));
+            }
+        }
+
+        // With only two players a Reverse acts like a Skip, so the turn comes straight
+        // back to the player who played it
+        // The player drawing a penalty only loses one turn however many copies were played
+        let skips = match card.card_type {
+            CardType::Skip => copies,
+            CardType::DrawTwo | CardType::DrawFourWildcard if !deferred => 1,
+            CardType::Reverse if self.players.len() == 2 => copies,
+            _ => 0
+        };
+        for _ in 0..skips + rule_skips {
+            let skipped = self.player().id;
+            self.emit(GameEvent::TurnSkipped { player: skipped });
+            outcome.skipped.push(skipped);
+            self.advance_turn();
+        }
+
+        outcome.next_player = self.player().id;
+        self.emit(GameEvent::TurnStarted { player: outcome.next_player });
+        outcome
+    }
+
+    // Ask the custom rules what else the card does and apply it. Skips are returned
+    // rather than applied since they happen once the turn has passed on
+    fn apply_rule_effects(&mut self, player: PlayerId, card: Card, outcome: &mut PlayOutcome) -> usize
+    {
+        if self.rules.is_empty() {
+            return 0;
+        }
+
+        let view = self.spectator_view();
+        let mut rules = std::mem::take(&mut self.rules);
+        let effects: Vec<RuleEffect> = rules.iter_mut()
+            .flat_map(|rule| rule.on_card_played(&view, player, card))
+            .collect();
+        self.rules = rules;
+
+        let mut skips = 0;
+        for effect in &effects {
+            match *effect {
+                RuleEffect::Skip => skips += 1,
+                RuleEffect::Reverse => {
+                    self.reverse();
+                    outcome.reversed = !outcome.reversed;
+                },
+                RuleEffect::Draw { player, number_of_cards } => {
+                    if let Some(seat) = self.seat_of(player) {
+                        self.penalize(seat, number_of_cards);
+                    }
+                },
+                RuleEffect::RotateHands => {
+                    self.pass_hands_on();
+                    outcome.hands_rotated = true;
+                }
+            }
+        }
+        outcome.rule_effects = effects;
+        skips
+    }
+
+    pub fn next_turn(&mut self) -> Result<(), GameError>
+    {
+        self.check_can_act()?;
+        self.advance_turn();
+        self.emit(GameEvent::TurnStarted { player: self.player().id });
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    fn advance_turn(&mut self)
+    {
+        self.record_turn();
+        self.turn_number += 1;
+        let over_limit = self.config.max_turns.is_some_and(|max_turns| self.turn_number > max_turns);
+        if over_limit && !self.turn_limit_reached {
+            self.turn_limit_reached = true;
+            self.emit(GameEvent::TurnLimitReached);
+        }
+        self.current_player_idx = array_next_index(self.current_player_idx, 
+            self.players.len(), self.direction);
+    }
+
+    // Add what the current player did this turn to the history
+    fn record_turn(&mut self)
+    {
+        let summary = std::mem::take(&mut self.current_turn);
+        let record = TurnRecord {
+            turn: self.turn_number,
+            player: self.player().id,
+            action: summary.action(),
+            top_card: self.top_card(),
+            chosen_color: summary.played.filter(Card::is_wild).and(self.active_color)
+        };
+        self.stats.record_turn(&record);
+        self.opponents.record_hand_size(record.player, self.player().cards.len());
+        self.history.push_back(record);
+
+        if let Some(limit) = self.config.history_limit {
+            while self.history.len() > limit {
+                self.history.pop_front();
+            }
+        }
+    }
+
+    // The last `n` turns, oldest first. Only as many turns as `GameConfig::history_limit`
+    // allows are kept
+    pub fn history(&self, n: usize) -> impl Iterator<Item = &TurnRecord>
+    {
+        self.history.iter().skip(self.history.len().saturating_sub(n))
+    }
+
+    pub fn direction(&self) -> Direction
+    {
+        self.direction
+    }
+
+    // Every player once, starting with the current player and following the
+    // direction of play
+    pub fn turn_order(&self) -> impl Iterator<Item = &Player>
+    {
+        let length = self.players.len();
+        let direction = self.direction;
+        std::iter::successors(Some(self.current_player_idx), move |&seat| Some(array_next_index(seat, length, direction)))
+            .take(length)
+            .map(|seat| &self.players[seat])
+    }
+
+    #[deprecated(note = "use `Game::direction`, which returns a `Direction`")]
+    pub fn turn_direction(&self) -> &str
+    {
+        match self.direction {
+            Direction::Clockwise => "Clockwise",
+            Direction::CounterClockwise => "Counter Clockwise"
+        }
+    }
+
+    // Flip the turn direction. This doesn't apply the two-player skip, playing a
+    // Reverse card does that
+    pub fn reverse(&mut self) 
+    {
+        self.direction = self.direction.reversed();
+        self.emit(GameEvent::DirectionReversed { direction: self.direction });
+        self.debug_check_invariants();
+    }
+
+    pub fn top_card(&self) -> Card
+    {
+        self.top_card
+    }
+
+    // The color the next card must match, which is the color chosen for a wildcard on
+    // top. None until that color has been chosen
+    pub fn active_color(&self) -> Option<Color>
+    {
+        self.active_color
+    }
+
+    // Every card placed face up on the pile so far, starting with the first top card
+    pub fn discarded(&self) -> &[Card]
+    {
+        &self.discarded
+    }
+
+    // How many cards of a kind have been seen on the pile. The color is ignored for
+    // wildcards since they are all the same card
+    pub fn seen_count_of(&self, card_type: CardType, color: Color) -> usize
+    {
+        self.discarded.iter()
+            .filter(|card| card.card_type == card_type && (card.is_wild() || card.color == Some(color)))
+            .count()
+    }
+
+    // Why the game ended, or None while it is still going
+    pub fn end(&self) -> Option<GameEnd>
+    {
+        if let Some(player) = self.players.iter().find(|player| player.cards.is_empty()) {
+            return Some(GameEnd::Won(player.id));
+        }
+        if let [player] = self.players.as_slice() {
+            return Some(GameEnd::LastPlayerStanding(player.id));
+        }
+        if !self.turn_limit_reached {
+            return None;
+        }
+
+        let winner = match self.config.stalemate_policy {
+            StalematePolicy::Draw => None,
+            StalematePolicy::LowestPoints => {
+                let lowest = self.players.iter().map(|player| player.hand_points()).min();
+                let mut lowest_players = self.players.iter().filter(|player| Some(player.hand_points()) == lowest);
+                match (lowest_players.next(), lowest_players.next()) {
+                    (Some(player), None) => Some(player.id),
+                    _ => None
+                }
+            }
+        };
+        Some(GameEnd::TurnLimitReached { winner })
+    }
+
+    // The game ends as soon as a player has played their last card, when only one
+    // player is left, or when the turn limit is reached
+    pub fn is_over(&self) -> bool
+    {
+        self.end().is_some()
+    }
+
+    // Nobody wins a game that hit the turn limit under `StalematePolicy::Draw`
+    pub fn winner(&self) -> Option<&Player>
+    {
+        self.end().and_then(|end| end.winner()).and_then(|id| self.player_by_id(id))
+    }
+
+    pub fn max_players(&self) -> usize
+    {
+        self.max_players
+    }
+
+    // Let a new player join a game that is already running. Their name is checked like
+    // it is in the lobby, they are dealt a starting hand and they take the seat right
+    // after the current player, so they get the next turn. Returns their seat
+    pub fn add_player_mid_game(&mut self, username: &str) -> Result<usize, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if self.players.len() >= self.max_players {
+            return Err(GameError::LobbyFull { max_players: self.max_players });
+        }
+
+        let name = validate_name(username,
+            self.players.iter().map(|player| player.name.as_str()), self.case_sensitive_names)?;
+
+        let id = PlayerId(self.next_player_id);
+        self.next_player_id += 1;
+
+        let mut cards = Vec::with_capacity(self.config.starting_hand_size());
+        for _ in 0..self.config.starting_hand_size() {
+            let card = self.deck.draw();
+            cards.push(card);
+            self.emit(GameEvent::CardDealt { player: id, card });
+        }
+
+        // Going counter clockwise the seat after the current player is the one before it
+        let seat = if self.direction == Direction::CounterClockwise {
+            self.current_player_idx += 1;
+            self.current_player_idx - 1
+        } else {
+            self.current_player_idx + 1
+        };
+        self.players.insert(seat, Player { id, name, cards, called_uno: false, catchable: false });
+        self.emit(GameEvent::PlayerJoined { player: id });
+        self.debug_check_invariants();
+        Ok(seat)
+    }
+
+    // Take the player in `seat` out of the game along with their cards. If it was their
+    // turn, play passes on and anything they still had to resolve is dropped. The game
+    // is over once only one player remains
+    pub fn remove_player(&mut self, seat: usize) -> Result<RemovedPlayer, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if seat >= self.players.len() {
+            return Err(GameError::InvalidSeat(seat));
+        }
+
+        let was_current = seat == self.current_player_idx;
+        let player = self.players.remove(seat);
+        self.departed.push((player.id, player.hand_points()));
+        self.emit(GameEvent::PlayerLeft { player: player.id });
+
+        if let Some(play) = &self.challengeable_draw_four {
+            if was_current || play.player == player.id {
+                self.challengeable_draw_four = None;
+            }
+        }
+
+        if seat < self.current_player_idx {
+            self.current_player_idx -= 1;
+        } else if was_current {
+            // The seats after the removed one have shifted down, so the player after
+            // them is already in their seat when going clockwise
+            self.current_player_idx = if self.direction == Direction::CounterClockwise {
+                array_next_index(seat, self.players.len(), Direction::CounterClockwise)
+            } else if seat >= self.players.len() {
+                0
+            } else {
+                seat
+            };
+
+            self.turn_number += 1;
+            self.current_turn = TurnSummary::default();
+
+            // Whatever was waiting on the player who left goes with them, except that
+            // an uncolored wildcard still needs a color from the next player
+            self.unresolved_play = None;
+            self.drawn_card = None;
+            self.pending_penalty = 0;
+            self.pending_action = if self.needs_wild_color() { Some(PendingAction::AwaitingWildColor) } else { None };
+        }
+        // The last player standing has nothing left to resolve
+        if self.is_over() {
+            self.unresolved_play = None;
+            self.drawn_card = None;
+            self.pending_penalty = 0;
+            self.pending_action = None;
+        }
+
+        let winner = self.winner().map(|winner| winner.id);
+        if was_current && !self.is_over() {
+            self.emit(GameEvent::TurnStarted { player: self.player().id });
+        }
+
+        self.debug_check_invariants();
+        Ok(RemovedPlayer { id: player.id, name: player.name, cards: player.cards, was_current, winner })
+    }
+
+    // Play a card from the current player's hand and apply its effects. Playing a
+    // wildcard this way leaves the effects waiting on `set_wildcard_color`
+    pub fn play(&mut self, card_index: usize) -> Result<PlayOutcome, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+
+        let player = &self.players[self.current_player_idx];
+        let attempted = *player.cards.get(card_index)
+            .ok_or(GameError::InvalidCardIndex { given: card_index, hand_size: player.cards.len() })?;
+        self.check_can_play(attempted)?;
+
+        self.challengeable_draw_four = None;
+        if self.config.draw_four_challenge && attempted.card_type == CardType::DrawFourWildcard {
+            self.challengeable_draw_four = Some(DrawFourPlay {
+                player: self.player().id,
+                hand: self.player().cards.clone(),
+                active_color: self.active_color
+            });
+        }
+
+        self.close_catch_window();
+        let player = &mut self.players[self.current_player_idx];
+        let card = player.cards.remove(card_index);
+        let id = player.id;
+        self.hand_changed(self.current_player_idx);
+        self.emit(GameEvent::CardPlayed { player: id, card });
+        let outcome = self.place_on_pile(id, card, 1);
+        self.debug_check_invariants();
+        Ok(outcome)
+    }
+
+    // Play several identical cards from the current player's hand at once. The first
+    // must be playable and every copy applies its effect, see `resolve_play`.
+    // Wildcards can't be played together
+    pub fn play_many(&mut self, indices: &[usize]) -> Result<PlayOutcome, GameError>
+    {
+        if !self.config.play_identical_together {
+            return Err(GameError::PlayManyDisabled);
+        }
+        let (&first, rest) = indices.split_first().ok_or(GameError::NoCardsSelected)?;
+        if rest.is_empty() {
+            return self.play(first);
+        }
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+
+        let hand = &self.player().cards;
+        let mut chosen = Vec::with_capacity(indices.len());
+        for &index in indices {
+            let card = *hand.get(index).ok_or(GameError::InvalidCardIndex { given: index, hand_size: hand.len() })?;
+            if chosen.iter().any(|&(chosen_index, _)| chosen_index == index) {
+                return Err(GameError::DuplicateCardIndex(index));
+            }
+            chosen.push((index, card));
+        }
+
+        let card = chosen[0].1;
+        if card.is_wild() {
+            return Err(GameError::CannotPlayTogether(card));
+        }
+        if let Some(&(_, other)) = chosen.iter().find(|(_, other)| *other != card) {
+            return Err(GameError::CardsNotIdentical { first: card, other });
+        }
+        self.check_can_play_copies(card, indices.len())?;
+
+        self.challengeable_draw_four = None;
+        self.close_catch_window();
+        // Remove from the back of the hand first so the other indices stay valid
+        chosen.sort_by_key(|(index, _)| std::cmp::Reverse(*index));
+        let id = self.player().id;
+        for (index, _) in chosen {
+            self.players[self.current_player_idx].cards.remove(index);
+            self.emit(GameEvent::CardPlayed { player: id, card });
+        }
+        self.hand_changed(self.current_player_idx);
+        let outcome = self.place_on_pile(id, card, indices.len());
+        self.debug_check_invariants();
+        Ok(outcome)
+    }
+
+    // Play a wildcard and choose its color in one step. Nothing changes unless both
+    // the card and the color are valid
+    pub fn play_wild(&mut self, card_index: usize, color: Color) -> Result<PlayOutcome, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+
+        let cards = &self.player().cards;
+        let card = *cards.get(card_index)
+            .ok_or(GameError::InvalidCardIndex { given: card_index, hand_size: cards.len() })?;
+
+        if !card.is_wild() {
+            return Err(GameError::NotAWildcard(card));
+        }
+        self.check_can_play(card)?;
+
+        let outcome = self.play(card_index)?;
+        if outcome.awaiting_wild_color {
+            Ok(self.set_wildcard_color(color)?.expect("A played wildcard resolves once its color is chosen"))
+        } else {
+            Ok(outcome)
+        }
+    }
+
+    pub fn draw_one(&mut self) -> Result<DrawnCard, GameError>
+    {
+        self.check_can_draw()?;
+        self.close_catch_window();
+
+        let (index, card) = self.draw_into_hand();
+        self.offer_drawn_card(index, card)
+    }
+
+    // Under the draw until playable rule, keep drawing until a playable card turns
+    // up or `limit` cards have been drawn. A playable card is then handled exactly
+    // like one drawn with `draw_one`. At least one card is always drawn, so a limit
+    // of 0 can't be used to pass the turn for free
+    pub fn draw_until_playable(&mut self, limit: Option<usize>) -> Result<DrawUntilOutcome, GameError>
+    {
+        if !self.config.draw_until_playable {
+            return Err(GameError::DrawUntilPlayableDisabled);
+        }
+        self.check_can_draw()?;
+        self.close_catch_window();
+
+        let limit = limit.map(|limit| limit.max(1));
+        let mut cards_drawn = 0;
+        while limit.is_none_or(|limit| cards_drawn < limit) {
+            let (index, card) = self.draw_into_hand();
+            cards_drawn += 1;
+
+            if self.is_playable_on_top(card) {
+                let found = Some(self.offer_drawn_card(index, card)?);
+                return Ok(DrawUntilOutcome { cards_drawn, found });
+            }
+        }
+
+        self.end_turn();
+        self.debug_check_invariants();
+        Ok(DrawUntilOutcome { cards_drawn, found: None })
+    }
+
+    fn check_can_draw(&self) -> Result<(), GameError>
+    {
+        self.check_can_act()?;
+
+        let playable_indices = self.forced_play_indices();
+        if !playable_indices.is_empty() {
+            return Err(GameError::MustPlay { playable_indices });
+        }
+        Ok(())
+    }
+
+    // Draw a card into the current player's hand, returning where it ended up
+    fn draw_into_hand(&mut self) -> (usize, Card)
+    {
+        self.record_action(self.player().id, ActionKind::Drew);
+        self.opponents.record_draw(self.player().id, self.active_color);
+        self.current_turn.drawn += 1;
+        let card = self.deck.draw();
+        let player = &mut self.players[self.current_player_idx];
+        player.cards.push(card);
+        let index = player.cards.len() - 1;
+        let id = player.id;
+        self.emit(GameEvent::CardDrawn { player: id, card });
+        self.hand_changed(self.current_player_idx);
+        (index, card)
+    }
+
+    fn offer_drawn_card(&mut self, index: usize, card: Card) -> Result<DrawnCard, GameError>
+    {
+        let playable = self.is_playable_on_top(card);
+        let mut drawn = DrawnCard { card, playable, auto_played: None };
+        if !playable {
+            // The card is not playable so the player keeps it and their turn ends
+            self.end_turn();
+        } else if self.config.auto_play_drawn_cards {
+            drawn.auto_played = Some(self.play(index)?);
+        } else {
+            self.drawn_card = Some(index);
+            self.pending_action = Some(PendingAction::PlayOrKeepDrawnCard);
+        }
+        self.debug_check_invariants();
+        Ok(drawn)
+    }
+
+    fn take_drawn_card(&mut self) -> Result<usize, GameError>
+    {
+        if self.pending_action != Some(PendingAction::PlayOrKeepDrawnCard) {
+            return Err(GameError::NoDrawnCard);
+        }
+        self.pending_action = None;
+        self.drawn_card.take().ok_or(GameError::NoDrawnCard)
+    }
+
+    // Play the playable card that was just drawn. A wildcard then waits for its color
+    // like any other played wildcard
+    pub fn play_drawn(&mut self) -> Result<PlayOutcome, GameError>
+    {
+        let index = self.take_drawn_card()?;
+        self.play(index)
+    }
+
+    pub fn play_drawn_wild(&mut self, color: Color) -> Result<PlayOutcome, GameError>
+    {
+        let index = self.drawn_card.ok_or(GameError::NoDrawnCard)?;
+        let card = self.player().cards[index];
+        if !card.is_wild() {
+            return Err(GameError::NotAWildcard(card));
+        }
+
+        let index = self.take_drawn_card()?;
+        self.play_wild(index, color)
+    }
+
+    // Keep the playable card that was just drawn, which ends the turn
+    pub fn keep_drawn(&mut self) -> Result<PlayerId, GameError>
+    {
+        if self.config.forced_play && self.pending_action == Some(PendingAction::PlayOrKeepDrawnCard) {
+            let playable_indices = self.drawn_card.into_iter().collect();
+            return Err(GameError::MustPlay { playable_indices });
+        }
+
+        self.take_drawn_card()?;
+        self.end_turn();
+        self.debug_check_invariants();
+        Ok(self.player().id)
+    }
+
+    // Put the current player's hand in order, see `Card::sort_key`. Card indices change,
+    // so this can't be done partway through a turn, like while a drawn card waits to be
+    // played or kept
+    pub fn sort_hand(&mut self) -> Result<(), GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if let Some(action) = self.pending_action {
+            return Err(GameError::ActionPending(action));
+        }
+
+        let seat = self.current_player_idx;
+        self.players[seat].cards.sort_by_key(Card::sort_key);
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    fn end_turn(&mut self)
+    {
+        self.advance_turn();
+        self.emit(GameEvent::TurnStarted { player: self.player().id });
+    }
+
+    // Draw the pending penalty and lose the turn
+    pub fn accept_penalty(&mut self) -> Result<PenaltyOutcome, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if let Some(action) = self.pending_action {
+            return Err(GameError::ActionPending(action));
+        }
+        if self.pending_penalty == 0 {
+            return Err(GameError::NoPenaltyPending);
+        }
+
+        self.close_catch_window();
+        let number_of_cards = std::mem::take(&mut self.pending_penalty);
+        self.challengeable_draw_four = None;
+        let player = self.player().id;
+        let cards_drawn = self.draw_multiple(number_of_cards);
+        self.current_turn.penalty += cards_drawn.len();
+        self.emit(GameEvent::TurnSkipped { player });
+
+        self.advance_turn();
+        let next_player = self.player().id;
+        self.emit(GameEvent::TurnStarted { player: next_player });
+        self.debug_check_invariants();
+        Ok(PenaltyOutcome { player, cards_drawn, next_player })
+    }
+
+    // Play a card identical to the top card from any player's hand, even when it isn't
+    // their turn. Play continues from the player who jumped in
+    pub fn jump_in(&mut self, seat: usize, card_index: usize) -> Result<JumpInOutcome, JumpInError>
+    {
+        if !self.config.jump_in {
+            return Err(GameError::JumpInDisabled);
+        }
+        self.check_can_act()?;
+
+        let player = self.players.get(seat).ok_or(GameError::InvalidSeat(seat))?;
+        let attempted = *player.cards.get(card_index)
+            .ok_or(GameError::InvalidCardIndex { given: card_index, hand_size: player.cards.len() })?;
+
+        // Wildcards are never identical since the one on top has had a color chosen
+        let top = self.top_card();
+        if attempted.is_wild() || attempted != top {
+            return Err(GameError::NotIdentical { attempted, top });
+        }
+        // The chance to jump in passes once someone draws on their turn. Penalty draws
+        // are part of the play itself so they don't count
+        let last_action = self.last_action().map(|action| action.kind);
+        if !matches!(last_action, Some(ActionKind::Played(_) | ActionKind::DrewPenalty(_))) {
+            return Err(GameError::JumpInTooLate);
+        }
+        // Custom rules may still turn the card down, and nothing changes until it's
+        // certain the play will go through
+        self.check_can_play(attempted)?;
+
+        let interrupted = self.player().id;
+        self.current_player_idx = seat;
+        self.emit(GameEvent::JumpedIn { player: self.player().id, interrupted });
+        let play = self.play(card_index).expect("A jump in is checked before the turn changes hands");
+        Ok(JumpInOutcome { interrupted, play })
+    }
+
+    // Under the seven-zero rule, swap the hand of the player who played a 7 with the
+    // player in `seat`, then apply the rest of the play
+    pub fn choose_swap_target(&mut self, seat: usize) -> Result<PlayOutcome, GameError>
+    {
+        if self.pending_action != Some(PendingAction::ChooseSwapTarget) {
+            return Err(GameError::NoSwapPending);
+        }
+        if seat >= self.players.len() || seat == self.current_player_idx {
+            return Err(GameError::InvalidSwapTarget(seat));
+        }
+
+        let player = self.player().id;
+        let target = self.players[seat].id;
+        let hand = std::mem::take(&mut self.players[self.current_player_idx].cards);
+        self.players[self.current_player_idx].cards = std::mem::replace(&mut self.players[seat].cards, hand);
+        self.hand_changed(self.current_player_idx);
+        self.hand_changed(seat);
+        self.emit(GameEvent::HandsSwapped { player, target });
+
+        self.pending_action = None;
+        let copies = self.unresolved_play.take().map_or(1, |(_, copies)| copies);
+        let mut outcome = self.resolve_play(player, self.top_card(), copies);
+        outcome.swapped_with = Some(target);
+        self.debug_check_invariants();
+        Ok(outcome)
+    }
+
+    // Pass every hand to the next player in the direction of play. Hands can't move
+    // while something is pending, since it may be waiting on a card in one of them
+    pub fn rotate_hands(&mut self) -> Result<(), GameError>
+    {
+        self.check_can_act()?;
+        self.pass_hands_on();
+        Ok(())
+    }
+
+    fn pass_hands_on(&mut self)
+    {
+        let length = self.players.len();
+        let hands: Vec<Vec<Card>> = self.players.iter_mut().map(|player| std::mem::take(&mut player.cards)).collect();
+
+        for (index, hand) in hands.into_iter().enumerate() {
+            let receiver = array_next_index(index, length, self.direction);
+            self.players[receiver].cards = hand;
+        }
+        for seat in 0..length {
+            self.hand_changed(seat);
+        }
+        self.emit(GameEvent::HandsRotated);
+        self.debug_check_invariants();
+    }
+
+    // Whether the player in `seat` could play a Draw Four right now without breaking
+    // the rule that they must not hold a card of the active color
+    pub fn can_legally_play_draw_four(&self, seat: usize) -> bool
+    {
+        self.players.get(seat).is_some_and(|player| {
+            !player.cards.iter().any(|card| card.color.is_some() && card.color == self.active_color)
+        })
+    }
+
+    pub fn can_challenge_draw_four(&self) -> bool
+    {
+        self.challengeable_draw_four.is_some() && self.pending_penalty > 0 && self.pending_action.is_none()
+    }
+
+    // Challenge the Draw Four that was just played on the current player. If it was
+    // played illegally its player draws 4 instead, otherwise the challenger draws 6 and loses their turn
+    pub fn challenge_draw_four(&mut self) -> Result<ChallengeOutcome, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if !self.can_challenge_draw_four() {
+            return Err(GameError::NoChallengeAvailable);
+        }
+
+        self.close_catch_window();
+        let play = self.challengeable_draw_four.take().expect("A challengeable Draw Four was checked for");
+        let challenger = self.player().id;
+        let offender_guilty = !play.was_legal();
+        self.emit(GameEvent::DrawFourChallenged { challenger, offender: play.player, offender_guilty });
+
+        if offender_guilty {
+            // The offender takes back the 4 cards, anything stacked before stays with the challenger
+            self.pending_penalty = self.pending_penalty.saturating_sub(4);
+            let seat = self.seat_of(play.player).expect("The offender is still in the game");
+            let cards_drawn = self.penalize(seat, 4);
+            self.debug_check_invariants();
+
+            Ok(ChallengeOutcome {
+                challenger,
+                offender: play.player,
+                offender_guilty,
+                penalized: play.player,
+                cards_drawn,
+                next_player: challenger
+            })
+        } else {
+            self.pending_penalty += 2;
+            let penalty = self.accept_penalty()?;
+
+            Ok(ChallengeOutcome {
+                challenger,
+                offender: play.player,
+                offender_guilty,
+                penalized: challenger,
+                cards_drawn: penalty.cards_drawn,
+                next_player: penalty.next_player
+            })
+        }
+    }
+
+    // Draw cards into the hand of the player in `seat` without changing whose turn it is.
+    // The cards count as a penalty in the stats
+    pub fn draw_multiple_for(&mut self, seat: usize, number_of_cards: u8) -> Result<Vec<Card>, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        if seat >= self.players.len() {
+            return Err(GameError::InvalidSeat(seat));
+        }
+        Ok(self.penalize(seat, number_of_cards))
+    }
+
+    // `draw_multiple_for` a seat that's known to exist
+    fn penalize(&mut self, seat: usize, number_of_cards: u8) -> Vec<Card>
+    {
+        let id = self.players[seat].id;
+        self.record_action(id, ActionKind::DrewPenalty(number_of_cards));
+        self.stats.player_mut(id).penalty_cards_received += number_of_cards as usize;
+        let mut drawn = Vec::with_capacity(number_of_cards as usize);
+
+        for _ in 0..number_of_cards {
+            let card = self.deck.draw();
+            self.players[seat].cards.push(card);
+            drawn.push(card);
+            self.emit(GameEvent::CardDrawn { player: id, card });
+        }
+        self.hand_changed(seat);
+        self.debug_check_invariants();
+        drawn
+    }
+
+    // Keep the Uno flags in step with the size of a player's hand
+    fn hand_changed(&mut self, seat: usize)
+    {
+        let player = &mut self.players[seat];
+        if player.cards.len() == 1 {
+            player.catchable = !player.called_uno;
+        } else {
+            player.called_uno = false;
+            player.catchable = false;
+        }
+    }
+
+    // A player who failed to call Uno can only be caught until the next play or draw
+    fn close_catch_window(&mut self)
+    {
+        for player in self.players.iter_mut() {
+            player.catchable = false;
+        }
+    }
+
+    // Call Uno for the player in `seat`. This is allowed once they are down to one
+    // card, or on their own turn while holding the two cards they are about to go down from
+    pub fn call_uno(&mut self, seat: usize) -> Result<(), GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let player = self.players.get(seat).ok_or(GameError::InvalidSeat(seat))?;
+        let allowed = match player.cards.len() {
+            1 => true,
+            2 => seat == self.current_player_idx,
+            _ => false
+        };
+        if !allowed {
+            return Err(GameError::CannotCallUno);
+        }
+
+        let player = &mut self.players[seat];
+        player.called_uno = true;
+        player.catchable = false;
+        let id = player.id;
+        self.emit(GameEvent::UnoCalled { player: id });
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    // Accuse the player in `target_seat` of not calling Uno. If they can still be
+    // caught they draw 2 cards. A false accusation is rejected, or costs the accuser
+    // 2 cards when `penalize_false_uno_accusations` is on
+    pub fn catch_failure_to_call(&mut self, accuser_seat: usize, target_seat: usize) -> Result<CatchOutcome, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let accuser = self.players.get(accuser_seat).ok_or(GameError::InvalidSeat(accuser_seat))?.id;
+        let target = self.players.get(target_seat).ok_or(GameError::InvalidSeat(target_seat))?;
+        let caught = target.catchable && accuser_seat != target_seat;
+        let target = target.id;
+
+        if !caught && !self.config.penalize_false_uno_accusations {
+            return Err(GameError::FalseUnoAccusation);
+        }
+
+        self.emit(GameEvent::UnoCaught { accuser, target, caught });
+        let (penalized, seat) = if caught { (target, target_seat) } else { (accuser, accuser_seat) };
+        let cards_drawn = self.penalize(seat, 2);
+        Ok(CatchOutcome { accuser, target, caught, penalized, cards_drawn })
+    }
+
+    // Draw cards into the current player's hand, returning the cards drawn
+    pub fn draw_multiple(&mut self, number_of_cards: u8) -> Vec<Card>
+    {
+        self.penalize(self.current_player_idx, number_of_cards)
+    }
+
+    // True while the top card is a wildcard whose color hasn't been chosen yet
+    pub fn needs_wild_color(&self) -> bool
+    {
+        self.top_card.is_wild() && self.active_color.is_none()
+    }
+
+    // Choose the color of the wildcard on top of the pile. If the wildcard was just
+    // played, its effects are applied now and the resulting outcome is returned. Only
+    // a wildcard still waiting for its color can be given one
+    pub fn set_wildcard_color(&mut self, color: Color) -> Result<Option<PlayOutcome>, GameError>
+    {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let card = self.top_card;
+        if !card.is_wild() {
+            return Err(GameError::TopCardNotWild);
+        }
+        if self.pending_action != Some(PendingAction::AwaitingWildColor) {
+            return Err(GameError::NoWildColorPending);
+        }
+
+        self.active_color = Some(color);
+        self.pending_action = None;
+        self.emit(GameEvent::WildColorChosen { player: self.player().id, color });
+
+        let outcome = self.unresolved_play.take().map(|(player, copies)| self.resolve_play(player, card, copies));
+        self.debug_check_invariants();
+        Ok(outcome)
+    }
+
+    // Check that the game's state is consistent, naming the first problem found.
+    // Debug builds run this after every change to the game
+    pub fn check_invariants(&self) -> Result<(), InvariantViolation>
+    {
+        if self.players.is_empty() {
+            return Err(InvariantViolation::NoPlayers);
+        }
+        if self.current_player_idx >= self.players.len() {
+            return Err(InvariantViolation::CurrentSeatOutOfRange {
+                seat: self.current_player_idx,
+                number_of_players: self.players.len()
+            });
+        }
+
+        let mut ids: Vec<PlayerId> = self.players.iter().map(|player| player.id).collect();
+        ids.sort();
+        if let Some(pair) = ids.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(InvariantViolation::DuplicatePlayerId(pair[0]));
+        }
+        // The next player to join has to get an id nobody has
+        if let Some(&id) = ids.last().filter(|id| id.0 >= self.next_player_id) {
+            return Err(InvariantViolation::PlayerIdOutOfRange(id));
+        }
+
+        // Wildcards never have a color and every other card always does
+        for &card in self.players.iter().flat_map(|player| player.cards.iter()) {
+            if card.is_wild() != card.color.is_none() {
+                return Err(InvariantViolation::MiscoloredCard { card, in_hand: true });
+            }
+        }
+        if let Some(&card) = self.discarded.iter().find(|card| card.is_wild() != card.color.is_none()) {
+            return Err(InvariantViolation::MiscoloredCard { card, in_hand: false });
+        }
+        if !self.top_card.is_wild() && self.active_color != self.top_card.color {
+            return Err(InvariantViolation::ActiveColorMismatch {
+                top: self.top_card,
+                active_color: self.active_color
+            });
+        }
+        if self.discarded.last() != Some(&self.top_card) {
+            return Err(InvariantViolation::TopCardNotOnPile {
+                top: self.top_card,
+                last_discarded: self.discarded.last().copied()
+            });
+        }
+
+        if self.is_over() {
+            if let Some(action) = self.pending_action {
+                return Err(InvariantViolation::ActionPendingAfterGameOver(action));
+            }
+            return Ok(());
+        }
+
+        if self.needs_wild_color() && self.pending_action != Some(PendingAction::AwaitingWildColor) {
+            return Err(InvariantViolation::UncoloredWildWithoutPendingAction);
+        }
+        if self.pending_action == Some(PendingAction::AwaitingWildColor) && !self.top_card.is_wild() {
+            return Err(InvariantViolation::PendingWildColorWithoutWild(self.top_card));
+        }
+        if self.pending_action == Some(PendingAction::PlayOrKeepDrawnCard) && self.drawn_card.is_none() {
+            return Err(InvariantViolation::PendingActionWithoutDrawnCard);
+        }
+        if let Some(index) = self.drawn_card {
+            if self.pending_action != Some(PendingAction::PlayOrKeepDrawnCard) {
+                return Err(InvariantViolation::DrawnCardWithoutPendingAction);
+            }
+            let hand_size = self.player().cards.len();
+            if index >= hand_size {
+                return Err(InvariantViolation::DrawnCardOutOfRange { index, hand_size });
+            }
+        }
+        Ok(())
+    }
+
+    fn debug_check_invariants(&self)
+    {
+        if cfg!(debug_assertions) {
+            if let Err(violation) = self.check_invariants() {
+                panic!("Game invariant violated: {}", violation);
+            }
+        }
+    }
+
+    // What the first top card did when the game started
+    pub fn start_effect(&self) -> &StartEffect
+    {
+        &self.start_effect
+    }
+
+    // Deal the starting hands and apply the effects of the top card the lobby turned over
+    fn start(&mut self)
+    {
+        for (player, card) in self.start_effect.draw_for_deal.clone() {
+            self.emit(GameEvent::DrewForDeal { player, card });
+        }
+
+        // Deal a starting hand to each player
+        for seat in 0..self.players.len() {
+            for _ in 0..self.config.starting_hand_size() {
+                let card = self.deck.draw();
+                self.players[seat].cards.push(card);
+                self.emit(GameEvent::CardDealt { player: self.players[seat].id, card });
+            }
+        }
+
+        self.turn_number = 1;
+        let first_player = self.player().id;
+        let top_card = self.top_card;
+        self.discarded.push(top_card);
+
+        self.emit(GameEvent::GameStarted {
+            players: self.players.iter().map(|player| player.id).collect(),
+            top_card
+        });
+
+        // The first card affects the first player as if it had been played on them,
+        // except that a Reverse sends the turn back the other way
+        match top_card.card_type {
+            CardType::Reverse => {
+                self.reverse();
+                self.start_effect.reversed = true;
+            },
+            CardType::DrawTwo => {
+                let cards = self.draw_multiple(2);
+                self.current_turn.penalty += cards.len();
+                self.start_effect.penalty = Some((first_player, cards));
+            },
+            CardType::Wildcard => {
+                self.pending_action = Some(PendingAction::AwaitingWildColor);
+                self.start_effect.awaiting_wild_color = true;
+            },
+            _ => ()
+        }
+        if let CardType::Skip | CardType::Reverse | CardType::DrawTwo = top_card.card_type {
+            self.emit(GameEvent::TurnSkipped { player: first_player });
+            self.start_effect.skipped = Some(first_player);
+            self.advance_turn();
+        }
+
+        self.start_effect.starting_player = self.player().id;
+        self.emit(GameEvent::TurnStarted { player: self.start_effect.starting_player });
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::config::{StackingPolicy, RULE_NAMES};
+
+    fn cards(names: &[&str]) -> Vec<Card>
+    {
+        names.iter().map(|name| name.parse().expect("Test cards are spelled right")).collect()
+    }
+
+    // A game between P0, P1... with seat 0 going first. `top` is turned over, everyone
+    // is dealt their hand from `hands`, which must all be the same size, and then the
+    // deck hands out `draws` before any random cards
+    fn scripted(config: GameConfig, top: &str, hands: &[&[&str]], draws: &[&str]) -> Game
+    {
+        let mut lobby = Lobby::with_players((0..hands.len()).map(|seat| format!("P{}", seat))).unwrap();
+        lobby.set_config(config);
+        deal(lobby, top, hands, draws)
+    }
+
+    // Like `scripted`, for a lobby that has been set up already
+    fn deal(mut lobby: Lobby, top: &str, hands: &[&[&str]], draws: &[&str]) -> Game
+    {
+        assert!(hands.iter().all(|hand| hand.len() == hands[0].len()), "Every hand is dealt the same size");
+        let mut config = lobby.config().clone();
+        config.starting_player = StartingPlayer::Seat(0);
+        config.hand_size = Some(hands[0].len());
+        lobby.set_config(config);
+
+        let mut deck = cards(&[top]);
+        for hand in hands {
+            deck.extend(cards(hand));
+        }
+        deck.extend(cards(draws));
+        lobby.start_with_deck(0, deck).unwrap()
+    }
+
+    fn id(game: &Game, seat: usize) -> PlayerId
+    {
+        game.players()[seat].id()
+    }
+
+    fn names(players: &[Player]) -> Vec<&str>
+    {
+        players.iter().map(|player| player.name().as_str()).collect()
+    }
+
+    #[test]
+    fn a_game_is_seated_the_way_the_lobby_was_left()
+    {
+        let mut lobby = Lobby::with_players(["Ann", "Ben", "Cat", "Dan"]).unwrap();
+        lobby.move_player(3, 0).unwrap();
+        lobby.move_player(1, 2).unwrap();
+        assert_eq!(names(lobby.seating()), ["Dan", "Ben", "Ann", "Cat"]);
+        assert_eq!(lobby.move_player(4, 0), Err(GameError::InvalidSeat(4)));
+        assert_eq!(lobby.move_player(0, 4), Err(GameError::InvalidSeat(4)));
+
+        let hands: [&[&str]; 4] = [&["red 1"], &["red 2"], &["red 3"], &["red 4"]];
+        let game = deal(lobby, "blue 5", &hands, &[]);
+        assert_eq!(names(game.players()), ["Dan", "Ben", "Ann", "Cat"]);
+        assert_eq!(game.players()[2].cards(), cards(&["red 3"]));
+        assert_eq!(game.to_string(), "[Dan] -> Ben -> Ann -> Cat ↻ clockwise");
+    }
+
+    #[test]
+    fn the_next_round_is_seated_from_the_game()
+    {
+        let mut lobby = Lobby::with_players(["Ann", "Ben", "Cat"]).unwrap();
+        lobby.set_case_sensitive_names(true);
+        let mut game = deal(lobby, "blue 5", &[&["red 1"], &["red 2"], &["red 3"]], &[]);
+        let cat = game.players()[2].id();
+        game.remove_player(2).unwrap();
+
+        // Cat's id isn't given to whoever takes their place, and names are still told apart by case
+        let mut lobby = Lobby::from_game(&game, Controllers::new());
+        assert_eq!(names(lobby.seating()), ["Ann", "Ben"]);
+        lobby.add_player("Dan").unwrap();
+        lobby.add_player("ann").unwrap();
+        let ids: Vec<PlayerId> = lobby.seating().iter().map(Player::id).collect();
+        assert!(!ids.contains(&cat));
+        assert_eq!(lobby.seating().len(), 4);
+    }
+
+    #[test]
+    fn shuffled_seats_follow_the_seed()
+    {
+        let shuffled = |seed| {
+            let mut lobby = Lobby::with_players(["Ann", "Ben", "Cat", "Dan", "Eve"]).unwrap();
+            let ids: Vec<(PlayerId, String)> =
+                lobby.seating().iter().map(|player| (player.id(), player.name().clone())).collect();
+            lobby.shuffle_seating(&mut SmallRng::seed_from_u64(seed));
+            let game = lobby.start_with_seed(seed).unwrap();
+            // Everyone keeps their id wherever they end up
+            for player in game.players() {
+                assert!(ids.contains(&(player.id(), player.name().clone())));
+            }
+            game.players().iter().map(|player| player.name().clone()).collect::<Vec<String>>()
+        };
+        assert_eq!(shuffled(1), shuffled(1));
+        assert!((0..10).any(|seed| shuffled(seed) != ["Ann", "Ben", "Cat", "Dan", "Eve"]));
+    }
+
+    // A three player game that turns over `turned_over` until one isn't a Draw Four.
+    // Seat 0 is chosen to go first and any draws come from `draws`
+    fn starting_on(turned_over: &[&str], draws: &[&str]) -> Game
+    {
+        let mut lobby = Lobby::with_players(["P0", "P1", "P2"]).unwrap();
+        lobby.set_config(GameConfig { starting_player: StartingPlayer::Seat(0), hand_size: Some(2),
+            ..GameConfig::default() });
+        let mut deck = cards(turned_over);
+        deck.extend(cards(&["blue 1", "blue 2", "green 3", "green 4", "yellow 6", "yellow 7"]));
+        deck.extend(cards(draws));
+        lobby.start_with_deck(0, deck).unwrap()
+    }
+
+    #[test]
+    fn a_number_card_turned_over_does_nothing()
+    {
+        let game = starting_on(&["red 5"], &[]);
+        let effect = game.start_effect();
+        assert_eq!(effect.top_card, "red 5".parse().unwrap());
+        assert!(effect.redrawn.is_empty());
+        assert!(!effect.reversed && !effect.awaiting_wild_color);
+        assert_eq!((effect.skipped, &effect.penalty), (None, &None));
+        assert_eq!((effect.first_player, effect.starting_player), (id(&game, 0), id(&game, 0)));
+        assert_eq!(game.pending_action(), None);
+    }
+
+    #[test]
+    fn a_skip_turned_over_skips_the_first_player()
+    {
+        let game = starting_on(&["red skip"], &[]);
+        let effect = game.start_effect();
+        assert_eq!(effect.skipped, Some(id(&game, 0)));
+        assert_eq!(effect.starting_player, id(&game, 1));
+        assert!(!effect.reversed);
+        assert_eq!(game.current_seat(), 1);
+    }
+
+    #[test]
+    fn a_reverse_turned_over_sends_the_turn_the_other_way()
+    {
+        let game = starting_on(&["red reverse"], &[]);
+        let effect = game.start_effect();
+        assert!(effect.reversed);
+        assert_eq!(effect.skipped, Some(id(&game, 0)));
+        assert_eq!(effect.starting_player, id(&game, 2));
+        assert_eq!(game.direction(), Direction::CounterClockwise);
+    }
+
+    #[test]
+    fn a_draw_two_turned_over_makes_the_first_player_draw()
+    {
+        let game = starting_on(&["red +2"], &["red 8", "red 9"]);
+        let effect = game.start_effect();
+        assert_eq!(effect.penalty, Some((id(&game, 0), cards(&["red 8", "red 9"]))));
+        assert_eq!(effect.skipped, Some(id(&game, 0)));
+        assert_eq!(effect.starting_player, id(&game, 1));
+        assert_eq!(game.players()[0].number_of_cards(), 4);
+    }
+
+    #[test]
+    fn a_wildcard_turned_over_lets_the_first_player_choose_the_color()
+    {
+        let game = starting_on(&["wild"], &[]);
+        let effect = game.start_effect();
+        assert!(effect.awaiting_wild_color);
+        assert_eq!(effect.skipped, None);
+        assert_eq!(effect.starting_player, id(&game, 0));
+        assert_eq!(game.pending_action(), Some(PendingAction::AwaitingWildColor));
+    }
+
+    #[test]
+    fn draw_fours_turned_over_are_put_back()
+    {
+        let game = starting_on(&["+4", "+4", "green skip"], &[]);
+        let effect = game.start_effect();
+        assert_eq!(effect.redrawn, cards(&["+4", "+4"]));
+        assert_eq!(effect.top_card, "green skip".parse().unwrap());
+        assert_eq!(game.top_card(), effect.top_card);
+        assert_eq!(effect.starting_player, id(&game, 1));
+        assert_eq!(game.players()[0].cards(), cards(&["blue 1", "blue 2"]));
+    }
+
+    #[test]
+    fn only_0_to_9_make_a_digit()
+    {
+        assert_eq!(Digit::new(9), Ok(Digit::NINE));
+        assert_eq!(Digit::try_from(0), Ok(Digit::ZERO));
+        assert_eq!(Digit::new(10), Err(InvalidDigit(10)));
+        assert_eq!(Digit::try_from(255), Err(InvalidDigit(255)));
+        assert_eq!(InvalidDigit(42).to_string(), "42 is not a digit from 0 to 9");
+        assert_eq!(u8::from(Digit::SEVEN), 7);
+        assert_eq!(Digit::all().map(u8::from).collect::<Vec<u8>>(), (0..=9).collect::<Vec<u8>>());
+
+        assert_eq!("red 12".parse::<Card>(), Err(ParseCardError::UnknownCard(String::from("red 12"))));
+        #[cfg(feature = "serde")]
+        {
+            assert_eq!(serde_json::from_str::<Digit>("7").unwrap(), Digit::SEVEN);
+            assert!(serde_json::from_str::<Digit>("42").is_err());
+        }
+    }
+
+    #[test]
+    fn a_number_matches_in_any_color_unless_colors_are_strict()
+    {
+        let hands: &[&[&str]] = &[&["blue 5", "green 1", "wild"], &["green 3", "green 4", "yellow 6"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        assert_eq!(game.legal_moves(), [Move::Play(0), Move::Play(2), Move::Draw]);
+        assert!(game.play(0).is_ok());
+
+        let strict = GameConfig { strict_color_matching: true, ..GameConfig::default() };
+        let mut game = scripted(strict, "red 5", hands, &["green 5"]);
+        assert_eq!(game.legal_moves(), [Move::Play(2), Move::Draw]);
+        let (attempted, top) = (game.players()[0].cards()[0], game.top_card());
+        assert_eq!(game.play(0), Err(GameError::CardUnplayable { attempted, top }));
+        assert!(!game.draw_one().unwrap().playable);
+    }
+
+    #[test]
+    fn a_symbol_matches_in_any_color_unless_colors_are_strict()
+    {
+        // Seat 0 skips seat 1 and goes again with a Red Skip on top
+        let hands: &[&[&str]] = &[&["red skip", "blue skip", "green 1"], &["green 3", "green 4", "green 5"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        game.play(0).unwrap();
+        assert_eq!(game.legal_moves(), [Move::Play(0), Move::Draw]);
+        assert!(game.play(0).is_ok());
+
+        let strict = GameConfig { strict_color_matching: true, ..GameConfig::default() };
+        let mut game = scripted(strict, "red 5", hands, &[]);
+        game.play(0).unwrap();
+        assert_eq!(game.legal_moves(), [Move::Draw]);
+    }
+
+    #[test]
+    fn a_turned_down_play_says_why()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["blue 2", "red 1"], &["green 3", "green 4"]], &[]);
+        let error = game.play(0).unwrap_err();
+        let (attempted, top) = ("blue 2".parse().unwrap(), "red 5".parse().unwrap());
+        assert_eq!(error, GameError::CardUnplayable { attempted, top });
+        assert_eq!(error.to_string(), "A Blue 2 cannot be played on a Red 5");
+
+        let error = game.play(2).unwrap_err();
+        assert_eq!(error, GameError::InvalidCardIndex { given: 2, hand_size: 2 });
+        assert_eq!(error.to_string(), "Card index 2 is out of range for a hand of 2 cards");
+
+        // Neither changed anything
+        assert_eq!(game.current_seat(), 0);
+        assert_eq!(game.players()[0].cards(), cards(&["blue 2", "red 1"]));
+    }
+
+    #[test]
+    fn with_two_players_a_reverse_is_a_skip()
+    {
+        let hands: &[&[&str]] = &[&["red reverse", "red 2", "red 3"], &["blue 3", "blue 4", "blue 5"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        let outcome = game.play(0).unwrap();
+        assert!(outcome.reversed);
+        assert_eq!(outcome.skipped, [id(&game, 1)]);
+        assert_eq!(outcome.penalty, None);
+        assert_eq!(outcome.next_player, id(&game, 0));
+        assert_eq!(game.current_seat(), 0);
+        assert_eq!(game.direction(), Direction::CounterClockwise);
+    }
+
+    #[test]
+    fn back_to_back_reverses_with_two_players()
+    {
+        let hands: &[&[&str]] = &[&["red reverse", "blue reverse", "blue 2", "yellow 1"],
+            &["green 3", "green 4", "blue 5", "yellow 2"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        let (first, second) = (id(&game, 0), id(&game, 1));
+
+        // Each Reverse skips the other player and flips the direction, which only shows
+        // once there are more players
+        let outcome = game.play(0).unwrap();
+        assert_eq!((outcome.skipped, outcome.next_player), (vec![second], first));
+        assert_eq!(game.direction(), Direction::CounterClockwise);
+        let outcome = game.play(0).unwrap();
+        assert_eq!((outcome.skipped, outcome.next_player), (vec![second], first));
+        assert_eq!(game.direction(), Direction::Clockwise);
+
+        assert_eq!(game.play(0).unwrap().next_player, second);
+        let outcome = game.play(2).unwrap();
+        assert!(outcome.skipped.is_empty());
+        assert_eq!(outcome.next_player, first);
+        assert_eq!(game.players()[0].cards(), cards(&["yellow 1"]));
+    }
+
+    #[test]
+    fn a_draw_four_makes_the_next_player_draw_and_skips_them()
+    {
+        let hands: &[&[&str]] = &[&["+4", "red 2"], &["blue 3", "blue 4"], &["green 6", "green 7"]];
+        let drawn = ["yellow 1", "yellow 2", "yellow 3", "yellow 4"];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &drawn);
+        let outcome = game.play(0).unwrap();
+        assert!(outcome.awaiting_wild_color);
+        assert!(outcome.skipped.is_empty());
+        assert_eq!(outcome.penalty, None);
+
+        // The penalty comes with the color
+        let outcome = game.set_wildcard_color(Color::Green).unwrap().unwrap();
+        assert!(!outcome.awaiting_wild_color);
+        assert!(!outcome.reversed);
+        assert_eq!(outcome.skipped, [id(&game, 1)]);
+        assert_eq!(outcome.penalty, Some((id(&game, 1), cards(&drawn))));
+        assert_eq!(outcome.next_player, id(&game, 2));
+        assert_eq!(game.players()[1].number_of_cards(), 6);
+        assert_eq!(game.current_seat(), 2);
+    }
+
+    #[test]
+    fn a_playable_drawn_card_can_be_played()
+    {
+        let hands: &[&[&str]] = &[&["blue 1", "blue 2"], &["green 3", "green 4"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["red 9"]);
+        let drawn = game.draw_one().unwrap();
+        assert_eq!(drawn.card, "red 9".parse().unwrap());
+        assert!(drawn.playable);
+        assert!(drawn.auto_played.is_none());
+        assert_eq!(game.pending_action(), Some(PendingAction::PlayOrKeepDrawnCard));
+        assert_eq!(game.legal_moves(), [Move::PlayDrawn, Move::KeepDrawn]);
+        assert_eq!(game.current_seat(), 0);
+
+        let outcome = game.play_drawn().unwrap();
+        assert_eq!(outcome.card, drawn.card);
+        assert_eq!(game.top_card(), drawn.card);
+        assert_eq!(game.players()[0].cards(), cards(&["blue 1", "blue 2"]));
+        assert_eq!(game.current_seat(), 1);
+    }
+
+    #[test]
+    fn a_playable_drawn_card_can_be_kept()
+    {
+        let hands: &[&[&str]] = &[&["blue 1", "blue 2"], &["green 3", "green 4"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["red 9"]);
+        game.draw_one().unwrap();
+        assert_eq!(game.keep_drawn(), Ok(id(&game, 1)));
+        assert_eq!(game.top_card(), "red 5".parse().unwrap());
+        assert_eq!(game.players()[0].cards(), cards(&["blue 1", "blue 2", "red 9"]));
+        assert_eq!(game.pending_action(), None);
+        assert_eq!(game.play_drawn().err(), Some(GameError::NoDrawnCard));
+    }
+
+    #[test]
+    fn an_unplayable_drawn_card_ends_the_turn()
+    {
+        let hands: &[&[&str]] = &[&["blue 1", "blue 2"], &["green 3", "green 4"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["yellow 9"]);
+        assert!(!game.draw_one().unwrap().playable);
+        assert_eq!(game.pending_action(), None);
+        assert_eq!(game.current_seat(), 1);
+    }
+
+    #[test]
+    fn drawing_until_playable_stops_at_the_limit()
+    {
+        let config = GameConfig { draw_until_playable: true, ..GameConfig::default() };
+        let hands: &[&[&str]] = &[&["blue 1", "blue 2"], &["green 3", "green 4"]];
+        let mut game = scripted(config.clone(), "red 5", hands, &["yellow 9", "yellow 8", "red 7"]);
+        assert_eq!(game.draw_until_playable(Some(2)).unwrap(), DrawUntilOutcome { cards_drawn: 2, found: None });
+        assert_eq!(game.players()[0].number_of_cards(), 4);
+        assert_eq!(game.current_seat(), 1);
+
+        // A limit of 0 still costs a card
+        let mut game = scripted(config, "red 5", hands, &["yellow 9"]);
+        assert_eq!(game.draw_until_playable(Some(0)).unwrap(), DrawUntilOutcome { cards_drawn: 1, found: None });
+        assert_eq!(game.players()[0].number_of_cards(), 3);
+        assert_eq!(game.current_seat(), 1);
+    }
+
+    #[test]
+    fn a_drawn_wildcard_still_needs_a_color()
+    {
+        let hands: &[&[&str]] = &[&["blue 1", "blue 2"], &["green 3", "green 4"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["wild"]);
+        assert!(game.draw_one().unwrap().playable);
+        assert!(game.play_drawn().unwrap().awaiting_wild_color);
+        assert_eq!(game.pending_action(), Some(PendingAction::AwaitingWildColor));
+        assert_eq!(game.current_seat(), 0);
+
+        game.set_wildcard_color(Color::Green).unwrap();
+        assert_eq!(game.active_color(), Some(Color::Green));
+        assert_eq!(game.current_seat(), 1);
+    }
+
+    #[test]
+    fn drawn_cards_can_be_played_straight_away()
+    {
+        let config = GameConfig { auto_play_drawn_cards: true, ..GameConfig::default() };
+        let hands: &[&[&str]] = &[&["blue 1", "blue 2"], &["green 3", "green 4"]];
+        let mut game = scripted(config, "red 5", hands, &["red 9"]);
+        let drawn = game.draw_one().unwrap();
+        assert_eq!(drawn.auto_played.map(|outcome| outcome.card), Some(drawn.card));
+        assert_eq!(game.top_card(), drawn.card);
+        assert_eq!(game.pending_action(), None);
+        assert_eq!(game.current_seat(), 1);
+    }
+
+    #[test]
+    fn indices_follow_the_sorted_hand()
+    {
+        let hands: &[&[&str]] = &[&["+4", "blue 2", "red skip", "red 3", "wild", "blue 1"],
+            &["green 3", "green 4", "green 6", "green 7", "green 8", "green 9"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["green 5"]);
+        game.sort_hand().unwrap();
+        assert_eq!(game.players()[0].cards(), cards(&["red 3", "red skip", "blue 1", "blue 2", "wild", "+4"]));
+        assert_eq!(game.legal_moves(), [Move::Play(0), Move::Play(1), Move::Play(4), Move::Play(5), Move::Draw]);
+
+        let outcome = game.play(1).unwrap();
+        assert_eq!(outcome.card, "red skip".parse().unwrap());
+        assert_eq!(game.players()[0].cards(), cards(&["red 3", "blue 1", "blue 2", "wild", "+4"]));
+
+        // Not once a drawn card is waiting, since that would move it. The skip brought the
+        // turn back, and then a green 3 goes on the red 3
+        game.play(0).unwrap();
+        game.play(0).unwrap();
+        assert!(game.draw_one().unwrap().playable);
+        assert_eq!(game.sort_hand(), Err(GameError::ActionPending(PendingAction::PlayOrKeepDrawnCard)));
+    }
+
+    #[test]
+    fn set_wildcard_color_needs_a_wild_on_top()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1", "blue 2"], &["green 3", "green 4"]], &[]);
+        assert!(!game.needs_wild_color());
+        assert_eq!(game.set_wildcard_color(Color::Blue), Err(GameError::TopCardNotWild));
+        assert_eq!(game.active_color(), Some(Color::Red));
+        assert_eq!(game.current_seat(), 0);
+    }
+
+    #[test]
+    fn a_starting_wildcard_waits_for_the_first_players_color()
+    {
+        let mut game = scripted(GameConfig::default(), "wild", &[&["red 1", "blue 2"], &["green 3", "green 4"]], &[]);
+        assert!(game.needs_wild_color());
+        assert_eq!(game.pending_action(), Some(PendingAction::AwaitingWildColor));
+        assert_eq!(game.play(0), Err(GameError::ActionPending(PendingAction::AwaitingWildColor)));
+        assert_eq!(game.draw_one().err(), Some(GameError::ActionPending(PendingAction::AwaitingWildColor)));
+        assert_eq!(game.next_turn(), Err(GameError::ActionPending(PendingAction::AwaitingWildColor)));
+        assert_eq!(game.players()[0].number_of_cards(), 2);
+
+        assert_eq!(game.set_wildcard_color(Color::Blue), Ok(None));
+        assert!(!game.needs_wild_color());
+        assert_eq!(game.active_color(), Some(Color::Blue));
+        assert_eq!(game.current_seat(), 0);
+    }
+
+    #[test]
+    fn a_wildcard_played_mid_game_holds_up_the_turn_until_a_color_is_chosen()
+    {
+        let hands: &[&[&str]] = &[&["wild", "blue 2", "red 1"], &["green 3", "green 4", "blue 9"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["yellow 7"]);
+        let outcome = game.play(0).unwrap();
+        assert!(outcome.awaiting_wild_color);
+        assert_eq!(game.pending_action(), Some(PendingAction::AwaitingWildColor));
+
+        let pending = Some(GameError::ActionPending(PendingAction::AwaitingWildColor));
+        assert_eq!(game.play(0).err(), pending);
+        assert_eq!(game.draw_one().err(), pending);
+        assert_eq!(game.next_turn().err(), pending);
+        assert_eq!(game.current_seat(), 0);
+        assert_eq!(game.players()[0].cards(), cards(&["blue 2", "red 1"]));
+
+        let outcome = game.set_wildcard_color(Color::Blue).unwrap().unwrap();
+        assert_eq!(outcome.next_player, id(&game, 1));
+        assert_eq!(game.pending_action(), None);
+        assert_eq!(game.legal_moves(), [Move::Play(2), Move::Draw]);
+    }
+
+    #[test]
+    fn a_number_card_goes_on_a_wildcard_in_the_chosen_color()
+    {
+        let hands: &[&[&str]] = &[&["wild", "green 2", "blue 3"], &["red 1", "blue 7", "yellow 8"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        game.play_wild(0, Color::Blue).unwrap();
+
+        // The wildcard itself stays colorless, only the active color changes
+        assert_eq!(game.top_card(), Card::wildcard());
+        assert_eq!(game.top_card().color, None);
+        assert_eq!(game.active_color(), Some(Color::Blue));
+
+        let (attempted, top) = (game.players()[1].cards()[0], game.top_card());
+        assert_eq!(game.play(0), Err(GameError::CardUnplayable { attempted, top }));
+        assert_eq!(game.legal_moves(), [Move::Play(1), Move::Draw]);
+        game.play(1).unwrap();
+        assert_eq!(game.active_color(), Some(Color::Blue));
+    }
+
+    #[test]
+    fn a_chosen_color_cant_be_changed()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["wild", "blue 2"], &["green 3", "green 4"]], &[]);
+        game.play(0).unwrap();
+        let outcome = game.set_wildcard_color(Color::Green).unwrap().unwrap();
+        assert_eq!(outcome.next_player, id(&game, 1));
+
+        assert_eq!(game.set_wildcard_color(Color::Blue), Err(GameError::NoWildColorPending));
+        assert_eq!(game.seat(1).unwrap().set_wildcard_color(Color::Blue), Err(GameError::NoWildColorPending));
+        assert_eq!(game.active_color(), Some(Color::Green));
+        assert_eq!(game.current_seat(), 1);
+    }
+
+    #[test]
+    fn choosing_a_color_doesnt_skip_a_drawn_card_decision()
+    {
+        let mut game = scripted(GameConfig::default(), "wild", &[&["blue 1", "blue 2"], &["green 3", "green 4"]],
+            &["red 9"]);
+        game.set_wildcard_color(Color::Red).unwrap();
+        assert!(game.draw_one().unwrap().playable);
+
+        assert_eq!(game.set_wildcard_color(Color::Blue), Err(GameError::NoWildColorPending));
+        assert_eq!(game.pending_action(), Some(PendingAction::PlayOrKeepDrawnCard));
+        assert_eq!(game.active_color(), Some(Color::Red));
+        assert!(game.play_drawn().is_ok());
+    }
+
+    #[test]
+    fn no_color_can_be_chosen_once_the_game_is_over()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["wild"], &["green 3"]], &[]);
+        let outcome = game.play(0).unwrap();
+        assert_eq!(outcome.winner, Some(id(&game, 0)));
+        assert_eq!(game.set_wildcard_color(Color::Blue), Err(GameError::GameOver));
+    }
+
+    fn jump_in_config() -> GameConfig
+    {
+        GameConfig { jump_in: true, ..GameConfig::default() }
+    }
+
+    #[test]
+    fn jumping_in_with_the_last_card_wins()
+    {
+        let mut game = scripted(jump_in_config(), "red 3",
+            &[&["red 5", "green 7"], &["blue 9", "blue 8"], &["red 5", "red 5"]], &[]);
+        game.play(0).unwrap();
+
+        let outcome = game.jump_in(2, 0).unwrap();
+        assert_eq!(outcome.interrupted, id(&game, 1));
+        assert_eq!(outcome.play.next_player, id(&game, 0));
+        assert_eq!(game.current_seat(), 0);
+
+        let outcome = game.jump_in(2, 0).unwrap();
+        assert_eq!(outcome.interrupted, id(&game, 0));
+        assert_eq!(outcome.play.winner, Some(id(&game, 2)));
+        assert_eq!(game.winner().map(Player::id), Some(id(&game, 2)));
+    }
+
+    #[test]
+    fn a_near_match_cant_jump_in()
+    {
+        let mut game = scripted(jump_in_config(), "red 3",
+            &[&["red 5", "green 7"], &["blue 5", "blue 8"], &["yellow 1", "yellow 2"]], &[]);
+        game.play(0).unwrap();
+        let events = game.events().len();
+
+        let near_miss = Card::new(CardType::Number(Digit::new(5).unwrap()), Color::Blue);
+        let top = game.top_card();
+        assert_eq!(game.jump_in(1, 0), Err(GameError::NotIdentical { attempted: near_miss, top }));
+        assert_eq!(game.jump_in(2, 5), Err(GameError::InvalidCardIndex { given: 5, hand_size: 2 }));
+        assert_eq!(game.current_seat(), 1);
+        assert_eq!(game.events().len(), events);
+    }
+
+    // Turns down any card played onto an identical one
+    #[derive(Debug, Clone)]
+    struct NoPairs;
+
+    impl RuleSet for NoPairs
+    {
+        fn name(&self) -> &str
+        {
+            "No pairs"
+        }
+
+        fn is_playable(&self, card: Card, top: Card, _active_color: Option<Color>) -> Option<bool>
+        {
+            (card == top).then_some(false)
+        }
+
+        fn clone_box(&self) -> Box<dyn RuleSet>
+        {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn a_jump_in_the_rules_turn_down_changes_nothing()
+    {
+        let mut lobby = Lobby::with_players(["P0", "P1", "P2"]).unwrap();
+        lobby.set_config(jump_in_config());
+        lobby.add_rule(NoPairs);
+        let mut game = deal(lobby, "red 3", &[&["red 5", "green 7"], &["blue 9", "blue 8"], &["red 5", "red 6"]], &[]);
+        game.play(0).unwrap();
+        let events = game.events().len();
+
+        let error = game.jump_in(2, 0).unwrap_err();
+        assert!(matches!(error, GameError::CardUnplayable { .. }), "{:?}", error);
+        assert_eq!(game.current_seat(), 1);
+        assert_eq!(game.players()[2].cards().len(), 2);
+        assert_eq!(game.events().len(), events);
+    }
+
+    #[test]
+    fn jumping_in_is_too_late_once_someone_draws()
+    {
+        let mut game = scripted(jump_in_config(), "red 3",
+            &[&["red 5", "green 7"], &["blue 9", "blue 8"], &["red 5", "red 6"]], &["yellow 1"]);
+        game.play(0).unwrap();
+        game.draw_one().unwrap();
+        assert_eq!(game.jump_in(2, 0), Err(GameError::JumpInTooLate));
+        assert_eq!(game.current_seat(), 2);
+    }
+
+    #[test]
+    fn forgetting_to_call_uno_can_be_caught_until_the_next_action()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1", "red 2"], &["blue 3", "blue 4"]],
+            &["green 9", "green 8"]);
+        game.play(0).unwrap();
+        assert!(!game.players()[0].has_called_uno());
+
+        let outcome = game.catch_failure_to_call(1, 0).unwrap();
+        assert!(outcome.caught);
+        assert_eq!(outcome.penalized, id(&game, 0));
+        assert_eq!(outcome.cards_drawn, cards(&["green 9", "green 8"]));
+        assert_eq!(game.players()[0].number_of_cards(), 3);
+    }
+
+    #[test]
+    fn the_catch_window_closes_with_the_next_play()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1", "red 2"], &["red 3", "blue 4"]], &[]);
+        game.play(0).unwrap();
+        assert_eq!(game.current_seat(), 1);
+        game.play(0).unwrap();
+        assert_eq!(game.catch_failure_to_call(1, 0), Err(GameError::FalseUnoAccusation));
+        assert_eq!(game.players()[0].number_of_cards(), 1);
+    }
+
+    #[test]
+    fn the_catch_window_closes_with_the_next_draw()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1", "red 2"], &["blue 3", "blue 4"]],
+            &["yellow 7"]);
+        game.play(0).unwrap();
+        game.draw_one().unwrap();
+        assert_eq!(game.catch_failure_to_call(1, 0), Err(GameError::FalseUnoAccusation));
+    }
+
+    #[test]
+    fn calling_uno_in_time_is_safe()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1", "red 2"], &["blue 3", "blue 4"]], &[]);
+        game.call_uno(0).unwrap();
+        game.play(0).unwrap();
+        assert!(game.players()[0].has_called_uno());
+        assert_eq!(game.catch_failure_to_call(1, 0), Err(GameError::FalseUnoAccusation));
+
+        // A late call still counts as long as nobody has caught them yet
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1", "red 2"], &["blue 3", "blue 4"]], &[]);
+        game.play(0).unwrap();
+        game.call_uno(0).unwrap();
+        assert_eq!(game.catch_failure_to_call(1, 0), Err(GameError::FalseUnoAccusation));
+    }
+
+    #[test]
+    fn uno_can_only_be_called_with_one_or_two_cards()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5",
+            &[&["red 1", "red 2", "red 3"], &["blue 3", "blue 4", "blue 5"]], &[]);
+        assert_eq!(game.call_uno(0), Err(GameError::CannotCallUno));
+        game.play(0).unwrap();
+        // Two cards only count on the player's own turn
+        assert_eq!(game.call_uno(0), Err(GameError::CannotCallUno));
+        assert_eq!(game.call_uno(5), Err(GameError::InvalidSeat(5)));
+    }
+
+    #[test]
+    fn a_false_accusation_can_cost_the_accuser()
+    {
+        let config = GameConfig { penalize_false_uno_accusations: true, ..GameConfig::default() };
+        let hands: &[&[&str]] = &[&["red 1", "red 2"], &["blue 3", "blue 4"]];
+        let mut game = scripted(config, "red 5", hands, &["green 1", "green 2"]);
+        let outcome = game.catch_failure_to_call(1, 0).unwrap();
+        assert!(!outcome.caught);
+        assert_eq!(outcome.penalized, id(&game, 1));
+        assert_eq!(game.players()[1].number_of_cards(), 4);
+    }
+
+    #[test]
+    fn uno_cant_be_called_once_the_game_is_over()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1"], &["blue 3"]], &[]);
+        game.play(0).unwrap();
+        assert_eq!(game.call_uno(1), Err(GameError::GameOver));
+        assert_eq!(game.catch_failure_to_call(0, 1), Err(GameError::GameOver));
+    }
+
+    #[test]
+    fn skipped_players_still_use_up_a_turn_number()
+    {
+        let hands: &[&[&str]] = &[&["red skip", "red 1", "red 2"], &["green 3", "green 4", "blue 5"],
+            &["red +2", "green 6", "green 7"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["yellow 1", "yellow 2", "blue 9"]);
+        assert_eq!((game.turn_number(), game.action_number()), (1, 0));
+
+        // Seat 1 is skipped on turn 2, and seat 2 plays on turn 3
+        game.play(0).unwrap();
+        assert_eq!((game.turn_number(), game.action_number()), (3, 1));
+        let skipped = RecordedEvent { turn: 2, action: 1, event: GameEvent::TurnSkipped { player: id(&game, 1) } };
+        assert!(game.events().contains(&skipped));
+
+        // Seat 0 draws the penalty on turn 4, which counts as an action, and loses the
+        // turn, so seat 1 plays on turn 5
+        game.play(0).unwrap();
+        assert_eq!((game.turn_number(), game.action_number()), (5, 3));
+        assert_eq!(game.current_seat(), 1);
+        let penalised = RecordedEvent { turn: 4, action: 3, event: GameEvent::TurnSkipped { player: id(&game, 0) } };
+        assert!(game.events().contains(&penalised));
+
+        game.draw_one().unwrap();
+        assert_eq!((game.turn_number(), game.action_number()), (6, 4));
+    }
+
+    // P1 twice draws rather than play on red, and is left holding nothing red
+    fn red_avoided(config: GameConfig) -> Game
+    {
+        let hands: &[&[&str]] = &[&["red 1", "red 2", "red 7", "red 4"], &["green 3", "green 4", "blue 5", "blue 6"]];
+        let mut game = scripted(config, "red 5", hands, &["blue 9", "green 9", "red 8"]);
+        for _ in 0..2 {
+            game.play(0).unwrap();
+            assert!(!game.draw_one().unwrap().playable);
+        }
+        game
+    }
+
+    #[test]
+    fn declining_to_play_on_a_color_marks_it_as_likely_void()
+    {
+        let mut game = red_avoided(GameConfig::default());
+        let profile = game.opponent_model(1).unwrap();
+        assert_eq!(profile.likely_void_colors, [Color::Red]);
+        assert_eq!(profile.hand_sizes, [5, 6]);
+        assert!(profile.hand_size_trend() > 0);
+        assert!(game.opponent_model(0).unwrap().likely_void_colors.is_empty());
+
+        // Playing a red card shows they had one after all
+        game.play(1).unwrap();
+        assert!(game.draw_one().unwrap().playable);
+        game.play_drawn().unwrap();
+        assert!(game.opponent_model(1).unwrap().likely_void_colors.is_empty());
+    }
+
+    #[test]
+    fn likely_void_colors_go_with_a_swapped_hand()
+    {
+        let mut game = red_avoided(GameConfig { seven_zero: true, ..GameConfig::default() });
+        game.play(0).unwrap();
+        game.choose_swap_target(1).unwrap();
+        assert_eq!(game.opponent_model(0).unwrap().likely_void_colors, [Color::Red]);
+        assert!(game.opponent_model(1).unwrap().likely_void_colors.is_empty());
+    }
+
+    fn turn_order(game: &Game) -> Vec<&str>
+    {
+        game.turn_order().map(|player| player.name().as_str()).collect()
+    }
+
+    #[test]
+    fn the_turn_order_starts_with_the_current_player()
+    {
+        let hands: &[&[&str]] = &[&["red 1", "red 2"], &["red 3", "red 4"], &["red 6", "red 7"], &["red 8", "red 9"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        assert_eq!(turn_order(&game), ["P0", "P1", "P2", "P3"]);
+        game.play(0).unwrap();
+        game.play(0).unwrap();
+        assert_eq!(turn_order(&game), ["P2", "P3", "P0", "P1"]);
+        assert_eq!(game.to_string(), "[P2] -> P3 -> P0 -> P1 ↻ clockwise");
+
+        game.reverse();
+        assert_eq!(turn_order(&game), ["P2", "P1", "P0", "P3"]);
+        assert_eq!(game.to_string(), "[P2] -> P1 -> P0 -> P3 ↺ counter-clockwise");
+    }
+
+    #[test]
+    fn the_turn_order_of_two_players()
+    {
+        let hands: &[&[&str]] = &[&["red 1", "red 2"], &["red 3", "red 4"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        game.play(0).unwrap();
+        assert_eq!(turn_order(&game), ["P1", "P0"]);
+        assert_eq!(game.to_string(), "[P1] -> P0");
+
+        // With two players the direction makes no difference
+        game.reverse();
+        assert_eq!(turn_order(&game), ["P1", "P0"]);
+        assert_eq!(game.to_string(), "[P1] -> P0");
+    }
+
+    #[test]
+    fn only_the_current_seat_can_take_a_turn()
+    {
+        let hands: &[&[&str]] = &[&["red 1", "red 2"], &["red 3", "wild"], &["green 6", "green 7"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &["red 9"]);
+        let before = game.save();
+        let mut waiting = game.seat(1).unwrap();
+        assert!(!waiting.is_my_turn());
+        let not_yours = Some(GameError::NotYourTurn { seat: 1 });
+        assert_eq!(waiting.play(0).err(), not_yours);
+        assert_eq!(waiting.play_wild(1, Color::Blue).err(), not_yours);
+        assert_eq!(waiting.draw().err(), not_yours);
+        assert_eq!(waiting.keep_drawn().err(), not_yours);
+        assert_eq!(waiting.accept_penalty().err(), not_yours);
+        assert_eq!(game.save(), before);
+
+        assert_eq!(game.seat(3).err(), Some(GameError::InvalidSeat(3)));
+
+        let mut current = game.seat(0).unwrap();
+        assert!(current.is_my_turn());
+        assert_eq!(current.play(0).map(|outcome| outcome.next_player).ok(), Some(id(&game, 1)));
+
+        // Calling Uno on the last card doesn't have to wait for a turn
+        let mut done = game.seat(0).unwrap();
+        assert_eq!(done.play(0).err(), Some(GameError::NotYourTurn { seat: 0 }));
+        assert_eq!(done.call_uno(), Ok(()));
+    }
+
+    fn play_identical() -> GameConfig
+    {
+        GameConfig { play_identical_together: true, ..GameConfig::default() }
+    }
+
+    #[test]
+    fn two_skips_skip_two_players()
+    {
+        let hands: &[&[&str]] = &[&["red skip", "green 1", "red skip"], &["blue 1", "blue 2", "blue 3"],
+            &["green 6", "green 7", "green 8"], &["yellow 1", "yellow 2", "yellow 3"]];
+        let mut game = scripted(play_identical(), "red 5", hands, &[]);
+        let outcome = game.play_many(&[0, 2]).unwrap();
+        assert_eq!(outcome.copies, 2);
+        assert_eq!(outcome.skipped, [id(&game, 1), id(&game, 2)]);
+        assert_eq!(outcome.next_player, id(&game, 3));
+        assert_eq!(game.players()[0].cards(), cards(&["green 1"]));
+        assert_eq!(game.discarded(), cards(&["red 5", "red skip", "red skip"]));
+    }
+
+    #[test]
+    fn two_draw_twos_make_the_next_player_draw_four()
+    {
+        let hands: &[&[&str]] = &[&["blue +2", "blue +2", "green 1"], &["blue 1", "blue 2", "blue 3"],
+            &["green 6", "green 7", "green 8"]];
+        let drawn = ["yellow 1", "yellow 2", "yellow 3", "yellow 4"];
+        let mut game = scripted(play_identical(), "blue 5", hands, &drawn);
+        let outcome = game.play_many(&[1, 0]).unwrap();
+        assert_eq!(outcome.penalty, Some((id(&game, 1), cards(&drawn))));
+        assert_eq!(outcome.skipped, [id(&game, 1)]);
+        assert_eq!(outcome.next_player, id(&game, 2));
+    }
+
+    #[test]
+    fn two_reverses_cancel_out()
+    {
+        let hands: &[&[&str]] = &[&["red reverse", "red reverse", "green 1"], &["blue 1", "blue 2", "blue 3"],
+            &["green 6", "green 7", "green 8"]];
+        let mut game = scripted(play_identical(), "red 5", hands, &[]);
+        let outcome = game.play_many(&[0, 1]).unwrap();
+        assert!(!outcome.reversed);
+        assert_eq!(game.direction(), Direction::Clockwise);
+        assert_eq!(outcome.next_player, id(&game, 1));
+    }
+
+    #[test]
+    fn only_identical_cards_are_played_together()
+    {
+        let hands: &[&[&str]] = &[&["green 4", "green 4", "blue 4", "wild", "wild"],
+            &["blue 1", "blue 2", "blue 3", "blue 5", "blue 6"]];
+        let mut game = scripted(play_identical(), "green 9", hands, &[]);
+        let (green, blue) = (game.players()[0].cards()[0], game.players()[0].cards()[2]);
+        assert_eq!(game.play_many(&[0, 2]), Err(GameError::CardsNotIdentical { first: green, other: blue }));
+        assert_eq!(game.play_many(&[0, 0]), Err(GameError::DuplicateCardIndex(0)));
+        assert_eq!(game.play_many(&[3, 4]), Err(GameError::CannotPlayTogether(Card::wildcard())));
+        assert_eq!(game.play_many(&[0, 5]), Err(GameError::InvalidCardIndex { given: 5, hand_size: 5 }));
+        assert_eq!(game.play_many(&[]), Err(GameError::NoCardsSelected));
+        let top = game.top_card();
+        assert_eq!(game.play_many(&[2]), Err(GameError::CardUnplayable { attempted: blue, top }));
+        assert_eq!(game.players()[0].number_of_cards(), 5);
+
+        assert!(game.play_many(&[1, 0]).is_ok());
+        assert_eq!(game.players()[0].cards(), cards(&["blue 4", "wild", "wild"]));
+
+        let mut game = scripted(GameConfig::default(), "green 9", hands, &[]);
+        assert_eq!(game.play_many(&[0, 1]), Err(GameError::PlayManyDisabled));
+    }
+
+    #[test]
+    fn the_current_player_is_written_first_after_a_reverse_card()
+    {
+        let hands: &[&[&str]] = &[&["red reverse", "red 1"], &["red 2", "red 3"], &["red reverse", "red 4"],
+            &["red 6", "red 7"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        game.play(0).unwrap();
+        assert_eq!(game.to_string(), "[P3] -> P2 -> P1 -> P0 ↺ counter-clockwise");
+        game.play(0).unwrap();
+        game.play(0).unwrap();
+        assert_eq!(game.to_string(), "[P3] -> P0 -> P1 -> P2 ↻ clockwise");
+
+        // With two players there's no direction to show
+        let hands: &[&[&str]] = &[&["red reverse", "red 1"], &["red 2", "red 3"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        game.play(0).unwrap();
+        assert_eq!(game.to_string(), "[P0] -> P1");
+    }
+
+    #[test]
+    fn ids_stay_with_players_as_others_come_and_go()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5",
+            &[&["red 1", "red 2"], &["blue 3", "blue 4"], &["green 6", "green 7"], &["yellow 8", "yellow 9"]],
+            &["blue 1", "blue 2"]);
+        let ids: Vec<PlayerId> = (0..4).map(|seat| id(&game, seat)).collect();
+        let cat = ids[2];
+
+        let removed = game.remove_player(1).unwrap();
+        assert_eq!(removed.id, ids[1]);
+        assert!(game.player_by_id(ids[1]).is_none());
+        assert_eq!(game.seat_of(ids[1]), None);
+        assert_eq!(game.seat_of(cat), Some(1));
+        assert_eq!(game.player_by_id(cat).unwrap().cards(), cards(&["green 6", "green 7"]));
+
+        // A newcomer never gets an id that was used before, even one that's free again
+        let seat = game.add_player_mid_game("P1").unwrap();
+        let newcomer = id(&game, seat);
+        assert!(!ids.contains(&newcomer));
+        // They sit down right after the current player, moving everyone after them along
+        assert_eq!(seat, 1);
+        assert_eq!(game.seat_of(cat), Some(2));
+        assert_eq!(game.player_by_id(cat).unwrap().name(), "P2");
+
+        // The log says who by id, whatever seat they were in
+        let left: Vec<PlayerId> = game.events().iter()
+            .filter_map(|recorded| match recorded.event {
+                GameEvent::PlayerLeft { player } => Some(player),
+                _ => None
+            })
+            .collect();
+        assert_eq!(left, [ids[1]]);
+        let joined = RecordedEvent { turn: 1, action: 0, event: GameEvent::PlayerJoined { player: newcomer } };
+        assert!(game.events().contains(&joined));
+    }
+
+    #[test]
+    fn removing_the_current_player_passes_the_turn_on()
+    {
+        let hands: &[&[&str]] = &[&["red 1", "red 2"], &["blue 3", "blue 4"], &["green 5", "green 6"],
+            &["red 7", "red 8"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        let ids: Vec<PlayerId> = game.players().iter().map(Player::id).collect();
+
+        let removed = game.remove_player(0).unwrap();
+        assert!(removed.was_current);
+        assert_eq!(removed.cards, cards(&["red 1", "red 2"]));
+        assert_eq!(game.player().id(), ids[1]);
+        // Everyone keeps their id, and the seats after the removed one shift down
+        assert_eq!(game.seat_of(ids[3]), Some(2));
+        assert_eq!(game.player_by_id(ids[2]).unwrap().cards(), cards(&["green 5", "green 6"]));
+        assert_eq!(game.seat_of(ids[0]), None);
+    }
+
+    #[test]
+    fn removing_a_player_going_counter_clockwise()
+    {
+        let hands: &[&[&str]] = &[&["red 1", "red 2"], &["blue 3", "blue 4"], &["green 5", "green 6"],
+            &["red 7", "red 8"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        let ids: Vec<PlayerId> = game.players().iter().map(Player::id).collect();
+        game.reverse();
+        game.next_turn().unwrap();
+        assert_eq!(game.player().id(), ids[3]);
+
+        game.remove_player(3).unwrap();
+        assert_eq!(game.player().id(), ids[2]);
+        game.remove_player(0).unwrap();
+        assert_eq!(game.player().id(), ids[2]);
+        assert_eq!(game.turn_order().map(Player::id).collect::<Vec<_>>(), [ids[2], ids[1]]);
+    }
+
+    #[test]
+    fn down_to_two_players_a_reverse_skips()
+    {
+        let hands: &[&[&str]] = &[&["red reverse", "red 2"], &["blue 3", "blue 4"], &["green 5", "green 6"]];
+        let mut game = scripted(GameConfig::default(), "red 5", hands, &[]);
+        let first = id(&game, 0);
+        game.remove_player(2).unwrap();
+
+        let outcome = game.play(0).unwrap();
+        assert_eq!(outcome.skipped, [id(&game, 1)]);
+        assert_eq!(outcome.next_player, first);
+    }
+
+    #[test]
+    fn the_last_player_standing_wins_with_nothing_left_pending()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["wild", "red 2"], &["blue 3", "blue 4"]], &[]);
+        game.play(0).unwrap();
+        assert_eq!(game.pending_action(), Some(PendingAction::AwaitingWildColor));
+
+        let removed = game.remove_player(0).unwrap();
+        assert_eq!(removed.winner, Some(id(&game, 0)));
+        assert_eq!(game.end(), Some(GameEnd::LastPlayerStanding(id(&game, 0))));
+        assert_eq!(game.pending_action(), None);
+        assert_eq!(game.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn drawing_for_a_seat_that_doesnt_exist_is_an_error()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1"], &["blue 3"]], &["green 2"]);
+        assert_eq!(game.draw_multiple_for(2, 1), Err(GameError::InvalidSeat(2)));
+        assert_eq!(game.draw_multiple_for(1, 1), Ok(cards(&["green 2"])));
+    }
+
+    // Random calls with random arguments, half of them legal moves and the rest
+    // anything at all, on games with random house rules. Debug builds check the
+    // invariants after every change, so this also shows the game never goes bad
+    #[test]
+    fn random_calls_never_panic()
+    {
+        let colors = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+        let mut rng = SmallRng::seed_from_u64(320);
+        for seed in 0..200 {
+            let mut config = GameConfig { max_turns: Some(150), ..GameConfig::default() };
+            for rule in RULE_NAMES {
+                config.set_rule(rule, rng.gen_bool(0.5)).unwrap();
+            }
+            let number_of_players = rng.gen_range(2..=5);
+            let mut lobby = Lobby::with_players((0..number_of_players).map(|seat| format!("P{}", seat))).unwrap();
+            lobby.set_config(config);
+            let mut game = lobby.start_with_seed(seed).unwrap();
+
+            for _ in 0..400 {
+                let seat = rng.gen_range(0..7);
+                let other = rng.gen_range(0..7);
+                let index = rng.gen_range(0..12);
+                let color = colors[rng.gen_range(0..4)];
+                assert_eq!(game.check_invariants(), Ok(()));
+                let legal = game.legal_moves();
+                if !legal.is_empty() && rng.gen_bool(0.5) {
+                    match legal[rng.gen_range(0..legal.len())] {
+                        Move::Play(index) => drop(game.play(index)),
+                        Move::Draw => drop(game.draw_one()),
+                        Move::PlayDrawn => drop(game.play_drawn()),
+                        Move::KeepDrawn => drop(game.keep_drawn()),
+                        Move::AcceptPenalty => drop(game.accept_penalty()),
+                        Move::ChallengeDrawFour => drop(game.challenge_draw_four())
+                    }
+                    continue;
+                }
+
+                match rng.gen_range(0..23) {
+                    0 => drop(game.play(index)),
+                    1 => drop(game.play_many(&[index, other, seat])),
+                    2 => drop(game.play_wild(index, color)),
+                    3 => drop(game.draw_one()),
+                    4 => drop(game.draw_until_playable(Some(index))),
+                    5 => drop(game.play_drawn()),
+                    6 => drop(game.play_drawn_wild(color)),
+                    7 => drop(game.keep_drawn()),
+                    8 => drop(game.accept_penalty()),
+                    9 => drop(game.jump_in(seat, index)),
+                    10 => drop(game.choose_swap_target(seat)),
+                    11 => drop(game.challenge_draw_four()),
+                    12 => drop(game.call_uno(seat)),
+                    13 => drop(game.catch_failure_to_call(seat, other)),
+                    14 => drop(game.set_wildcard_color(color)),
+                    15 => drop(game.sort_hand()),
+                    16 => drop(game.next_turn()),
+                    17 => drop(game.draw_multiple_for(seat, index as u8)),
+                    18 => drop((game.rotate_hands(), game.reverse())),
+                    19 if rng.gen_bool(0.1) => drop(game.remove_player(seat)),
+                    20 if rng.gen_bool(0.1) => drop(game.add_player_mid_game(&format!("Late {}", index))),
+                    21 => drop((game.view_for(seat), game.observe(seat), game.opponent_model(seat))),
+                    _ => drop((game.spectator_view(), game.end(), game.drawn_card(), game.to_string()))
+                }
+            }
+        }
+    }
+
+    // P0 has just played a red Draw Two, or a Draw Four that was made red, on P1, who
+    // holds a blue Draw Two, a Draw Four, a red Draw Two and a green 3
+    fn penalty_on_p1(config: GameConfig, draw_four: bool) -> Game
+    {
+        let hands: &[&[&str]] = &[&["red +2", "+4", "red 1", "red 9"], &["blue +2", "+4", "red +2", "green 3"],
+            &["yellow 1", "yellow 2", "yellow 3", "yellow 4"]];
+        let mut game = scripted(config, "red 5", hands, &[]);
+        if draw_four {
+            game.play_wild(1, Color::Red).unwrap();
+        } else {
+            game.play(0).unwrap();
+        }
+        game
+    }
+
+    fn stacking(policy: StackingPolicy) -> GameConfig
+    {
+        GameConfig { stacking: true, stacking_policy: policy, ..GameConfig::default() }
+    }
+
+    #[test]
+    fn same_kind_stacking()
+    {
+        let game = penalty_on_p1(stacking(StackingPolicy::SAME_KIND), false);
+        assert_eq!(game.pending_penalty(), 2);
+        assert_eq!(game.legal_moves(), [Move::Play(0), Move::Play(2), Move::AcceptPenalty]);
+
+        let mut game = penalty_on_p1(stacking(StackingPolicy::SAME_KIND), true);
+        assert_eq!(game.legal_moves(), [Move::Play(1), Move::AcceptPenalty]);
+        game.play_wild(1, Color::Blue).unwrap();
+        assert_eq!(game.pending_penalty(), 8);
+        assert_eq!(game.current_seat(), 2);
+    }
+
+    #[test]
+    fn a_chain_of_draw_twos_goes_around_the_table()
+    {
+        let hands: &[&[&str]] = &[&["red +2", "red 1", "red 2"], &["blue +2", "blue 1", "blue 2"],
+            &["green +2", "green 1", "green 2"]];
+        let drawn = ["yellow 1", "yellow 2", "yellow 3", "yellow 4", "yellow 5", "yellow 6"];
+        let mut game = scripted(stacking(StackingPolicy::SAME_KIND), "red 5", hands, &drawn);
+        for seat in 0..3 {
+            let outcome = game.play(0).unwrap();
+            assert_eq!(outcome.penalty, None);
+            assert_eq!(outcome.pending_penalty, 2 * (seat as u8 + 1));
+            assert_eq!(game.pending_action(), Some(PendingAction::DrawPenalty(2 * (seat as u8 + 1))));
+        }
+
+        // Back at the first player, who has no Draw Two left to pass it on with
+        assert_eq!(game.current_seat(), 0);
+        assert_eq!(game.legal_moves(), [Move::AcceptPenalty]);
+        let pending = Some(GameError::ActionPending(PendingAction::DrawPenalty(6)));
+        assert_eq!(game.play(0).err(), pending);
+        assert_eq!(game.draw_one().err(), pending);
+
+        let outcome = game.accept_penalty().unwrap();
+        assert_eq!(outcome.player, id(&game, 0));
+        assert_eq!(outcome.cards_drawn, cards(&drawn));
+        assert_eq!(outcome.next_player, id(&game, 1));
+        assert_eq!(game.pending_penalty(), 0);
+        assert_eq!(game.players()[0].number_of_cards(), 8);
+    }
+
+    #[test]
+    fn a_penalty_follows_the_direction_after_a_reverse()
+    {
+        let hands: &[&[&str]] = &[&["red reverse", "red 1", "red 2"], &["blue +2", "blue 1", "blue 2"],
+            &["red +2", "green 1", "green 2"]];
+        let drawn = ["yellow 1", "yellow 2", "yellow 3", "yellow 4"];
+        let mut game = scripted(stacking(StackingPolicy::SAME_KIND), "red 5", hands, &drawn);
+        game.play(0).unwrap();
+        assert_eq!(game.current_seat(), 2);
+
+        // Going counter clockwise the penalty goes from seat 2 to 1 and on to 0
+        game.play(0).unwrap();
+        assert_eq!(game.current_seat(), 1);
+        game.play(0).unwrap();
+        assert_eq!(game.current_seat(), 0);
+        assert_eq!(game.pending_penalty(), 4);
+
+        let outcome = game.accept_penalty().unwrap();
+        assert_eq!(outcome.cards_drawn, cards(&drawn));
+        assert_eq!(outcome.next_player, id(&game, 2));
+        assert_eq!(game.direction(), Direction::CounterClockwise);
+    }
+
+    #[test]
+    fn progressive_stacking()
+    {
+        let mut game = penalty_on_p1(stacking(StackingPolicy::PROGRESSIVE), false);
+        assert_eq!(game.legal_moves(), [Move::Play(0), Move::Play(1), Move::Play(2), Move::AcceptPenalty]);
+        game.play_wild(1, Color::Green).unwrap();
+        assert_eq!(game.pending_penalty(), 6);
+
+        let game = penalty_on_p1(stacking(StackingPolicy::PROGRESSIVE), true);
+        assert_eq!(game.legal_moves(), [Move::Play(1), Move::AcceptPenalty]);
+    }
+
+    #[test]
+    fn anything_goes_stacking()
+    {
+        let mut game = penalty_on_p1(stacking(StackingPolicy::ANYTHING_GOES), true);
+        assert_eq!(game.legal_moves(), [Move::Play(0), Move::Play(1), Move::Play(2), Move::AcceptPenalty]);
+        game.play(0).unwrap();
+        assert_eq!(game.pending_penalty(), 6);
+    }
+
+    #[test]
+    fn stacking_in_the_active_color_only()
+    {
+        let policy = StackingPolicy { allow_color_change: false, ..StackingPolicy::ANYTHING_GOES };
+        let mut game = penalty_on_p1(stacking(policy), false);
+        let (blue, top) = (game.players()[1].cards()[0], game.top_card());
+        assert_eq!(game.play(0), Err(GameError::CannotStack { attempted: blue, top }));
+        assert_eq!(game.legal_moves(), [Move::Play(1), Move::Play(2), Move::AcceptPenalty]);
+    }
+
+    #[test]
+    fn stacking_stops_at_the_maximum_penalty()
+    {
+        let policy = StackingPolicy { max_penalty: Some(4), ..StackingPolicy::SAME_KIND };
+        let mut game = penalty_on_p1(stacking(policy), false);
+        game.play(2).unwrap();
+        assert_eq!(game.pending_penalty(), 4);
+        assert_eq!(game.legal_moves(), [Move::AcceptPenalty]);
+
+        let mut game = penalty_on_p1(stacking(policy), true);
+        assert_eq!(game.play_wild(1, Color::Red), Err(GameError::PenaltyLimitReached { max_penalty: 4 }));
+    }
+
+    #[test]
+    fn nothing_stacks_on_a_challengeable_draw_four_without_stacking()
+    {
+        let config = GameConfig { draw_four_challenge: true, ..GameConfig::default() };
+        let mut game = penalty_on_p1(config, true);
+        assert_eq!(game.pending_penalty(), 4);
+        assert_eq!(game.legal_moves(), [Move::AcceptPenalty, Move::ChallengeDrawFour]);
+
+        let top = game.top_card();
+        assert_eq!(game.play_wild(1, Color::Blue), Err(GameError::CannotStack { attempted: Card::draw_four(), top }));
+        assert_eq!(game.pending_penalty(), 4);
+    }
+
+    #[test]
+    fn without_stacking_a_draw_two_is_drawn_straight_away()
+    {
+        let game = penalty_on_p1(GameConfig::default(), false);
+        assert_eq!(game.pending_penalty(), 0);
+        assert_eq!(game.current_seat(), 2);
+        assert_eq!(game.players()[1].number_of_cards(), 6);
+    }
+
+    #[test]
+    fn a_draw_four_can_be_challenged_in_a_determinized_game()
+    {
+        let config = GameConfig { draw_four_challenge: true, ..GameConfig::default() };
+        let game = penalty_on_p1(config, true);
+        let view = game.view_for(1).unwrap();
+        assert_eq!(view.table.challengeable_draw_four, Some((id(&game, 0), Some(Color::Red))));
+        assert_eq!(view.table.discarded, cards(&["red 5", "+4"]));
+
+        // The challenge comes out however the guess at the offender's hand says
+        let guilty = Game::determinize_with(&view, vec![cards(&["red 1", "red 9"])], Vec::new(), 0);
+        assert_eq!(guilty.legal_moves(), [Move::AcceptPenalty, Move::ChallengeDrawFour]);
+        assert!(guilty.clone().challenge_draw_four().unwrap().offender_guilty);
+        let innocent = Game::determinize_with(&view, vec![cards(&["blue 1", "green 9"])], Vec::new(), 0);
+        assert!(!innocent.clone().challenge_draw_four().unwrap().offender_guilty);
+    }
+
+    // Two games that only differ in what P1 holds and draws: P0 plays, then P1 draws
+    // a card they can't play
+    fn games_with_hidden_differences() -> (Game, Game)
+    {
+        let mut games = (
+            scripted(GameConfig::default(), "red 5", &[&["red 2", "blue 6"], &["green 7", "green 8"]], &["green 9"]),
+            scripted(GameConfig::default(), "red 5", &[&["red 2", "blue 6"], &["yellow 3", "+4"]], &["yellow 1"])
+        );
+        for game in [&mut games.0, &mut games.1] {
+            game.play(0).unwrap();
+            game.draw_one().unwrap();
+            assert_eq!(game.current_seat(), 0);
+        }
+        (games.0, games.1)
+    }
+
+    #[test]
+    fn views_dont_show_other_players_cards()
+    {
+        let (game, other) = games_with_hidden_differences();
+        assert_ne!(game.players()[1].cards(), other.players()[1].cards());
+
+        let view = game.view_for(0).unwrap();
+        assert_eq!(view.hand, cards(&["blue 6"]));
+        assert_eq!(view.table.players[1].number_of_cards, 3);
+        assert_eq!(view, other.view_for(0).unwrap());
+        assert_eq!(game.spectator_view(), other.spectator_view());
+
+        // Nor does P1's own view show P0's hand
+        let view = game.view_for(1).unwrap();
+        assert_eq!(view.hand, cards(&["green 7", "green 8", "green 9"]));
+        assert_eq!(view.new_cards, cards(&["green 9"]));
+        assert!(!format!("{:?}", view).contains(&format!("{:?}", cards(&["blue 6"])[0])));
+    }
+
+    #[test]
+    fn bot_observations_dont_show_other_players_cards()
+    {
+        let (game, other) = games_with_hidden_differences();
+        let observation = game.observe(0).unwrap();
+        assert_eq!(observation, other.observe(0).unwrap());
+        assert_eq!(observation.hand(), cards(&["blue 6"]));
+
+        // P1's draw is there, but not the card they drew
+        let kinds: Vec<ActionKind> = observation.recent_actions.iter().map(|record| record.kind).collect();
+        assert_eq!(kinds, [ActionKind::Played(cards(&["red 2"])[0]), ActionKind::Drew]);
+        // Only cards P0 has seen are taken out of the unseen ones
+        let unseen = |name: &str| observation.unseen.iter()
+            .find(|(card, _)| *card == cards(&[name])[0])
+            .map_or(0, |(_, count)| *count);
+        assert_eq!((unseen("green 9"), unseen("blue 6"), unseen("red 5")), (2, 1, 1));
+    }
+
+    #[test]
+    fn corrupt_saves_are_turned_down()
+    {
+        let game = scripted(GameConfig::default(), "red 5", &[&["red 2", "blue 6"], &["green 7", "green 8"]], &[]);
+        let resume = |saved: &SavedGame| Game::resume(saved, 1).err();
+        assert_eq!(resume(&game.save()), None);
+
+        let mut saved = game.save();
+        saved.table.players[1].id = PlayerId(u32::MAX);
+        assert_eq!(resume(&saved), Some(InvariantViolation::PlayerIdOutOfRange(PlayerId(u32::MAX))));
+
+        // Asked to play or keep a drawn card there's no trace of
+        let mut saved = game.save();
+        saved.table.pending_action = Some(PendingAction::PlayOrKeepDrawnCard);
+        assert_eq!(resume(&saved), Some(InvariantViolation::PendingActionWithoutDrawnCard));
+
+        // Asked for the color of a wildcard that isn't there
+        let mut saved = game.save();
+        saved.table.pending_action = Some(PendingAction::AwaitingWildColor);
+        assert_eq!(resume(&saved), Some(InvariantViolation::PendingWildColorWithoutWild(cards(&["red 5"])[0])));
+    }
+
+    #[test]
+    fn points_left_behind_are_kept_in_a_save()
+    {
+        let mut game = scripted(GameConfig::default(), "red 5", &[&["red 1"], &["blue 3"], &["green 4"]], &[]);
+        let leaver = id(&game, 2);
+        game.remove_player(2).unwrap();
+
+        let mut resumed = Game::resume(&game.save(), 1).unwrap();
+        assert_eq!(resumed.departed(), [(leaver, 4)]);
+        assert!(resumed.next_player_id > leaver.0);
+        resumed.play(0).unwrap();
+        assert_eq!(resumed.round_points(), 3 + 4);
+    }
+
+    #[test]
+    fn forks_play_on_without_touching_the_original()
+    {
+        let game = scripted(GameConfig::default(), "red 5", &[&["red 2", "blue 6"], &["green 7", "green 8"]], &[]);
+        let saved = game.save();
+
+        let mut played = game.fork(1);
+        played.play(0).unwrap();
+        let mut drew = game.fork(2);
+        drew.draw_one().unwrap();
+        assert_eq!(played.top_card(), cards(&["red 2"])[0]);
+        assert_eq!(drew.players()[0].number_of_cards(), 3);
+        assert_eq!(game.save(), saved);
+
+        // A clone deals what the original would, a fork its own cards
+        let upcoming = |mut game: Game| (0..20).map(|_| game.deck.draw()).collect::<Vec<Card>>();
+        assert_eq!(upcoming(game.clone()), upcoming(game.clone()));
+        assert_ne!(upcoming(game.fork(1)), upcoming(game.clone()));
+        assert_ne!(upcoming(game.fork(1)), upcoming(game.fork(2)));
+        assert_eq!(upcoming(game.fork(1)), upcoming(game.fork(1)));
     }
 }
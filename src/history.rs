@@ -0,0 +1,83 @@
+use std::fmt;
+
+use crate::game::{Card, Color, PlayerId};
+
+// What a player ended up doing with their turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TurnAction
+{
+    Played(Card),
+    // Drew one or more cards and then played one of them
+    DrewAndPlayed { drawn: usize, card: Card },
+    Drew(usize),
+    // Drew a Draw Two or Draw Four penalty and lost the turn
+    Penalized(usize),
+    Skipped
+}
+
+impl fmt::Display for TurnAction
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TurnAction::Played(card) => write!(f, "played a {}", card),
+            TurnAction::DrewAndPlayed { drawn, card } =>
+                write!(f, "drew {} and played a {}", cards(*drawn), card),
+            TurnAction::Drew(drawn) => write!(f, "drew {}", cards(*drawn)),
+            TurnAction::Penalized(drawn) => write!(f, "was penalized {}", cards(*drawn)),
+            TurnAction::Skipped => write!(f, "was skipped")
+        }
+    }
+}
+
+fn cards(count: usize) -> String
+{
+    if count == 1 { "1 card".to_string() } else { format!("{} cards", count) }
+}
+
+// A digest of one turn of the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TurnRecord
+{
+    pub turn: u32,
+    pub player: PlayerId,
+    pub action: TurnAction,
+    // The top card once the turn was over
+    pub top_card: Card,
+    // The color picked for a wildcard played this turn
+    pub chosen_color: Option<Color>
+}
+
+// Something a player did that changed the cards in play
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActionKind
+{
+    Played(Card),
+    // The drawn card is only known to the player who drew it
+    Drew,
+    DrewPenalty(u8)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionRecord
+{
+    pub action: u32,
+    pub turn: u32,
+    pub player: PlayerId,
+    pub kind: ActionKind
+}
+
+impl ActionRecord
+{
+    // The card that was played, if any
+    pub fn card(&self) -> Option<Card>
+    {
+        match self.kind {
+            ActionKind::Played(card) => Some(card),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::process;
+
+use serde::Serialize;
+
+use crate::cli::{normalize_input, read_line};
+use crate::console::Console;
+use crate::controller::{self, Narrator, PlayerController};
+use crate::error::GameError;
+use crate::events::RecordedEvent;
+use crate::game::{Color, Game, GameEnd, Lobby, Move, PlayerId};
+use crate::view::{BotObservation, PlayerView};
+
+// Everything the frontend says in JSON mode, one object to a line, for a program driving
+// the game rather than a person. Input is still read a line at a time, and a prompt
+// lists every line it will accept
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message<'a>
+{
+    // Who's playing, before anything happens
+    Players { players: Vec<Seat<'a>> },
+    // Everything the game records, see `GameEvent`
+    Event(&'a RecordedEvent),
+    Prompt { player: PlayerId, question: Question, inputs: Vec<String>, view: &'a PlayerView },
+    // The last input couldn't be used, and the same prompt follows
+    Rejected { player: PlayerId, error: String },
+    GameOver { end: GameEnd, winner: Option<PlayerId> }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Seat<'a>
+{
+    pub id: PlayerId,
+    pub name: &'a str,
+    pub bot: bool
+}
+
+// What a prompt is asking for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Question
+{
+    Move,
+    WildColor,
+    SwapTarget,
+    CallUno
+}
+
+const COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+
+pub fn emit(console: &mut impl Console, message: &Message)
+{
+    let line = serde_json::to_string(message).expect("Messages are always valid JSON");
+    console.write(&(line + "\n"));
+}
+
+// What's typed for a move: a card's number from 1, or a word
+pub fn move_input(chosen: Move) -> String
+{
+    match chosen {
+        Move::Play(index) => (index + 1).to_string(),
+        Move::Draw | Move::AcceptPenalty => String::from("draw"),
+        Move::PlayDrawn => String::from("play"),
+        Move::KeepDrawn => String::from("keep"),
+        Move::ChallengeDrawFour => String::from("challenge")
+    }
+}
+
+// A player answering prompts as JSON. Anything not in a prompt's inputs is rejected
+#[derive(Debug, Clone)]
+pub struct JsonController<C: Console>
+{
+    console: C
+}
+
+impl<C: Console> JsonController<C>
+{
+    pub fn new(console: C) -> JsonController<C>
+    {
+        JsonController { console }
+    }
+
+    // Ask until one of `inputs` is given, returning its place in the list
+    fn ask(&mut self, view: &PlayerView, question: Question, inputs: Vec<String>) -> usize
+    {
+        loop {
+            emit(&mut self.console, &Message::Prompt { player: view.id, question, inputs: inputs.clone(), view });
+            let line = read_line(&mut self.console);
+            // The program driving the game has closed its end
+            if line.is_empty() {
+                process::exit(1);
+            }
+            let input = normalize_input(&line);
+            if let Some(place) = inputs.iter().position(|accepted| *accepted == input) {
+                break place;
+            }
+            let error = format!("'{}' isn't one of the inputs", input);
+            emit(&mut self.console, &Message::Rejected { player: view.id, error });
+            self.console.input_rejected();
+        }
+    }
+}
+
+impl<C: Console + Clone + 'static> PlayerController for JsonController<C>
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let inputs = observation.legal_moves.iter().map(|chosen| move_input(*chosen)).collect();
+        observation.legal_moves[self.ask(&observation.view, Question::Move, inputs)]
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        let inputs = COLORS.iter().map(|color| color.to_string().to_lowercase()).collect();
+        COLORS[self.ask(view, Question::WildColor, inputs)]
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        let seats: Vec<usize> = view.opponents().map(|(seat, _)| seat).collect();
+        let inputs = seats.iter().map(|seat| view.table.players[*seat].name.to_lowercase()).collect();
+        seats[self.ask(view, Question::SwapTarget, inputs)]
+    }
+
+    fn wants_to_call_uno(&mut self, view: &PlayerView) -> bool
+    {
+        self.ask(view, Question::CallUno, vec![String::from("yes"), String::from("no")]) == 0
+    }
+
+    fn rejected(&mut self, view: &PlayerView, error: &GameError)
+    {
+        emit(&mut self.console, &Message::Rejected { player: view.id, error: error.to_string() });
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// Passes on every event the game records
+#[derive(Debug, Clone)]
+pub struct JsonNarrator<C: Console>
+{
+    console: C
+}
+
+impl<C: Console> JsonNarrator<C>
+{
+    pub fn new(console: C) -> JsonNarrator<C>
+    {
+        JsonNarrator { console }
+    }
+}
+
+impl<C: Console> Narrator for JsonNarrator<C>
+{
+    fn event(&mut self, _game: &Game, event: &RecordedEvent)
+    {
+        emit(&mut self.console, &Message::Event(event));
+    }
+}
+
+// Play a whole game in JSON. Everyone without a controller in the lobby answers
+// prompts at the console, and there's no lobby menu, so the lobby has to be full enough
+// to start
+pub fn run_game<C: Console + Clone + 'static>(console: &mut C, lobby: Lobby, seed: u64) -> Result<GameEnd, GameError>
+{
+    let mut controllers = lobby.bots().clone();
+    let players = lobby.seating().iter()
+        .map(|player| Seat { id: player.id(), name: player.name(), bot: lobby.is_bot(player.id()) })
+        .collect();
+    emit(console, &Message::Players { players });
+
+    let mut game = lobby.start_with_seed(seed)?;
+    for player in game.players() {
+        controllers.entry(player.id()).or_insert_with(|| Box::new(JsonController::new(console.clone())));
+    }
+    let end = controller::run_game(&mut game, &mut controllers, &mut JsonNarrator::new(console.clone()))?;
+    emit(console, &Message::GameOver { end, winner: end.winner() });
+    Ok(end)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::cell::{Cell, RefCell};
+    use std::io;
+    use std::rc::Rc;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::bots::{GreedyBot, GreedyWeights};
+
+    // A program at the other end that answers every prompt with the first input it
+    // lists, after one answer that isn't any of them
+    #[derive(Debug, Clone, Default)]
+    struct FirstInput
+    {
+        output: Rc<RefCell<String>>,
+        answered: Rc<Cell<bool>>
+    }
+
+    impl Console for FirstInput
+    {
+        fn read_line(&mut self) -> io::Result<String>
+        {
+            if !self.answered.replace(true) {
+                return Ok(String::from("red 12\n"));
+            }
+            let output = self.output.borrow();
+            let prompt: Value = serde_json::from_str(output.lines().last().expect("A prompt was written")).unwrap();
+            assert_eq!(prompt["type"], "prompt");
+            Ok(format!("{}\n", prompt["inputs"][0].as_str().expect("Prompts list their inputs")))
+        }
+
+        fn write(&mut self, text: &str)
+        {
+            self.output.borrow_mut().push_str(text);
+        }
+    }
+
+    #[test]
+    fn every_line_is_json()
+    {
+        let mut lobby = Lobby::with_players(["Ann"]).unwrap();
+        assert!(lobby.add_bot("Bot", Box::new(GreedyBot::new(GreedyWeights::default()))));
+        let mut console = FirstInput::default();
+        let end = run_game(&mut console, lobby, 7).unwrap();
+
+        let output = console.output.borrow();
+        let lines: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines[0]["players"][1], serde_json::json!({ "id": 1, "name": "Bot", "bot": true }));
+
+        // The first answer is turned down and the same prompt comes again
+        let first = lines.iter().position(|line| line["type"] == "prompt").unwrap();
+        assert_eq!(lines[first + 1]["type"], "rejected");
+        assert_eq!(lines[first + 1]["error"], "'red 12' isn't one of the inputs");
+        assert_eq!(lines[first + 2], lines[first]);
+
+        // Following the events, the winner is the one who runs out of cards
+        let mut held = [0i64; 2];
+        for line in &lines {
+            assert!(line["type"] != "prompt" || !line["inputs"].as_array().unwrap().is_empty());
+            let Some((kind, event)) = line["event"].as_object().and_then(|event| event.iter().next()) else {
+                continue;
+            };
+            let seat = event["player"].as_u64().map(|player| player as usize);
+            match (kind.as_str(), seat) {
+                ("CardDealt" | "CardDrawn", Some(seat)) => held[seat] += 1,
+                ("CardPlayed", Some(seat)) => held[seat] -= 1,
+                _ => ()
+            }
+        }
+        let last = lines.last().unwrap();
+        assert_eq!(last["type"], "game_over");
+        let winner: PlayerId = serde_json::from_value(last["winner"].clone()).unwrap();
+        assert_eq!(Some(winner), end.winner());
+        let seat = last["winner"].as_u64().unwrap() as usize;
+        assert_eq!(held[seat], 0);
+        assert!(held[1 - seat] > 0);
+    }
+}
@@ -0,0 +1,37 @@
+// The Uno engine and everything built on it: bots, simulations, tournaments and a
+// terminal frontend. The `uno` binary runs the frontend on standard input and output
+
+pub mod bots;
+pub mod cli;
+pub mod clock;
+pub mod config;
+pub mod console;
+pub mod controller;
+pub mod counting;
+pub mod determinization;
+pub mod error;
+pub mod events;
+pub mod game;
+pub mod history;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod matches;
+pub mod menu;
+pub mod messages;
+pub mod montecarlo;
+pub mod names;
+pub mod opponents;
+pub mod render;
+pub mod replay;
+pub mod rules;
+#[cfg(feature = "serde")]
+pub mod save;
+pub mod scenario;
+pub mod seat;
+pub mod simulation;
+pub mod stats;
+pub mod strategy;
+pub mod tournament;
+pub mod transcript;
+pub mod tutorial;
+pub mod view;
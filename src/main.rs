@@ -1,208 +1,631 @@
-use game::{Color, Game};
-use std::io::{self, Write};
-use crate::game::{CardType, PlayError};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
 
-mod game;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
+use uno::bots::{Bot, Difficulty};
+use uno::cli;
+use uno::json;
+use uno::messages::{fill, Language};
+use uno::config::RULE_NAMES;
+#[cfg(feature = "readline")]
+use uno::console::EditorConsole;
+use uno::console::{Console, StdioConsole};
+use uno::controller::{Controllers, PlayerController};
+use uno::game::{Game, Lobby, MIN_PLAYERS};
+use uno::matches::{SavedMatch, DEFAULT_TARGET};
+use uno::render::CardStyle;
+use uno::replay::Replay;
+use uno::save::SaveFile;
+use uno::scenario::Scenario;
+use uno::simulation::{game_seed, Contestant, SimulationRunner, SIMULATION_MAX_TURNS};
+use uno::transcript::Transcript;
 
-fn get_next_line() -> String
+use crate::script::ScriptConsole;
+use crate::settings::{
+    bot_key, parse_bot, parse_difficulty, parse_duration_arg, parse_language, parse_style, ColorDisplay, ColorMode, Config
+};
+
+// Answers prompts from a file for --script
+mod script;
+// The config file and command line settings
+mod settings;
+
+#[derive(Parser)]
+#[command(about = "Play Uno in the terminal", args_conflicts_with_subcommands = true)]
+struct Cli
 {
-    let mut line = String::new();
-    io::stdin().read_line(&mut line).expect("Can't read from standard input :(");
-    line
+    #[arg(long, global = true, value_name = "PATH",
+        help = "Read settings from this file instead of ~/.config/uno/config.toml")]
+    config: Option<PathBuf>,
+    #[arg(long, global = true, help = "Print the settings from the config file and these arguments combined, then exit")]
+    print_config: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+    // `uno` on its own plays, so the play arguments work without the subcommand too
+    #[command(flatten)]
+    play: PlayArgs
 }
 
-fn print_and_flush(text: &str)
+#[derive(Subcommand)]
+enum Command
 {
-    print!("{text}");
-    std::io::stdout().flush().expect("Error while flushing stdout");
+    #[command(about = "Play a game, set up from the arguments or the lobby menu (the default)")]
+    Play(PlayArgs),
+    #[command(about = "Play many games between bots without watching and sum up how they went")]
+    Simulate(SimulateArgs),
+    #[command(about = "Play back a game recorded with --record")]
+    Replay
+    {
+        file: PathBuf,
+        #[arg(long, value_parser = parse_duration_arg, value_name = "TIME",
+            help = "How long each turn stays up when the replay plays on by itself [default: the bot delay]")]
+        pace: Option<Duration>
+    },
+    #[command(about = "Host games for players on other machines")]
+    Serve
+    {
+        #[arg(long, default_value_t = 7777)]
+        port: u16
+    }
 }
 
-fn skip_turn(game: &mut Game) 
+// The rules of the game itself, shared by every command that plays games
+#[derive(clap::Args)]
+struct RuleArgs
 {
-    println!("{} had their turn skipped!\n", game.player().name());
-    game.next_turn();
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=30), help = "How many cards everyone is dealt")]
+    hand_size: Option<u8>,
+    #[arg(long, value_delimiter = ',', value_name = "RULES",
+        help = format!("House rules to turn on, from: {}", RULE_NAMES.join(", ")))]
+    rules: Vec<String>,
+    #[arg(long, help = "Fewer cards and simpler rules for younger players")]
+    junior: bool
 }
 
-fn reverse(game: &mut Game) 
+impl RuleArgs
 {
-    game.reverse();
-    println!("Reversing the turn direction! The new direction is {}\n\
-    New turn order: {}\n", game.turn_direction(), game);
+    // The arguments that were given override the config file
+    fn apply_to(&self, config: &mut Config)
+    {
+        if !self.rules.is_empty() {
+            config.rules = self.rules.clone();
+        }
+        if self.hand_size.is_some() {
+            config.hand_size = self.hand_size;
+        }
+        config.junior |= self.junior;
+    }
 }
 
-fn draw(game: &mut Game, number_of_cards: u8) 
+#[derive(clap::Args)]
+struct PlayArgs
 {
-    debug_assert_ne!(number_of_cards, 1);
-    println!("{} drew {} cards", game.player().name(), number_of_cards);
-    game.draw_multiple(number_of_cards);
+    #[arg(long = "player", value_name = "NAME", help = "Add a player, once for each. With nobody added, a lobby menu sets the game up")]
+    players: Vec<String>,
+    #[arg(long, help = "How many bots to add after the players [default: 0]")]
+    bots: Option<usize>,
+    #[arg(long, value_parser = parse_bot, help = "How the bots play [default: greedy]")]
+    bot_style: Option<Bot>,
+    #[arg(long, value_parser = parse_difficulty, help = "How often the bots slip up [default: hard]")]
+    difficulty: Option<Difficulty>,
+    #[command(flatten)]
+    rules: RuleArgs,
+    #[arg(long, value_parser = parse_duration_arg,
+        help = "How long bots think before each move, 0 for no pause [default: 800ms]")]
+    bot_delay: Option<Duration>,
+    #[arg(long, value_parser = parse_duration_arg, help = "A random extra pause of up to this long [default: 400ms]")]
+    bot_jitter: Option<Duration>,
+    #[arg(long, help = "Print cards without colors. Colors are also left off when NO_COLOR is set or the output \
+        isn't a terminal")]
+    no_color: bool,
+    #[arg(long, value_enum, help = "Show card colors as colors, as letters like [R] for players who can't tell the \
+        colors apart, or both [default: colors]")]
+    color_mode: Option<ColorDisplay>,
+    #[arg(long, value_parser = parse_style, help = "How cards are shown: plain, fancy (a row of little cards) or \
+        compact (colored squares, like 🟥7) [default: plain]")]
+    style: Option<CardStyle>,
+    #[arg(long, conflicts_with = "style", help = "Draw your hand as a row of little cards, the same as --style fancy")]
+    fancy: bool,
+    #[arg(long, help = "Let everyone see the screen all the time. Otherwise, when more than one person is playing, \
+        the screen is cleared between turns and each player is asked to take the computer")]
+    no_privacy: bool,
+    #[arg(long, help = "Put your cards in order at the start of every turn. Type 'sort' to do it once")]
+    auto_sort: bool,
+    #[arg(long, value_parser = parse_duration_arg, value_name = "TIME", conflicts_with_all = ["demo", "json"],
+        help = "Give each player this long for their turn, like 30s. When it runs out a card is drawn for them. \
+        Bots aren't timed [default: no limit]")]
+    turn_timer: Option<Duration>,
+    #[arg(long = "match", value_name = "POINTS", conflicts_with_all = ["demo", "json"],
+        help = "Play rounds until someone has this many points, 500 if it isn't given. The winner of each round \
+        scores the cards left in everyone else's hands")]
+    match_target: Option<Option<u32>>,
+    #[arg(long, value_parser = parse_language, value_name = "CODE", help = "The language to play in, en or es. \
+        Commands are typed in English whatever the language [default: from the locale]")]
+    lang: Option<Language>,
+    #[arg(long, help = "Write for a screen reader: whole sentences instead of symbols, the hand read out a card at \
+        a time, and no colors or columns")]
+    accessible: bool,
+    #[arg(long, help = "Watch bots play a game on their own")]
+    demo: bool,
+    #[arg(long, value_delimiter = ',', value_parser = parse_bot, default_value = "greedy,hoarder,aggressor,color-fixer",
+        help = "The bots in the demo, in seating order")]
+    lineup: Vec<Bot>,
+    #[arg(long, help = "Replay a particular game")]
+    seed: Option<u64>,
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["demo", "json", "match_target"],
+        help = "Start from the hands, top card and rules in this puzzle file instead of dealing, and say at the end \
+        whether its goal was met. There are some in examples/")]
+    scenario: Option<PathBuf>,
+    #[arg(long, value_name = "FILE",
+        help = "Answer every prompt from the lines of this file, and fail if it runs out or a line is turned down. \
+        Lines starting with # are skipped, lines starting with ! are expected to be turned down, and a line of ~ \
+        waits for the turn timer")]
+    script: Option<PathBuf>,
+    #[arg(long, value_name = "FILE", help = "Add a line to this file for everything that happens in each game, with \
+        the time. The log shows everyone's cards, even with privacy on")]
+    log_file: Option<PathBuf>,
+    #[arg(long = "record", value_name = "FILE", conflicts_with_all = ["demo", "json", "scenario"],
+        help = "Write each game to this file as it's dealt and played, replacing the last, to watch again with \
+        'uno replay FILE'")]
+    replay_file: Option<PathBuf>,
+    #[arg(long, conflicts_with_all = ["demo", "log_file"],
+        help = "Write one JSON object per line instead of text, for a program playing the game. \
+        Every prompt lists the lines it accepts. Players have to be added with --player or --bots")]
+    json: bool
 }
 
-fn pick_wildcard_color(game: &mut Game)
+impl PlayArgs
 {
-    let color: Color = loop {
-        print_and_flush("Select a color for the wildcard:\n\
-        1 - Red\n\
-        2 - Green\n\
-        3 - Blue\n\
-        4 - Yellow\n\
-        Your choice: ");
-    
-        let choice = get_next_line();
-        break match choice.trim() {
-            "1" => Color::Red,
-            "2" => Color::Green,
-            "3" => Color::Blue,
-            "4" => Color::Yellow,
-            _ => {
-                println!("Enter a value between 1 and 4!\n");
-                continue
-            }
-        };
-    };
+    fn apply_to(&self, config: &mut Config)
+    {
+        if !self.players.is_empty() {
+            config.players = self.players.clone();
+        }
+        if let Some(bots) = self.bots {
+            config.bots = bots;
+        }
+        if let Some(bot) = self.bot_style {
+            config.bot_style = bot_key(bot);
+        }
+        if let Some(difficulty) = self.difficulty {
+            config.difficulty = difficulty.to_string().to_lowercase();
+        }
+        self.rules.apply_to(config);
+        if let Some(delay) = self.bot_delay {
+            config.bot_delay = format!("{}ms", delay.as_millis());
+        }
+        if let Some(jitter) = self.bot_jitter {
+            config.bot_jitter = format!("{}ms", jitter.as_millis());
+        }
+        if self.no_color {
+            config.color = ColorMode::Never;
+        }
+        if let Some(color_mode) = self.color_mode {
+            config.color_mode = color_mode;
+        }
+        if let Some(style) = self.style {
+            config.style = style;
+        }
+        if self.fancy {
+            config.style = CardStyle::Fancy;
+        }
+        if self.no_privacy {
+            config.privacy = false;
+        }
+        if self.auto_sort {
+            config.auto_sort = true;
+        }
+        if let Some(target) = self.match_target {
+            config.match_target = Some(target.unwrap_or(DEFAULT_TARGET));
+        }
+        if let Some(limit) = self.turn_timer {
+            config.turn_timer = Some(format!("{}ms", limit.as_millis()));
+        }
+        if self.accessible {
+            config.accessible = true;
+        }
+        if self.lang.is_some() {
+            config.language = self.lang;
+        }
+        if let Some(path) = &self.log_file {
+            config.log_file = Some(path.clone());
+        }
+        if let Some(path) = &self.replay_file {
+            config.replay_file = Some(path.clone());
+        }
+    }
+}
 
-    println!("The wildcard color is now {}\n", color);
-    game.set_wildcard_color(color);
+#[derive(clap::Args)]
+struct SimulateArgs
+{
+    #[arg(long, default_value_t = 1000, help = "How many games to play")]
+    games: usize,
+    #[arg(long, value_delimiter = ',', value_parser = parse_bot, default_value = "greedy,random",
+        help = "The bots that play, in seating order")]
+    bots: Vec<Bot>,
+    #[command(flatten)]
+    rules: RuleArgs,
+    #[arg(long, help = "Repeat a particular run")]
+    seed: Option<u64>
 }
 
-fn main() 
+// Report a bad argument the way clap does, then exit
+fn argument_error(message: impl fmt::Display) -> !
 {
-    let mut game = Game::new();
+    Cli::command().error(ErrorKind::ValueValidation, message).exit()
+}
 
-    println!("To start the game, you must add at least 2 players, then select 'start'\n");
+// Seat the players and bots from the config file and command line, held to the same
+// rules as the lobby menu
+fn add_configured_players(lobby: &mut Lobby, settings: &Config)
+{
+    for name in &settings.players {
+        if let Err(error) = lobby.add_player(name) {
+            argument_error(format!("can't add player '{}': {}", name, error));
+        }
+    }
+    for _ in 0..settings.bots {
+        let controller = Box::new(settings.difficulty().apply(settings.bot_style().controller(), rand::random()));
+        if !lobby.add_bot("", controller) {
+            argument_error(format!("there's only room for {} players", lobby.max_players()));
+        }
+    }
+    if lobby.number_of_players() < MIN_PLAYERS {
+        argument_error(format!("a game needs at least {} players, counting bots", MIN_PLAYERS));
+    }
+}
 
-    loop {
-        if game.number_of_players() >= 2 {
-            print_and_flush("Select an option:\n\
-            1. Add a player\n\
-            2. Start the game\n\
-            Choose an option: ");
+// Bots play a whole game on their own with their hands on show
+fn run_demo(args: &PlayArgs, settings: &Config) -> Result<(), Box<dyn Error>>
+{
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut lobby = Lobby::new();
+    let mut config = settings.game_config();
+    config.max_turns = config.max_turns.or(Some(SIMULATION_MAX_TURNS));
+    lobby.set_config(config);
 
-            let choice = get_next_line();
-            match choice.trim() {
-                "1" => println!(),
-                "2" => break,
-                _ =>  {
-                    println!("Please enter an option in the range 1 - 2!\n");
-                    continue
-                }
-            }
+    for (seat, bot) in args.lineup.iter().enumerate() {
+        let title: Vec<String> = bot.name().split(' ')
+            .map(|word| word[..1].to_uppercase() + &word[1..])
+            .collect();
+        let existing: Vec<String> = lobby.seating().iter().map(|player| player.name().clone()).collect();
+        let name = (1..).map(|number| match number {
+                1 => title.join(" "),
+                number => format!("{} {}", title.join(" "), number)
+            })
+            .find(|name| !existing.contains(name))
+            .expect("Some number is always free");
+        if !lobby.add_bot(&name, bot.seeded_controller(game_seed(seed, seat as u64 + 1))) {
+            argument_error(format!("there's only room for {} players", lobby.max_players()));
         }
+    }
 
-        loop {
-            print_and_flush("Enter a username: ");
-            let username = get_next_line().trim().to_owned();
-            if game.add_player(&username) {
-                println!("Added player {}!\n", username);
-                break;
-            }
+    let mut console = StdioConsole;
+    let options = settings.cli_options(&console);
+    cli::run_demo(&mut console, lobby, options, seed)?;
+    Ok(())
+}
+
+// A game at the terminal, with the players and bots from the settings or the lobby menu
+fn run_play(args: &PlayArgs, settings: &Config) -> Result<(), Box<dyn Error>>
+{
+    let mut lobby = Game::new();
+    lobby.set_config(settings.game_config());
+    // With nobody named in the file or the arguments, the lobby menu sets the game up
+    if !settings.players.is_empty() || settings.bots > 0 {
+        add_configured_players(&mut lobby, settings);
+    }
 
-            println!("Username '{}' is already taken. Please choose a different username\n", username);
+    let seed = args.seed.unwrap_or_else(rand::random);
+    if args.json {
+        if lobby.number_of_players() < MIN_PLAYERS {
+            argument_error(format!("--json needs at least {} players from --player, --bots or the config file",
+                MIN_PLAYERS));
+        }
+        // Only the JSON is written, so the script's lines aren't echoed
+        match &args.script {
+            Some(path) => json::run_game(&mut ScriptConsole::new(&read_script(path)).without_echo(), lobby, seed)?,
+            None => json::run_game(&mut StdioConsole, lobby, seed)?
+        };
+        return Ok(());
+    }
+    match &args.script {
+        Some(path) => {
+            let mut console = ScriptConsole::new(&read_script(path));
+            let options = settings.cli_options(&console);
+            match settings.match_target {
+                Some(target) => cli::run_match(&mut console, lobby, options, seed, target)?,
+                None => cli::run_game(&mut console, &mut lobby, options, seed).map(|_| ())?
+            }
+        },
+        None => {
+            // Line editing when someone is typing, and plain reading when the input is piped in.
+            // The line editor can't be interrupted, so it isn't used with a turn timer
+            #[cfg(feature = "readline")]
+            if let Some(console) = EditorConsole::new().filter(|_| settings.turn_timer.is_none()) {
+                return play_at_keyboard(console, lobby, settings, seed);
+            }
+            play_at_keyboard(StdioConsole, lobby, settings, seed)?;
         }
     }
+    Ok(())
+}
 
-    let mut game = game.start().unwrap();
-    println!("\nStarting the game! The starting player is {}\n\
-    Turn order: {}\n\n\
-    The top card is a {}\n", game.player().name(), game, game.top_card());
+// A puzzle from a scenario file, played at the keyboard or from a script
+fn run_scenario(path: &Path, args: &PlayArgs, settings: &Config) -> Result<(), Box<dyn Error>>
+{
+    let scenario = Scenario::read(path)
+        .unwrap_or_else(|error| argument_error(format!("can't use the scenario {}: {}", path.display(), error)));
+    let mut game = scenario.start(args.seed.unwrap_or_else(rand::random))
+        .unwrap_or_else(|error| argument_error(format!("can't use the scenario {}: {}", path.display(), error)));
+    let bots: Controllers = scenario.bot_seats()
+        .map(|seat| {
+            let controller = settings.difficulty().apply(settings.bot_style().controller(), rand::random());
+            (game.players()[seat].id(), Box::new(controller) as Box<dyn PlayerController>)
+        })
+        .collect();
 
-    if match game.top_card().card_type {
-        CardType::Skip => { 
-            skip_turn(&mut game); 
-            true
-        },
-        CardType::Reverse => { 
-            reverse(&mut game); 
-            skip_turn(&mut game); 
-            true
-        },
-        CardType::DrawTwo => { 
-            draw(&mut game, 2); 
-            skip_turn(&mut game); 
-            true
-        },
-        CardType::Wildcard => {
-            println!("{}", game.player());
-            pick_wildcard_color(&mut game);
-            false
-        },
-        _ => false
+    if let Some(script) = &args.script {
+        return solve(ScriptConsole::new(&read_script(script)), &scenario, &mut game, bots, settings);
     }
-    {
-        println!("The new starting player is {}\n", game.player().name());
+    #[cfg(feature = "readline")]
+    if let Some(console) = EditorConsole::new().filter(|_| settings.turn_timer.is_none()) {
+        return solve(console, &scenario, &mut game, bots, settings);
     }
+    solve(StdioConsole, &scenario, &mut game, bots, settings)
+}
 
-    loop {
-        let player = game.player();
+fn solve<C: Console + Clone + 'static>(mut console: C, scenario: &Scenario, game: &mut Game, bots: Controllers,
+    settings: &Config) -> Result<(), Box<dyn Error>>
+{
+    let options = settings.cli_options(&console);
+    cli::play_scenario(&mut console, scenario, game, bots, options)?;
+    Ok(())
+}
 
-        print_and_flush(format!("\
-        It's {}'s turn!\n\
-        The top card is a {}\n\n\
-        {}\
-        Choose a card or type 'draw': ", 
-        player.name(), game.top_card(), player).as_str());
+// A recorded game played back a turn at a time
+fn run_replay(path: &Path, pace: Option<Duration>, settings: &Config) -> Result<(), Box<dyn Error>>
+{
+    let mut console = StdioConsole;
+    let options = settings.cli_options(&console);
+    let pace = pace.unwrap_or(options.bot_delay.delay);
+    Replay::read(path)
+        .and_then(|replay| cli::play_back(&mut console, &replay, options, pace))
+        .unwrap_or_else(|error| argument_error(format!("can't play back {}: {}", path.display(), error)));
+    Ok(())
+}
+
+fn read_script(path: &Path) -> String
+{
+    fs::read_to_string(path).unwrap_or_else(|error| argument_error(format!("can't read {}: {}", path.display(), error)))
+}
 
-        let result = match get_next_line().trim().to_lowercase().as_str() {
-            "draw" => {
-                match game.draw_one() {
-                    Some(card) => { println!("You drew a {}! It's not playable on the current card!", card); Ok(false) }
-                    None => { println!("You drew a {}! It's playable on the current card!", game.top_card()); Ok(true) }
-                }
+// Resume the saved game or play a session of new ones, then wait to close
+fn play_at_keyboard<C: Console + Clone + 'static>(mut console: C, lobby: Lobby, settings: &Config, seed: u64)
+    -> Result<(), Box<dyn Error>>
+{
+    let options = settings.cli_options(&console);
+    match offer_resume(&mut console, settings) {
+        Some((mut game, bots, in_match)) => {
+            match in_match {
+                Some(saved) => cli::resume_match(&mut console, game, bots, options, &saved)?,
+                None => cli::resume_game(&mut console, &mut game, bots, options, None).map(|_| ())?
             }
-            text => {
-                text.parse::<usize>()
-                    .map_err(|_| PlayError::InvalidCardIndex)
-                    .and_then(|choice| game.play(choice - 1))
-                    .map(|_| true)
+            // The game is over, so there's nothing left to resume
+            if let Some(path) = settings.save_path() {
+                let _ = fs::remove_file(path);
             }
-        };
+        },
+        None => match settings.match_target {
+            Some(target) => cli::run_match(&mut console, lobby, options, seed, target)?,
+            None => cli::run_session(&mut console, lobby, options, seed)?
+        }
+    }
+    console.write(settings.language().messages().press_enter_to_close);
+    cli::read_line(&mut console);
+    Ok(())
+}
 
-        let player = game.player();
-        if result.is_err() {
-            match result.unwrap_err() {
-                PlayError::InvalidCardIndex =>
-                    println!("Please enter a card index in the range 1 - {}, or type 'draw' to draw\n", player.number_of_cards()),
-                PlayError::CardUnplayable =>
-                    println!("The card you picked cannot be played on a {}. \
-                    Select a different card or choose the 'draw' option\n", game.top_card())
-            }
-            continue;
+// Ask whether to carry on the game in the save file, if there is one. A save that can't
+// be used is reported, and a new game is played instead
+fn offer_resume(console: &mut impl Console, settings: &Config) -> Option<(Game, Controllers, Option<SavedMatch>)>
+{
+    let messages = settings.language().messages();
+    let path = settings.save_path().filter(|path| path.exists())?;
+    let save = match SaveFile::read(&path) {
+        Ok(save) => save,
+        Err(error) => {
+            writeln!(console, "{}\n{}\n", fill(messages.save_unreadable, &[&path.display(), &error]),
+                messages.starting_new_game);
+            return None;
+        }
+    };
+    let question = fill(messages.resume_question, &[&save.saved_at_text()]);
+    if !cli::ask_yes_no(console, messages, &question) {
+        return None;
+    }
+    match save.resume() {
+        Ok(game) => {
+            let bots = save.bots.iter()
+                .filter(|bot| game.players().iter().any(|player| player.id() == **bot))
+                .map(|bot| {
+                    let controller = settings.difficulty().apply(settings.bot_style().controller(), rand::random());
+                    (*bot, Box::new(controller) as Box<dyn PlayerController>)
+                })
+                .collect();
+            Some((game, bots, save.in_match))
+        },
+        Err(error) => {
+            writeln!(console, "{}\n{}\n", fill(messages.resume_failed, &[&error]), messages.starting_new_game);
+            None
         }
+    }
+}
 
-        if result.unwrap() {
-            println!("{} played a {}!\n", player.name(), game.top_card());
+// Bots play each other over and over, and only the totals are printed
+fn run_simulate(args: &SimulateArgs, settings: &Config) -> Result<(), Box<dyn Error>>
+{
+    if args.bots.len() < MIN_PLAYERS {
+        argument_error(format!("a simulation needs at least {} bots", MIN_PLAYERS));
+    }
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let roster = args.bots.iter().map(|bot| Contestant::bot(*bot)).collect();
+    let runner = SimulationRunner::new(roster, settings.game_config(), seed, args.games);
+    #[cfg(feature = "rayon")]
+    let results = runner.run_parallel(0)?;
+    #[cfg(not(feature = "rayon"))]
+    let results = runner.run()?;
 
-            if player.number_of_cards() == 0 {
-                println!("{} has played their last card! They are the winner!\n", player.name());
-                break;
-            }
+    println!("Played {} games. Repeat them with --seed {}\n", results.games, seed);
+    println!("{:<6}{:<14}{:>8}{:>10}", "Seat", "Bot", "Wins", "Win rate");
+    for seat in &results.seats {
+        println!("{:<6}{:<14}{:>8}{:>9.1}%", seat.seat + 1, seat.controller, seat.wins, seat.win_rate * 100.0);
+    }
+    let turns = &results.turn_percentiles;
+    println!("\nNobody won {} games, {} of them at the turn limit", results.no_winner, results.turn_limit_reached);
+    println!("Turns per game: {:.1} on average, {} at the median, {} at the 90th percentile and {} at most",
+        results.mean_turns, turns.p50, turns.p90, turns.max);
+    println!("Cards drawn per game: {:.1}, of which {:.1} were penalties. Somebody drew a penalty in {:.1}% of games",
+        results.mean_cards_drawn, results.mean_penalty_cards, results.penalty_frequency * 100.0);
+    Ok(())
+}
 
-            match game.top_card().card_type {
-                CardType::Reverse => reverse(&mut game),
-                CardType::Wildcard | CardType::DrawFourWildcard => pick_wildcard_color(&mut game),
-                _ => ()
-            }
+// Commands that are on the way but can't do anything yet
+fn not_implemented(error: &str) -> !
+{
+    eprintln!("error: {}", error);
+    process::exit(1)
+}
 
-            game.next_turn();
-
-            match game.top_card().card_type {
-                CardType::Skip => skip_turn(&mut game),
-                CardType::Reverse if game.number_of_players() == 2 => skip_turn(&mut game),
-                CardType::DrawTwo => { 
-                    draw(&mut game, 2); 
-                    skip_turn(&mut game); 
-                },
-                CardType::DrawFourWildcard => {
-                    draw(&mut game, 4); 
-                    skip_turn(&mut game); 
-                }
-                _ => ()
-            }
-        } else {
-            println!("{} was unable to play a card! Their turn is over\n", player.name());
-            game.next_turn();
-        }
+impl Cli
+{
+    // The command given, or play with the arguments given without one
+    fn into_command(self) -> Command
+    {
+        self.command.unwrap_or(Command::Play(self.play))
     }
+}
 
-    print_and_flush("Press enter to close the program...");
-    get_next_line();
+// Which of the run_ functions a command is handled by, with what it needs
+enum Handler
+{
+    Demo(PlayArgs),
+    Scenario(PathBuf, PlayArgs),
+    Play(PlayArgs),
+    Simulate(SimulateArgs),
+    Replay(PathBuf, Option<Duration>),
+    // What to say about a command that can't be used yet
+    NotImplemented(String)
+}
+
+fn route(command: Command) -> Handler
+{
+    match command {
+        Command::Play(args) if args.demo => Handler::Demo(args),
+        Command::Play(args) => match args.scenario.clone() {
+            Some(path) => Handler::Scenario(path, args),
+            None => Handler::Play(args)
+        },
+        Command::Simulate(args) => Handler::Simulate(args),
+        Command::Replay { file, pace } => Handler::Replay(file, pace),
+        Command::Serve { port } =>
+            Handler::NotImplemented(format!("serving on port {}: playing over the network isn't implemented yet", port))
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>>
+{
+    let cli = Cli::parse();
+    let mut settings = Config::load(cli.config.as_deref()).unwrap_or_else(|error| argument_error(error));
+    let print_config = cli.print_config;
+    let command = cli.into_command();
+    match &command {
+        Command::Play(args) => args.apply_to(&mut settings),
+        Command::Simulate(args) => args.rules.apply_to(&mut settings),
+        Command::Replay { .. } | Command::Serve { .. } => ()
+    }
+    settings.validate().unwrap_or_else(|error| argument_error(error));
+    // Better to find out now than after the game
+    if let Some(path) = &settings.log_file {
+        Transcript::open(path)
+            .unwrap_or_else(|error| argument_error(format!("can't write the log file {}: {}", path.display(), error)));
+    }
+    if print_config {
+        print!("{}", toml::to_string_pretty(&settings)?);
+        return Ok(());
+    }
+
+    match route(command) {
+        Handler::Demo(args) => run_demo(&args, &settings),
+        Handler::Scenario(path, args) => run_scenario(&path, &args, &settings),
+        Handler::Play(args) => run_play(&args, &settings),
+        Handler::Simulate(args) => run_simulate(&args, &settings),
+        Handler::Replay(file, pace) => run_replay(&file, pace, &settings),
+        Handler::NotImplemented(error) => not_implemented(&error)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn handler(args: &[&str]) -> Handler
+    {
+        let cli = Cli::try_parse_from(["uno"].iter().chain(args)).unwrap_or_else(|error| panic!("{}", error));
+        route(cli.into_command())
+    }
+
+    fn rejected(args: &[&str]) -> Option<ErrorKind>
+    {
+        Cli::try_parse_from(["uno"].iter().chain(args)).err().map(|error| error.kind())
+    }
+
+    #[test]
+    fn playing_is_the_default()
+    {
+        assert!(matches!(handler(&[]), Handler::Play(args) if args.players.is_empty()));
+        assert!(matches!(handler(&["--player", "Alice", "--bots", "2"]),
+            Handler::Play(args) if args.players == ["Alice"] && args.bots == Some(2)));
+        assert!(matches!(handler(&["play", "--player", "Alice"]), Handler::Play(args) if args.players == ["Alice"]));
+        assert!(matches!(handler(&["--demo", "--seed", "9"]), Handler::Demo(args) if args.seed == Some(9)));
+        assert!(matches!(handler(&["play", "--scenario", "examples/one_card_left.toml"]),
+            Handler::Scenario(path, _) if path == Path::new("examples/one_card_left.toml")));
+    }
+
+    #[test]
+    fn each_subcommand_has_its_handler()
+    {
+        assert!(matches!(handler(&["simulate", "--games", "1000", "--bots", "greedy,random"]),
+            Handler::Simulate(args) if args.games == 1000 && args.bots.len() == 2 && args.seed.is_none()));
+        assert!(matches!(handler(&["replay", "game.jsonl"]),
+            Handler::Replay(file, None) if file == Path::new("game.jsonl")));
+        assert!(matches!(handler(&["replay", "game.jsonl", "--pace", "2s"]),
+            Handler::Replay(_, Some(pace)) if pace == Duration::from_secs(2)));
+        assert!(matches!(handler(&["serve", "--port", "7777"]), Handler::NotImplemented(error)
+            if error == "serving on port 7777: playing over the network isn't implemented yet"));
+    }
+
+    #[test]
+    fn bad_arguments_are_turned_down()
+    {
+        assert_eq!(rejected(&["--bogus"]), Some(ErrorKind::UnknownArgument));
+        assert_eq!(rejected(&["simulate", "--games", "lots"]), Some(ErrorKind::ValueValidation));
+        assert_eq!(rejected(&["simulate", "--bots", "greedy,nobody"]), Some(ErrorKind::ValueValidation));
+        assert_eq!(rejected(&["serve", "--port", "70000"]), Some(ErrorKind::ValueValidation));
+        assert_eq!(rejected(&["replay"]), Some(ErrorKind::MissingRequiredArgument));
+        assert_eq!(rejected(&["--hand-size", "0"]), Some(ErrorKind::ValueValidation));
+
+        // Flags that can't go together
+        assert_eq!(rejected(&["--fancy", "--style", "plain"]), Some(ErrorKind::ArgumentConflict));
+        assert_eq!(rejected(&["--demo", "--turn-timer", "30s"]), Some(ErrorKind::ArgumentConflict));
+        assert_eq!(rejected(&["play", "--json", "--log-file", "uno.log"]), Some(ErrorKind::ArgumentConflict));
+        assert!(rejected(&["--player", "Alice", "simulate"]).is_some());
+    }
 }
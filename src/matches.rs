@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use crate::config::StartingPlayer;
+use crate::error::GameError;
+use crate::game::{Game, Lobby, PlayerId};
+
+// The points a match is played to unless it's told otherwise
+pub const DEFAULT_TARGET: u32 = 500;
+
+// A series of rounds between the same players. The first round picks who goes first
+// the way the lobby's config says, then the deal passes one seat clockwise each round
+// instead of being chosen again. The winner of each round scores the cards left in
+// everyone else's hands, and the first to reach the target wins the match
+#[derive(Clone)]
+pub struct Match
+{
+    lobby: Lobby,
+    // The seat that went first in the latest round
+    starting_seat: Option<usize>,
+    rounds_started: u32,
+    target: u32,
+    totals: BTreeMap<PlayerId, u32>,
+    // Who won the latest round that was scored, and what they scored
+    last_round: Option<(PlayerId, u32)>
+}
+
+// Where a match stands, for picking it back up later, see `Match::save`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedMatch
+{
+    pub target: u32,
+    pub rounds_started: u32,
+    pub starting_seat: Option<usize>,
+    pub totals: Vec<(PlayerId, u32)>
+}
+
+impl Match
+{
+    pub fn new(lobby: Lobby) -> Match
+    {
+        Match { lobby, starting_seat: None, rounds_started: 0, target: DEFAULT_TARGET, totals: BTreeMap::new(),
+            last_round: None }
+    }
+
+    // Carry on a match with the players in `lobby`, who have to be the ones it was saved
+    // with
+    pub fn resume(lobby: Lobby, saved: &SavedMatch) -> Match
+    {
+        Match {
+            lobby,
+            starting_seat: saved.starting_seat,
+            rounds_started: saved.rounds_started,
+            target: saved.target,
+            totals: saved.totals.iter().copied().collect(),
+            last_round: None
+        }
+    }
+
+    pub fn save(&self) -> SavedMatch
+    {
+        SavedMatch {
+            target: self.target,
+            rounds_started: self.rounds_started,
+            starting_seat: self.starting_seat,
+            totals: self.totals.iter().map(|(id, points)| (*id, *points)).collect()
+        }
+    }
+
+    pub fn lobby(&self) -> &Lobby
+    {
+        &self.lobby
+    }
+
+    pub fn rounds_started(&self) -> u32
+    {
+        self.rounds_started
+    }
+
+    pub fn target(&self) -> u32
+    {
+        self.target
+    }
+
+    pub fn set_target(&mut self, target: u32)
+    {
+        self.target = target;
+    }
+
+    // The seat that will go first next round. None before the first round, which
+    // leaves it to the config
+    pub fn next_starting_seat(&self) -> Option<usize>
+    {
+        self.starting_seat.map(|seat| (seat + 1) % self.lobby.number_of_players())
+    }
+
+    // The seat that deals the next round, the one before whoever goes first
+    pub fn next_dealer(&self) -> Option<usize>
+    {
+        let players = self.lobby.number_of_players();
+        self.next_starting_seat().map(|seat| (seat + players - 1) % players)
+    }
+
+    // Deal a fresh round with the same players and rules
+    pub fn start_round(&mut self) -> Result<Game, GameError>
+    {
+        self.start_round_with_seed(rand::random())
+    }
+
+    // Like `start_round`, with the deal following from `seed`
+    pub fn start_round_with_seed(&mut self, seed: u64) -> Result<Game, GameError>
+    {
+        let mut lobby = self.lobby.clone();
+        if let Some(seat) = self.next_starting_seat() {
+            let mut config = lobby.config().clone();
+            config.starting_player = StartingPlayer::Seat(seat);
+            lobby.set_config(config);
+        }
+
+        let game = lobby.start_with_seed(seed)?;
+        self.starting_seat = game.seat_of(game.start_effect().first_player);
+        self.rounds_started += 1;
+        Ok(game)
+    }
+
+    // Add the points from a finished round to its winner's total. Returns what they
+    // scored, nothing when the round had no winner
+    pub fn record_round(&mut self, game: &Game) -> u32
+    {
+        let Some(winner) = game.winner().map(|player| player.id()) else {
+            self.last_round = None;
+            return 0;
+        };
+        let points = game.round_points();
+        *self.totals.entry(winner).or_default() += points;
+        self.last_round = Some((winner, points));
+        points
+    }
+
+    pub fn total(&self, id: PlayerId) -> u32
+    {
+        self.totals.get(&id).copied().unwrap_or_default()
+    }
+
+    // What `id` scored in the latest round
+    pub fn last_round_points(&self, id: PlayerId) -> u32
+    {
+        self.last_round.filter(|(winner, _)| *winner == id).map_or(0, |(_, points)| points)
+    }
+
+    // Everyone in the match from the most points to the fewest, in seating order when
+    // they're level
+    pub fn standings(&self) -> Vec<(PlayerId, u32)>
+    {
+        let mut standings: Vec<(PlayerId, u32)> = self.lobby.seating().iter()
+            .map(|player| (player.id(), self.total(player.id())))
+            .collect();
+        standings.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+        standings
+    }
+
+    // Whoever has the most points once someone has reached the target
+    pub fn winner(&self) -> Option<PlayerId>
+    {
+        self.standings().first().filter(|(_, total)| *total >= self.target).map(|(id, _)| *id)
+    }
+}
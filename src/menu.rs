@@ -0,0 +1,340 @@
+use crate::bots::{Bot, Difficulty};
+use crate::cli::{normalize_input, read_line, reject, CliOptions};
+use crate::config::{StackingPolicy, StalematePolicy, StartingPlayer};
+use crate::console::Console;
+use crate::error::{GameError, NameError};
+use crate::game::Lobby;
+use crate::messages::{fill, Messages};
+use crate::names::NamePack;
+use crate::tutorial::Tutorial;
+
+fn on_off(messages: &Messages, enabled: bool) -> &'static str
+{
+    if enabled { messages.on } else { messages.off }
+}
+
+fn configure_house_rules<C: Console>(console: &mut C, messages: &Messages, lobby: &mut Lobby)
+{
+    loop {
+        let mut config = lobby.config().clone();
+        let on_off = |enabled| on_off(messages, enabled);
+        let settings = [
+            on_off(config.stacking).to_owned(), on_off(config.draw_four_challenge).to_owned(),
+            on_off(config.forced_play).to_owned(), on_off(config.jump_in).to_owned(),
+            on_off(config.seven_zero).to_owned(),
+            on_off(config.penalize_false_uno_accusations).to_owned(), on_off(config.auto_play_drawn_cards).to_owned(),
+            on_off(config.draw_until_playable).to_owned(), on_off(config.strict_color_matching).to_owned(),
+            config.max_turns.map_or(messages.no_turn_limit.to_owned(), |turns| turns.to_string()),
+            stalemate_policy_name(messages, config.stalemate_policy).to_owned(),
+            on_off(config.play_identical_together).to_owned(),
+            stacking_policy_name(messages, config.stacking_policy).to_owned(),
+            on_off(config.no_going_out_on_wild).to_owned(),
+            on_off(config.starting_player == StartingPlayer::DrawForDeal).to_owned()
+        ];
+        writeln!(console, "{}", messages.house_rules_title);
+        for (index, (rule, setting)) in messages.house_rules.iter().zip(settings).enumerate() {
+            writeln!(console, "{}. {} ({})", index + 1, rule, setting);
+        }
+        console.write(messages.choose_rule);
+
+        let (name, enabled) = match normalize_input(&read_line(console)).as_str() {
+            "" => {
+                writeln!(console);
+                break;
+            },
+            "1" => {
+                config.stacking = !config.stacking;
+                (messages.house_rules_toggled[0], config.stacking)
+            },
+            "2" => {
+                config.draw_four_challenge = !config.draw_four_challenge;
+                (messages.house_rules_toggled[1], config.draw_four_challenge)
+            },
+            "3" => {
+                config.forced_play = !config.forced_play;
+                (messages.house_rules_toggled[2], config.forced_play)
+            },
+            "4" => {
+                config.jump_in = !config.jump_in;
+                (messages.house_rules_toggled[3], config.jump_in)
+            },
+            "5" => {
+                config.seven_zero = !config.seven_zero;
+                (messages.house_rules_toggled[4], config.seven_zero)
+            },
+            "6" => {
+                config.penalize_false_uno_accusations = !config.penalize_false_uno_accusations;
+                (messages.house_rules_toggled[5], config.penalize_false_uno_accusations)
+            },
+            "7" => {
+                config.auto_play_drawn_cards = !config.auto_play_drawn_cards;
+                (messages.house_rules_toggled[6], config.auto_play_drawn_cards)
+            },
+            "8" => {
+                config.draw_until_playable = !config.draw_until_playable;
+                (messages.house_rules_toggled[7], config.draw_until_playable)
+            },
+            "9" => {
+                config.strict_color_matching = !config.strict_color_matching;
+                (messages.house_rules_toggled[8], config.strict_color_matching)
+            },
+            "10" => {
+                console.write(messages.max_turns_prompt);
+                config.max_turns = match normalize_input(&read_line(console)).as_str() {
+                    "" => None,
+                    text => match text.parse::<u32>() {
+                        Ok(turns) if turns > 0 => Some(turns),
+                        _ => {
+                            reject(console, format!("{}\n", messages.max_turns_rejected));
+                            continue
+                        }
+                    }
+                };
+                lobby.set_config(config);
+                writeln!(console);
+                continue
+            },
+            "11" => {
+                config.stalemate_policy = match config.stalemate_policy {
+                    StalematePolicy::LowestPoints => StalematePolicy::Draw,
+                    StalematePolicy::Draw => StalematePolicy::LowestPoints
+                };
+                let policy = stalemate_policy_name(messages, config.stalemate_policy);
+                writeln!(console, "{}\n", fill(messages.stalemate_now, &[&policy]));
+                lobby.set_config(config);
+                continue
+            },
+            "12" => {
+                config.play_identical_together = !config.play_identical_together;
+                (messages.house_rules_toggled[11], config.play_identical_together)
+            },
+            "13" => {
+                config.stacking_policy = match config.stacking_policy {
+                    StackingPolicy::SAME_KIND => StackingPolicy::PROGRESSIVE,
+                    StackingPolicy::PROGRESSIVE => StackingPolicy::ANYTHING_GOES,
+                    _ => StackingPolicy::SAME_KIND
+                };
+                let policy = stacking_policy_name(messages, config.stacking_policy);
+                writeln!(console, "{}\n", fill(messages.stacking_now, &[&policy]));
+                lobby.set_config(config);
+                continue
+            },
+            "14" => {
+                config.no_going_out_on_wild = !config.no_going_out_on_wild;
+                (messages.house_rules_toggled[13], config.no_going_out_on_wild)
+            },
+            "15" => {
+                config.starting_player = match config.starting_player {
+                    StartingPlayer::DrawForDeal => StartingPlayer::Random,
+                    _ => StartingPlayer::DrawForDeal
+                };
+                (messages.house_rules_toggled[14], config.starting_player == StartingPlayer::DrawForDeal)
+            },
+            _ => {
+                reject(console, format!("{}\n", messages.rule_rejected));
+                continue
+            }
+        };
+
+        writeln!(console, "{}\n", fill(messages.rule_now, &[&name, &on_off(enabled)]));
+        lobby.set_config(config);
+    }
+}
+
+fn stacking_policy_name(messages: &Messages, policy: StackingPolicy) -> &'static str
+{
+    match policy {
+        StackingPolicy::SAME_KIND => messages.stacking_policies[0],
+        StackingPolicy::PROGRESSIVE => messages.stacking_policies[1],
+        StackingPolicy::ANYTHING_GOES => messages.stacking_policies[2],
+        _ => messages.stacking_policies[3]
+    }
+}
+
+fn stalemate_policy_name(messages: &Messages, policy: StalematePolicy) -> &'static str
+{
+    match policy {
+        StalematePolicy::LowestPoints => messages.stalemate_policies[0],
+        StalematePolicy::Draw => messages.stalemate_policies[1]
+    }
+}
+
+fn print_seating<C: Console>(console: &mut C, messages: &Messages, lobby: &Lobby)
+{
+    writeln!(console, "{}", messages.seating_title);
+    for (index, player) in lobby.seating().iter().enumerate() {
+        writeln!(console, "{}. {}", index + 1, player.name());
+    }
+    writeln!(console);
+}
+
+fn read_seat<C: Console>(console: &mut C, messages: &Messages, prompt: &str, number_of_players: usize) -> usize
+{
+    loop {
+        console.write(prompt);
+        match normalize_input(&read_line(console)).parse::<usize>() {
+            Ok(seat @ 1..) if seat <= number_of_players => break seat - 1,
+            _ => reject(console, fill(messages.seat_rejected, &[&number_of_players]))
+        }
+    }
+}
+
+fn reorder_players<C: Console>(console: &mut C, messages: &Messages, lobby: &mut Lobby)
+{
+    print_seating(console, messages, lobby);
+    let from = read_seat(console, messages, messages.move_which, lobby.number_of_players());
+    let to = read_seat(console, messages, messages.to_which_seat, lobby.number_of_players());
+
+    lobby.move_player(from, to).expect("Seats were validated while reading them");
+    writeln!(console);
+    print_seating(console, messages, lobby);
+}
+
+fn add_bot<C: Console>(console: &mut C, messages: &Messages, lobby: &mut Lobby)
+{
+    writeln!(console, "{}", messages.choose_bot);
+    for (index, bot) in Bot::ALL.iter().enumerate() {
+        writeln!(console, "{}. {}", index + 1, bot);
+    }
+
+    let bot = loop {
+        console.write(messages.your_choice);
+        match normalize_input(&read_line(console)).parse::<usize>() {
+            Ok(choice @ 1..) if choice <= Bot::ALL.len() => break Bot::ALL[choice - 1],
+            _ => reject(console, fill(messages.bot_rejected, &[&Bot::ALL.len()]))
+        }
+    };
+
+    writeln!(console, "{}", messages.choose_difficulty);
+    for (index, difficulty) in Difficulty::ALL.iter().enumerate() {
+        writeln!(console, "{}. {}", index + 1, difficulty);
+    }
+    let difficulty = loop {
+        console.write(messages.your_choice);
+        match normalize_input(&read_line(console)).parse::<usize>() {
+            Ok(choice @ 1..) if choice <= Difficulty::ALL.len() => break Difficulty::ALL[choice - 1],
+            _ => reject(console, fill(messages.difficulty_rejected, &[&Difficulty::ALL.len()]))
+        }
+    };
+
+    loop {
+        console.write(fill(messages.bot_name_prompt, &[&lobby.bot_names()]).as_str());
+        let name = read_line(console);
+        if lobby.add_bot(&name, Box::new(difficulty.apply(bot.controller(), rand::random()))) {
+            let name = lobby.seating().last().expect("A bot was just added").name();
+            writeln!(console, "{}\n", fill(messages.added_bot, &[&name, &bot, &difficulty]));
+            break;
+        }
+        reject(console, format!("{}\n", fill(messages.bad_bot_name, &[&name.trim()])));
+    }
+}
+
+// Play the tutorial as the first person seated at the keyboard
+fn learn_to_play<C: Console + Clone + 'static>(console: &mut C, options: &CliOptions, lobby: &Lobby)
+{
+    let name = lobby.seating().iter().find(|player| !lobby.is_bot(player.id()))
+        .map_or(options.renderer.messages.tutorial_student, |player| player.name().as_str());
+    if let Err(error) = Tutorial::BASICS.run(console, options, name) {
+        writeln!(console, "{}\n", error);
+    }
+}
+
+fn choose_bot_names<C: Console>(console: &mut C, messages: &Messages, lobby: &mut Lobby)
+{
+    writeln!(console, "{}", messages.choose_bot_names);
+    for (index, pack) in NamePack::ALL.iter().enumerate() {
+        writeln!(console, "{}. {}", index + 1, pack);
+    }
+    loop {
+        console.write(messages.your_choice);
+        match normalize_input(&read_line(console)).parse::<usize>() {
+            Ok(choice @ 1..) if choice <= NamePack::ALL.len() => {
+                lobby.set_bot_names(NamePack::ALL[choice - 1]);
+                writeln!(console, "{}\n", fill(messages.bot_names_chosen, &[&lobby.bot_names()]));
+                break;
+            },
+            _ => reject(console, fill(messages.choice_rejected, &[&NamePack::ALL.len()]))
+        }
+    }
+}
+
+// Seat players and bots and choose the house rules until someone starts the game
+pub fn run_lobby_menu<C: Console + Clone + 'static>(console: &mut C, options: &CliOptions, game: &mut Lobby)
+{
+    let messages = options.renderer.messages;
+    writeln!(console, "{}\n", messages.lobby_intro);
+
+    loop {
+        if game.number_of_players() >= 2 {
+            writeln!(console, "{}", messages.lobby_title);
+            for (index, option) in messages.lobby_options.iter().enumerate() {
+                writeln!(console, "{}. {}", index + 1, option);
+            }
+            console.write(messages.choose_option);
+
+            let choice = normalize_input(&read_line(console));
+            match choice.as_str() {
+                "1" | "6" if game.is_full() => {
+                    reject(console, format!("{}\n", fill(messages.lobby_full, &[&game.max_players()])));
+                    continue
+                },
+                "1" => writeln!(console),
+                "2" => break,
+                "3" => {
+                    reorder_players(console, messages, game);
+                    continue
+                },
+                "4" => {
+                    game.shuffle_seating(&mut rand::thread_rng());
+                    writeln!(console, "{}", messages.shuffled);
+                    print_seating(console, messages, game);
+                    continue
+                },
+                "5" => {
+                    configure_house_rules(console, messages, game);
+                    continue
+                },
+                "6" => {
+                    add_bot(console, messages, game);
+                    continue
+                },
+                "7" => {
+                    choose_bot_names(console, messages, game);
+                    continue
+                },
+                "8" => {
+                    learn_to_play(console, options, game);
+                    continue
+                },
+                _ =>  {
+                    reject(console, format!("{}\n", messages.lobby_rejected));
+                    continue
+                }
+            }
+        }
+
+        loop {
+            console.write(messages.username_prompt);
+            let username = read_line(console).trim().to_owned();
+            match game.add_player(&username) {
+                Ok(()) => {
+                    writeln!(console, "{}\n", fill(messages.added_player, &[&username]));
+                    break;
+                }
+                Err(GameError::InvalidName(error)) => match error {
+                    NameError::Empty => reject(console, format!("{}\n", messages.name_empty)),
+                    NameError::TooLong { max_length } =>
+                        reject(console, format!("{}\n", fill(messages.name_too_long, &[&max_length]))),
+                    NameError::Taken(_) =>
+                        reject(console, format!("{}\n", fill(messages.name_taken_retry, &[&username]))),
+                    NameError::InvalidCharacters => reject(console, format!("{}\n", messages.name_invalid))
+                },
+                Err(error) => {
+                    writeln!(console, "{}\n", error);
+                    break;
+                }
+            }
+        }
+    }
+
+}
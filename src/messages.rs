@@ -0,0 +1,1086 @@
+use std::fmt;
+
+use crate::game::{Card, CardType, Color, Direction};
+
+// The languages the terminal frontend can be played in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Language
+{
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "en"))]
+    English,
+    #[cfg_attr(feature = "serde", serde(rename = "es"))]
+    Spanish
+}
+
+impl Language
+{
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    // e.g. "en", the way it's chosen on the command line and in the config file
+    pub fn code(self) -> &'static str
+    {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es"
+        }
+    }
+
+    // A language code, or a locale like "es_MX.UTF-8"
+    pub fn from_code(text: &str) -> Option<Language>
+    {
+        let code = text.trim().split(['_', '-', '.', '@']).next().unwrap_or_default().to_ascii_lowercase();
+        Language::ALL.into_iter().find(|language| language.code() == code)
+    }
+
+    // The first locale variable that's set decides, as it does for the C library, and
+    // English is used for any language without a translation
+    pub fn from_env() -> Language
+    {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Language::from_code(&locale))
+            .unwrap_or_default()
+    }
+
+    pub fn messages(self) -> &'static Messages
+    {
+        match self {
+            Language::English => &Messages::ENGLISH,
+            Language::Spanish => &Messages::SPANISH
+        }
+    }
+}
+
+impl fmt::Display for Language
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.code())
+    }
+}
+
+// Put `values` into the places in `template`: "{0}" is the first, "{1}" the second and
+// so on. Sentences are written with numbered places rather than pieced together, so a
+// translation can put the names and numbers wherever its word order needs them
+pub fn fill(template: &str, values: &[&dyn fmt::Display]) -> String
+{
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text += &rest[..start];
+        let place = rest[start + 1..].find('}')
+            .and_then(|end| rest[start + 1..start + 1 + end].parse::<usize>().ok().map(|index| (index, end)));
+        match place {
+            Some((index, end)) if index < values.len() => {
+                text += &values[index].to_string();
+                rest = &rest[start + end + 2..];
+            },
+            _ => {
+                text.push('{');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    text + rest
+}
+
+// Everything the terminal frontend says, in one language. The commands players type,
+// like 'draw' and 'status', are the same in every language, so a script or a habit
+// works whatever the language is. Templates are filled in with `fill`, and the numbers
+// in them are described next to each one when it isn't obvious
+#[derive(Debug, PartialEq, Eq)]
+pub struct Messages
+{
+    // Answers to a yes or no question, the first of each being the one to suggest
+    pub yes: &'static [&'static str],
+    pub no: &'static [&'static str],
+    pub answer_yes_no: &'static str,
+    pub your_choice: &'static str,
+
+    // Cards. Red, Green, Blue and Yellow, in that order
+    pub colors: [&'static str; 4],
+    // The color, then what's on the card
+    pub card: &'static str,
+    pub skip: &'static str,
+    pub reverse: &'static str,
+    pub draw_two: &'static str,
+    pub wildcard: &'static str,
+    pub draw_four: &'static str,
+    // Fancy and compact cards have room for five letters at most
+    pub short_skip: &'static str,
+    pub short_reverse: &'static str,
+    pub short_wild: &'static str,
+    // Marks each color, then wildcards, when colors can't be seen
+    pub color_letters: [char; 5],
+    // After a card drawn since the player's last turn, in a plain hand
+    pub new_mark: &'static str,
+    pub clockwise: &'static str,
+    pub counter_clockwise: &'static str,
+    // For the end of a turn order, after an arrow
+    pub arrow_clockwise: &'static str,
+    pub arrow_counter_clockwise: &'static str,
+    pub one_card: &'static str,
+    pub many_cards: &'static str,
+    // How many cards, then the cards
+    pub described_cards: &'static str,
+
+    // What a player did on a turn
+    pub action_played: &'static str,
+    // What was drawn, then what was played
+    pub action_drew_and_played: &'static str,
+    pub action_drew: &'static str,
+    pub action_penalized: &'static str,
+    pub action_skipped: &'static str,
+
+    // The play prompt
+    pub alias_for: &'static str,
+    pub color_prompt: &'static str,
+    pub color_rejected: &'static str,
+    // ", 'hint'" when hints are on
+    pub commands: &'static str,
+    pub dont_have: &'static str,
+    // Every card but the last, then the last
+    pub which_one: &'static str,
+    pub a_card: &'static str,
+    pub no_player_named: &'static str,
+    pub hint_last_of_color: &'static str,
+    pub hint_one_more: &'static str,
+    // How many, then the color
+    pub hint_more: &'static str,
+    // The card's number, the card and the reason
+    pub hint_play: &'static str,
+    // The card's number, the card and the color
+    pub hint_play_wild: &'static str,
+    pub hint_take_penalty_early: &'static str,
+    pub hint_take_penalty: &'static str,
+    pub hint_must_draw: &'static str,
+    pub hint_draw: &'static str,
+    pub no_hint: &'static str,
+    // The turn number, the player and the direction
+    pub status_turn: &'static str,
+    pub top_card: &'static str,
+    pub status_penalty: &'static str,
+    pub status_players: &'static str,
+    pub status_called_uno: &'static str,
+    // The player, then ", called Uno" or nothing
+    pub status_one_card: &'static str,
+    // The player, how many cards, then ", called Uno" or nothing
+    pub status_many_cards: &'static str,
+    pub history_empty: &'static str,
+    pub history_title: &'static str,
+    pub history_penalized_one: &'static str,
+    pub history_penalized: &'static str,
+    pub history_chose: &'static str,
+    pub jump_notice: &'static str,
+    pub play_many_notice: &'static str,
+    pub penalty_notice: &'static str,
+    pub hint_notice: &'static str,
+    pub no_playable: &'static str,
+    pub one_playable: &'static str,
+    pub many_playable: &'static str,
+    pub prompt_turn: &'static str,
+    pub turn_order: &'static str,
+    pub recently_played: &'static str,
+    pub cards_of: &'static str,
+    pub prompt_uno: &'static str,
+    pub prompt_players: &'static str,
+    pub prompt_look: &'static str,
+    pub prompt_save: &'static str,
+    pub prompt_choose: &'static str,
+    // The turn timer, in whole seconds
+    pub time_left: &'static str,
+    pub time_warning: &'static str,
+    pub time_up: &'static str,
+    pub drew_must_play: &'static str,
+    pub drew_play_it: &'static str,
+    pub challenge_question: &'static str,
+    pub draw_four_warning: &'static str,
+    // The commands, then the short forms
+    pub help: &'static str,
+    pub concede_question: &'static str,
+    pub rules_title: &'static str,
+    pub indices_rejected: &'static str,
+    pub did_you_mean: &'static str,
+    // How many cards there are, then the commands
+    pub card_choice_rejected: &'static str,
+    pub swap_title: &'static str,
+    // The seat, the player and how many cards they have
+    pub swap_option: &'static str,
+    pub choose_listed_player: &'static str,
+    pub index_out_of_range: &'static str,
+    pub must_play: &'static str,
+    // The card picked, then the top card
+    pub unplayable: &'static str,
+    pub cannot_go_out_on_wild: &'static str,
+    pub no_identical: &'static str,
+    pub name_taken: &'static str,
+
+    // Telling everyone what happened
+    pub pass_computer: &'static str,
+    pub nowhere_to_save: &'static str,
+    pub save_failed: &'static str,
+    pub saved: &'static str,
+    pub keep_playing: &'static str,
+    pub save_waiting: &'static str,
+    pub save_unavailable: &'static str,
+    pub hand_sizes: &'static str,
+    // The player, how many copies and the card
+    pub played_copies: &'static str,
+    pub played: &'static str,
+    pub last_card_winner: &'static str,
+    pub swapped: &'static str,
+    pub rotated: &'static str,
+    pub reversed: &'static str,
+    pub new_turn_order: &'static str,
+    // The player, then the cards
+    pub drew_cards: &'static str,
+    pub skipped: &'static str,
+    pub must_stack: &'static str,
+    pub thinking: &'static str,
+    pub wild_color_now: &'static str,
+    pub drew_unplayable_hidden: &'static str,
+    pub drew_playable_hidden: &'static str,
+    pub drew_unplayable: &'static str,
+    pub unable_to_play: &'static str,
+    pub drew_playable: &'static str,
+    pub drew_before_playable: &'static str,
+    pub drew_none_playable: &'static str,
+    pub kept_drawn_hidden: &'static str,
+    pub kept_drawn: &'static str,
+    // The player caught out, then what they drew
+    pub challenge_succeeded: &'static str,
+    pub challenge_failed: &'static str,
+    pub jumped_in: &'static str,
+    pub called_uno: &'static str,
+    // The accuser, the player caught, the player who drew and what they drew
+    pub caught: &'static str,
+    // The player accused, the accuser and what the accuser drew
+    pub false_accusation: &'static str,
+    pub joined: &'static str,
+    pub left: &'static str,
+    pub last_player_standing: &'static str,
+    pub nobody_at_keyboard: &'static str,
+    pub turn_passes: &'static str,
+    pub demo_intro: &'static str,
+    pub demo_stopped: &'static str,
+    pub demo_turn: &'static str,
+    pub turn_limit_reached: &'static str,
+    pub fewest_points_winner: &'static str,
+    pub nobody_wins: &'static str,
+    // The statistics table's headings, over two lines
+    pub stats_headings: [[&'static str; 8]; 2],
+    // The summary after a game, see `cli::print_summary`
+    pub summary_title: &'static str,
+    pub standings_headings: [&'static str; 4],
+    // The winner, then their points
+    pub round_points: &'static str,
+    // The number of turns, then how long the game took
+    pub summary_turns: &'static str,
+    pub draw_for_deal: &'static str,
+    pub drew_for_deal: &'static str,
+    pub starting_game: &'static str,
+    pub put_back: &'static str,
+    pub new_starting_player: &'static str,
+    pub wins_so_far: &'static str,
+    pub play_again: &'static str,
+    pub pass_deal: &'static str,
+    pub move_seats: &'static str,
+    // A match played over several rounds, see `cli::run_match`. The round's number, then
+    // the points that win the match
+    pub match_round: &'static str,
+    pub match_title: &'static str,
+    pub match_headings: [&'static str; 4],
+    // A place in the match, a name, their points from the latest round and in all
+    pub spoken_match_standing: &'static str,
+    pub match_won: &'static str,
+    pub next_round: &'static str,
+    pub between_rounds_options: [&'static str; 3],
+    pub match_nowhere_to_save: &'static str,
+    pub resuming: &'static str,
+    pub log_open_failed: &'static str,
+    pub log_write_failed: &'static str,
+    pub press_enter_to_close: &'static str,
+    pub save_unreadable: &'static str,
+    pub starting_new_game: &'static str,
+    pub resume_question: &'static str,
+    pub resume_failed: &'static str,
+    // A puzzle from a scenario file, see `cli::play_scenario`. The player with the goal,
+    // then how many turns they have
+    pub scenario_title: &'static str,
+    pub goal_win: &'static str,
+    pub goal_win_within: &'static str,
+    pub goal_met: &'static str,
+    pub goal_missed: &'static str,
+    // Playing back a recorded game, see `cli::play_back`. The seed it was dealt from
+    pub replay_intro: &'static str,
+    pub replay_prompt: &'static str,
+    pub replay_auto: &'static str,
+    // The turn asked for, then the first and last turns
+    pub replay_no_turn: &'static str,
+    pub replay_over: &'static str,
+    pub replay_write_failed: &'static str,
+
+    // The lobby menu
+    pub on: &'static str,
+    pub off: &'static str,
+    pub no_turn_limit: &'static str,
+    pub house_rules_title: &'static str,
+    // As they're listed, and then as they're named once one is switched on or off
+    pub house_rules: [&'static str; 15],
+    pub house_rules_toggled: [&'static str; 15],
+    pub choose_rule: &'static str,
+    pub max_turns_prompt: &'static str,
+    pub max_turns_rejected: &'static str,
+    pub stalemate_now: &'static str,
+    pub stacking_now: &'static str,
+    pub rule_rejected: &'static str,
+    // The rule, then on or off
+    pub rule_now: &'static str,
+    // Only the same kind, a Draw Four on a Draw Two, anything, and a custom set
+    pub stacking_policies: [&'static str; 4],
+    // The lowest hand wins, then a draw
+    pub stalemate_policies: [&'static str; 2],
+    pub seating_title: &'static str,
+    pub seat_rejected: &'static str,
+    pub move_which: &'static str,
+    pub to_which_seat: &'static str,
+    pub choose_bot: &'static str,
+    pub bot_rejected: &'static str,
+    pub choose_difficulty: &'static str,
+    pub difficulty_rejected: &'static str,
+    pub bot_name_prompt: &'static str,
+    // The name, the bot and the difficulty
+    pub added_bot: &'static str,
+    pub bad_bot_name: &'static str,
+    pub choose_bot_names: &'static str,
+    pub bot_names_chosen: &'static str,
+    pub choice_rejected: &'static str,
+    pub lobby_intro: &'static str,
+    pub lobby_title: &'static str,
+    pub lobby_options: [&'static str; 8],
+    pub choose_option: &'static str,
+    pub lobby_full: &'static str,
+    pub lobby_rejected: &'static str,
+    pub shuffled: &'static str,
+    pub username_prompt: &'static str,
+    pub added_player: &'static str,
+    pub name_empty: &'static str,
+    pub name_too_long: &'static str,
+    pub name_taken_retry: &'static str,
+    pub name_invalid: &'static str,
+
+    // The accessible mode, for a screen reader. The card's number, then the card
+    pub spoken_card: &'static str,
+    pub spoken_playable_card: &'static str,
+    // Said after a card drawn since the player's last turn
+    pub spoken_new_card: &'static str,
+    pub spoken_current_player: &'static str,
+    pub spoken_next: &'static str,
+    // A name, then how many cards they have
+    pub spoken_player_cards: &'static str,
+    // Between the items of a list read out in order
+    pub spoken_then: &'static str,
+    // The direction's arrow word
+    pub spoken_direction: &'static str,
+    // A wildcard, then the color chosen for it
+    pub spoken_wild_color: &'static str,
+    pub spoken_turn: &'static str,
+    // A name, then their cards
+    pub spoken_holds: &'static str,
+    // A name, then what's counted
+    pub spoken_stats: &'static str,
+    // A heading from `stats_headings`, then the count
+    pub spoken_stat: &'static str,
+    // A place in the standings, a name, their cards and the points in their hand
+    pub spoken_standing: &'static str,
+
+    // The tutorial, see `tutorial::Tutorial`. The tutor's name
+    pub tutorial_intro: &'static str,
+    // For the player when nobody at the keyboard has been seated yet
+    pub tutorial_student: &'static str,
+    pub tutorial_tutor: &'static str,
+    // The card's number, then the card
+    pub tutorial_try_card: &'static str,
+    pub tutorial_wrong_card: &'static str,
+    pub tutorial_try_color: &'static str,
+    pub tutorial_wrong_color: &'static str,
+    pub tutorial_try_uno: &'static str,
+    pub tutorial_wrong_uno: &'static str,
+    pub tutorial_done: &'static str,
+    pub lesson_color: &'static str,
+    pub lesson_number: &'static str,
+    pub lesson_skip: &'static str,
+    pub lesson_wild: &'static str,
+    pub lesson_wild_color: &'static str,
+    pub lesson_draw_two: &'static str,
+    pub lesson_uno: &'static str,
+    pub lesson_last_card: &'static str
+}
+
+impl Messages
+{
+    pub const ENGLISH: Messages = Messages {
+        yes: &["y", "yes"],
+        no: &["n", "no"],
+        answer_yes_no: "Please answer 'y' or 'n'",
+        your_choice: "Your choice: ",
+
+        colors: ["Red", "Green", "Blue", "Yellow"],
+        card: "{0} {1}",
+        skip: "Skip",
+        reverse: "Reverse",
+        draw_two: "Draw 2",
+        wildcard: "Wildcard",
+        draw_four: "Draw 4 Wildcard",
+        short_skip: "Skip",
+        short_reverse: "Rev",
+        short_wild: "Wild",
+        color_letters: ['R', 'G', 'B', 'Y', 'W'],
+        new_mark: "(new)",
+        clockwise: "Clockwise",
+        counter_clockwise: "Counter Clockwise",
+        arrow_clockwise: "clockwise",
+        arrow_counter_clockwise: "counter-clockwise",
+        one_card: "1 card",
+        many_cards: "{0} cards",
+        described_cards: "{0} cards: {1}",
+
+        action_played: "played a {0}",
+        action_drew_and_played: "drew {0} and played a {1}",
+        action_drew: "drew {0}",
+        action_penalized: "was penalized {0}",
+        action_skipped: "was skipped",
+
+        alias_for: "'{0}' for '{1}'",
+        color_prompt: "Select a color for the wildcard:",
+        color_rejected: "Enter a value between 1 and 4, or the name of a color!",
+        commands: "'draw', 'sort', 'uno', 'catch <name>', 'jump <name>', 'join <name>', 'leave <name>', 'quit', \
+            'save', 'history', 'status', 'rules', 'help'{0}",
+        dont_have: "You don't have a {0}",
+        which_one: "Which one? You have {0} and {1}",
+        a_card: "a {0}",
+        no_player_named: "There is no player named '{0}'",
+        hint_last_of_color: "it's your last {0} card",
+        hint_one_more: "you have 1 more {0} card",
+        hint_more: "you have {0} more {1} cards",
+        hint_play: "Hint: play card {0} ({1}), {2}",
+        hint_play_wild: "Hint: play card {0} ({1}) and choose {2}, since you have the most {2} cards",
+        hint_take_penalty_early: "Hint: type 'draw' to take the penalty and keep your cards for later",
+        hint_take_penalty: "Hint: type 'draw' to take the penalty, you have nothing to stack on it",
+        hint_must_draw: "Hint: type 'draw', nothing in your hand is playable so that's all you can do",
+        hint_draw: "Hint: type 'draw' to draw a card",
+        no_hint: "There is nothing to suggest right now",
+        status_turn: "Turn {0}: It's {1}'s turn, and play goes {2}",
+        top_card: "The top card is a {0}",
+        status_penalty: "A penalty of {0} cards is waiting to be stacked on or drawn",
+        status_players: "Players in turn order:",
+        status_called_uno: ", called Uno",
+        status_one_card: "{0}: 1 card{1}",
+        status_many_cards: "{0}: {1} cards{2}",
+        history_empty: "Nobody has had a turn yet",
+        history_title: "The last few turns:",
+        history_penalized_one: "drew a penalty card and lost their turn",
+        history_penalized: "drew {0} penalty cards and lost their turn",
+        history_chose: " and chose {0}",
+        jump_notice: "Anyone holding an identical card can type 'jump <name>' to jump in",
+        play_many_notice: "Identical cards can be played together by separating them with commas, like 3,5",
+        penalty_notice: "You must stack a draw card or type 'draw' to take the {0} card penalty",
+        hint_notice: "Type 'hint' for a suggestion",
+        no_playable: "No playable cards, so you must draw",
+        one_playable: "1 playable card",
+        many_playable: "{0} playable cards",
+        prompt_turn: "Turn {0}: It's {1}'s turn!",
+        turn_order: "Turn order: {0}",
+        recently_played: "Recently played: {0}",
+        cards_of: "{0}'s Cards:",
+        prompt_uno: "Type 'uno' to call Uno, or 'catch <name>' to catch a player who forgot to",
+        prompt_players: "Type 'join <name>' to add a player, 'leave <name>' if a player has to leave, or 'quit' to \
+            concede",
+        prompt_look: "Type 'status', 'history' or 'rules' to look over the game, or 'sort' to put your cards in order",
+        prompt_save: "Type 'save' to finish the game another time",
+        prompt_choose: "Choose a card or type 'draw': ",
+        time_left: "{0} seconds left",
+        time_warning: "Only {0} seconds left!",
+        time_up: "Time's up for {0}!",
+        drew_must_play: "You drew a {0}! It's playable on the current card, so you must play it!",
+        drew_play_it: "You drew a {0}! It's playable on the current card. Play it? (y/n): ",
+        challenge_question: "{0}, a Draw Four was played on you. Challenge it? (y/n): ",
+        draw_four_warning: "You hold a matching color, so playing this Draw Four can be challenged. Play it anyway? \
+            (y/n): ",
+        help: "You can type a card's number, a card like 'red 5', or one of {0}\nShort forms work too: {1}",
+        concede_question: "Concede the game? Your cards will leave play (y/n): ",
+        rules_title: "The rules of this game:",
+        indices_rejected: "Please enter card indices separated by commas, like 3,5",
+        did_you_mean: "Did you mean '{0}'?",
+        card_choice_rejected: "Please enter a card index in the range 1 - {0}, a card like 'red 5' or 'wild blue', or \
+            one of {1}",
+        swap_title: "Choose a player to swap hands with:",
+        swap_option: "{0}. {1} ({2} cards)",
+        choose_listed_player: "Please choose one of the players listed above!",
+        index_out_of_range: "Please enter a card index in the range 1 - {0}, or type 'draw' to draw",
+        must_play: "You have a playable card, so you can't draw! You could play card {0}",
+        unplayable: "The {0} you picked cannot be played on a {1}. Select a different card or choose the 'draw' option",
+        cannot_go_out_on_wild: "Your last card can't be a {0} in this game, so you'll have to draw instead",
+        no_identical: "{0} has no card identical to the {1} on top of the pile",
+        name_taken: "Username '{0}' is already taken",
+
+        pass_computer: "Pass the computer to {0}, then press Enter",
+        nowhere_to_save: "There's nowhere to save the game to. Try 'save <file>'",
+        save_failed: "Couldn't save the game to {0}: {1}",
+        saved: "Saved the game to {0}",
+        keep_playing: "Keep playing? (y/n): ",
+        save_waiting: "The game will be waiting for you next time!",
+        save_unavailable: "Games can only be saved with the serde feature",
+        hand_sizes: "Hand sizes: {0}",
+        played_copies: "{0} played {1} copies of the {2}!",
+        played: "{0} played a {1}!",
+        last_card_winner: "{0} has played their last card! They are the winner!",
+        swapped: "{0} swapped hands with {1}!",
+        rotated: "Everyone passed their hand to the next player!",
+        reversed: "Reversing the turn direction! The new direction is {0}",
+        new_turn_order: "New turn order: {0}",
+        drew_cards: "{0} drew {1}",
+        skipped: "{0} had their turn skipped!",
+        must_stack: "{0} must stack a draw card or draw {1} cards!",
+        thinking: "{0} is thinking...",
+        wild_color_now: "The wildcard color is now {0}",
+        drew_unplayable_hidden: "{0} drew a card and was unable to play it! Their turn is over",
+        drew_playable_hidden: "{0} drew a playable card",
+        drew_unplayable: "You drew a {0}! It's not playable on the current card!",
+        unable_to_play: "{0} was unable to play a card! Their turn is over",
+        drew_playable: "You drew a {0}! It's playable on the current card!",
+        drew_before_playable: "{0} drew {1} cards before finding a playable one",
+        drew_none_playable: "{0} drew {1} cards without finding a playable one! Their turn is over",
+        kept_drawn_hidden: "{0} kept the card they drew. Their turn is over",
+        kept_drawn: "{0} kept the {1}. Their turn is over",
+        challenge_succeeded: "The challenge succeeded! {0} held a matching card and drew {1}",
+        challenge_failed: "The challenge failed! {0} drew {1}",
+        jumped_in: "{0} jumped in on {1}'s turn!",
+        called_uno: "{0} called Uno!",
+        caught: "{0} caught {1} without calling Uno! {2} drew {3}",
+        false_accusation: "{0} doesn't need to call Uno! {1} drew {2} for the false accusation",
+        joined: "{0} joined the game and will play next!",
+        left: "{0} left the game! Their {1} cards are out of play",
+        last_player_standing: "{0} is the last player left! They are the winner!",
+        nobody_at_keyboard: "Nobody is left at the keyboard, so the game is over. Thanks for playing!",
+        turn_passes: "The turn passes to {0}",
+        demo_intro: "Watching the bots play! Type q and press enter to stop\nReplay this game with --demo --seed {0}",
+        demo_stopped: "Stopped the demo",
+        demo_turn: "Turn {0}, the top card is a {1}",
+        turn_limit_reached: "The turn limit has been reached!",
+        fewest_points_winner: "{0} has the fewest points in their hand! They are the winner!",
+        nobody_wins: "Nobody wins this game",
+        stats_headings: [
+            ["Player", "Played", "Drawn", "Skipped", "Penalized", "Penalties", "Wilds", "Longest"],
+            ["", "", "", "", "", "dealt", "", "streak"]
+        ],
+        summary_title: "How the game went:",
+        standings_headings: ["", "Player", "Cards", "Points"],
+        round_points: "{0} scores {1} points for the cards left in everyone else's hands",
+        summary_turns: "{0} turns in {1}",
+        draw_for_deal: "Drawing for the deal, the highest number goes first:",
+        drew_for_deal: "{0} drew a {1}",
+        starting_game: "Starting the game! The starting player is {0}",
+        put_back: "A {0} was turned over and put back into the deck",
+        new_starting_player: "The new starting player is {0}",
+        wins_so_far: "Wins so far: {0}",
+        play_again: "Play again with the same players? (y/n): ",
+        pass_deal: "Pass the deal to the next player? (y/n): ",
+        move_seats: "Move everyone one seat along? (y/n): ",
+        match_round: "Round {0}! The first to {1} points wins the match",
+        match_title: "The match so far:",
+        match_headings: ["", "Player", "This round", "Total"],
+        spoken_match_standing: "{0}. {1}: {2} points this round, {3} in all.",
+        match_won: "{0} wins the match with {1} points!",
+        next_round: "Next round: {0} deals",
+        between_rounds_options: ["Play the next round", "Show the standings", "Save the match and stop"],
+        match_nowhere_to_save: "There's nowhere to save the match to",
+        resuming: "Picking the game back up on turn {0}! It's {1}'s turn",
+        log_open_failed: "Couldn't open the log file {0}: {1}",
+        log_write_failed: "Couldn't write all of the game to the log file {0}: {1}",
+        press_enter_to_close: "Press enter to close the program...",
+        save_unreadable: "Couldn't read the saved game in {0}: {1}",
+        starting_new_game: "Starting a new game instead",
+        resume_question: "Resume the saved game from {0}? (y/n): ",
+        resume_failed: "Couldn't resume the saved game: {0}",
+        scenario_title: "Puzzle: {0}",
+        goal_win: "Goal: {0} has to win",
+        goal_win_within: "Goal: {0} has to win within {1} turn(s)",
+        goal_met: "Goal met! Puzzle solved",
+        goal_missed: "The goal wasn't met. Give it another go!",
+        replay_intro: "Playing back a game dealt with seed {0}",
+        replay_prompt: "Press enter for the next turn, or type a turn to go to, 'a' to play on by itself or 'q' to \
+            stop: ",
+        replay_auto: "Playing on by itself. Type 'a' and press enter to pause",
+        replay_no_turn: "There's no turn {0}. The turns go from {1} to {2}",
+        replay_over: "That's the end of the replay",
+        replay_write_failed: "Couldn't write the replay to {0}: {1}",
+
+        on: "on",
+        off: "off",
+        no_turn_limit: "none",
+        house_rules_title: "House rules:",
+        house_rules: [
+            "Draw card stacking", "Draw Four challenges", "Forced play", "Jump in", "Seven-zero",
+            "Penalize false Uno accusations", "Automatically play drawn cards", "Draw until playable",
+            "Strict color matching", "Turn limit", "When the turn limit is reached", "Play identical cards together",
+            "What can be stacked", "No going out on a wildcard", "Draw for the deal to choose who goes first"
+        ],
+        house_rules_toggled: [
+            "Draw card stacking", "Draw Four challenges", "Forced play", "Jump in", "Seven-zero",
+            "Penalizing false Uno accusations", "Automatically playing drawn cards", "Drawing until playable",
+            "Strict color matching", "Turn limit", "When the turn limit is reached", "Playing identical cards together",
+            "What can be stacked", "No going out on a wildcard", "Drawing for the deal"
+        ],
+        choose_rule: "Choose a rule to toggle or press enter to go back: ",
+        max_turns_prompt: "Enter the maximum number of turns, or press enter for no limit: ",
+        max_turns_rejected: "Please enter a number of turns greater than 0!",
+        stalemate_now: "When the turn limit is reached {0}",
+        stacking_now: "Stacking now allows {0}",
+        rule_rejected: "Please enter an option in the range 1 - 15!",
+        rule_now: "{0} is now {1}",
+        stacking_policies: [
+            "only the same kind of draw card", "a Draw Four on a Draw Two", "any draw card on any other",
+            "a custom set of draw cards"
+        ],
+        stalemate_policies: ["the lowest hand wins", "the game is a draw"],
+        seating_title: "Seating order:",
+        seat_rejected: "Please enter a seat in the range 1 - {0}!",
+        move_which: "Move which player? ",
+        to_which_seat: "To which seat? ",
+        choose_bot: "Choose a bot:",
+        bot_rejected: "Please enter a bot in the range 1 - {0}!",
+        choose_difficulty: "Choose a difficulty:",
+        difficulty_rejected: "Please enter a difficulty in the range 1 - {0}!",
+        bot_name_prompt: "Enter a name for the bot, or press enter to use the {0} names: ",
+        added_bot: "Added {0} the {1} bot ({2})!",
+        bad_bot_name: "'{0}' can't be used as a name. Please choose a different name",
+        choose_bot_names: "Choose where bot names come from:",
+        bot_names_chosen: "Bots will use the {0} names",
+        choice_rejected: "Please enter a choice in the range 1 - {0}!",
+        lobby_intro: "To start the game, you must add at least 2 players, then select 'start'",
+        lobby_title: "Select an option:",
+        lobby_options: [
+            "Add a player", "Start the game", "Reorder players", "Shuffle seats", "House rules", "Add a bot",
+            "Bot names", "Learn to play"
+        ],
+        choose_option: "Choose an option: ",
+        lobby_full: "The lobby is full! A game can have at most {0} players",
+        lobby_rejected: "Please enter an option in the range 1 - 8!",
+        shuffled: "Shuffled the seats!",
+        username_prompt: "Enter a username: ",
+        added_player: "Added player {0}!",
+        name_empty: "Your username cannot be empty. Please enter a username",
+        name_too_long: "Usernames can be at most {0} characters long. Please choose a shorter username",
+        name_taken_retry: "Username '{0}' is already taken. Please choose a different username",
+        name_invalid: "Usernames cannot contain control characters. Please choose a different username",
+
+        spoken_card: "Card {0}: {1}.",
+        spoken_playable_card: "Card {0}: {1}, playable.",
+        spoken_new_card: "Just drawn.",
+        spoken_current_player: "Current player: {0}.",
+        spoken_next: "Next: {0}.",
+        spoken_player_cards: "{0} with {1}",
+        spoken_then: ", then ",
+        spoken_direction: "Play goes {0}.",
+        spoken_wild_color: "{0}, with {1} chosen",
+        spoken_turn: "Turn {0}",
+        spoken_holds: "{0} holds {1}.",
+        spoken_stats: "{0}: {1}.",
+        spoken_stat: "{0} {1}",
+        spoken_standing: "{0}. {1}: {2} cards, {3} points.",
+
+        tutorial_intro: "Welcome to the tutorial! You'll play a short game against {0}, with the cards set up to show \
+            you how Uno works. Follow along by doing what each lesson asks",
+        tutorial_student: "Student",
+        tutorial_tutor: "Tutor",
+        tutorial_try_card: "Try playing card {0}: ",
+        tutorial_wrong_card: "Not this time. This lesson is about card {0}, the {1}",
+        tutorial_try_color: "Try choosing {0}: ",
+        tutorial_wrong_color: "Not this time. This lesson is about choosing {0}",
+        tutorial_try_uno: "Type 'uno' to call it: ",
+        tutorial_wrong_uno: "Type 'uno', before anyone catches you!",
+        tutorial_done: "That's the tutorial done! You know everything you need for a real game",
+        lesson_color: "Lesson: a card can go on the top card when they're the same color",
+        lesson_number: "Lesson: a card can also go on the top card when they have the same number, whatever their \
+            colors",
+        lesson_skip: "Lesson: a Skip makes the next player miss their turn. With only two players, that means you go \
+            again",
+        lesson_wild: "Lesson: a Wildcard can go on anything, and you choose the color that has to be played next",
+        lesson_wild_color: "Lesson: choose the color you have the most of, so you have something to play next time",
+        lesson_draw_two: "Lesson: a Draw 2 makes the next player draw two cards and miss their turn",
+        lesson_uno: "Lesson: you're down to one card, so call Uno! If somebody catches you first, you draw two cards",
+        lesson_last_card: "Lesson: the first player to play their last card wins the game"
+    };
+
+    pub const SPANISH: Messages = Messages {
+        yes: &["s", "si", "sí", "y", "yes"],
+        no: &["n", "no"],
+        answer_yes_no: "Responde 's' o 'n'",
+        your_choice: "Tu elección: ",
+
+        colors: ["Rojo", "Verde", "Azul", "Amarillo"],
+        card: "{1} {0}",
+        skip: "Salta",
+        reverse: "Reversa",
+        draw_two: "Roba 2",
+        wildcard: "Comodín",
+        draw_four: "Comodín Roba 4",
+        short_skip: "Salta",
+        short_reverse: "Rev",
+        short_wild: "Comod",
+        // Azul and Amarillo both start with A, so Amarillo is M
+        color_letters: ['R', 'V', 'A', 'M', 'C'],
+        new_mark: "(nueva)",
+        clockwise: "en el sentido del reloj",
+        counter_clockwise: "en sentido contrario al reloj",
+        arrow_clockwise: "horario",
+        arrow_counter_clockwise: "antihorario",
+        one_card: "1 carta",
+        many_cards: "{0} cartas",
+        described_cards: "{0} cartas: {1}",
+
+        action_played: "jugó la carta {0}",
+        action_drew_and_played: "robó {0} y jugó la carta {1}",
+        action_drew: "robó {0}",
+        action_penalized: "fue penalizado con {0}",
+        action_skipped: "perdió el turno",
+
+        alias_for: "'{0}' para '{1}'",
+        color_prompt: "Elige un color para el comodín:",
+        color_rejected: "¡Escribe un número del 1 al 4, o el nombre de un color!",
+        commands: "'draw' (robar), 'sort' (ordenar), 'uno', 'catch <nombre>' (pillar), 'jump <nombre>' (colarse), \
+            'join <nombre>' (unirse), 'leave <nombre>' (salir), 'quit' (rendirse), 'save' (guardar), \
+            'history' (historial), 'status' (estado), 'rules' (reglas), 'help' (ayuda){0}",
+        dont_have: "No tienes la carta {0}",
+        which_one: "¿Cuál? Tienes {0} y {1}",
+        a_card: "la carta {0}",
+        no_player_named: "No hay ningún jugador llamado '{0}'",
+        hint_last_of_color: "es tu última carta de color {0}",
+        hint_one_more: "te queda 1 carta más de color {0}",
+        hint_more: "te quedan {0} cartas más de color {1}",
+        hint_play: "Pista: juega la carta {0} ({1}), {2}",
+        hint_play_wild: "Pista: juega la carta {0} ({1}) y elige {2}, ya que tienes más cartas de color {2}",
+        hint_take_penalty_early: "Pista: escribe 'draw' para aceptar la penalización y guardar tus cartas para después",
+        hint_take_penalty: "Pista: escribe 'draw' para aceptar la penalización, no tienes nada que acumular",
+        hint_must_draw: "Pista: escribe 'draw', no puedes jugar ninguna carta así que es lo único que puedes hacer",
+        hint_draw: "Pista: escribe 'draw' para robar una carta",
+        no_hint: "Ahora mismo no hay nada que sugerir",
+        status_turn: "Turno {0}: le toca a {1}, y el juego va {2}",
+        top_card: "La carta de arriba es {0}",
+        status_penalty: "Hay una penalización de {0} cartas esperando a que alguien acumule o robe",
+        status_players: "Jugadores en orden de turno:",
+        status_called_uno: ", ha cantado Uno",
+        status_one_card: "{0}: 1 carta{1}",
+        status_many_cards: "{0}: {1} cartas{2}",
+        history_empty: "Todavía nadie ha jugado un turno",
+        history_title: "Los últimos turnos:",
+        history_penalized_one: "robó una carta de penalización y perdió el turno",
+        history_penalized: "robó {0} cartas de penalización y perdió el turno",
+        history_chose: " y eligió {0}",
+        jump_notice: "Quien tenga una carta idéntica puede escribir 'jump <nombre>' para colarse",
+        play_many_notice: "Las cartas idénticas se pueden jugar juntas separándolas con comas, como 3,5",
+        penalty_notice: "Tienes que acumular una carta de robar o escribir 'draw' para aceptar la penalización de {0} \
+            cartas",
+        hint_notice: "Escribe 'hint' para pedir una pista",
+        no_playable: "No puedes jugar ninguna carta, así que tienes que robar",
+        one_playable: "1 carta jugable",
+        many_playable: "{0} cartas jugables",
+        prompt_turn: "Turno {0}: ¡le toca a {1}!",
+        turn_order: "Orden de turno: {0}",
+        recently_played: "Jugadas recientes: {0}",
+        cards_of: "Cartas de {0}:",
+        prompt_uno: "Escribe 'uno' para cantar Uno, o 'catch <nombre>' para pillar a quien se le olvidó",
+        prompt_players: "Escribe 'join <nombre>' para añadir un jugador, 'leave <nombre>' si alguien tiene que irse, o \
+            'quit' para rendirte",
+        prompt_look: "Escribe 'status', 'history' o 'rules' para repasar la partida, o 'sort' para ordenar tus cartas",
+        prompt_save: "Escribe 'save' para terminar la partida en otro momento",
+        prompt_choose: "Elige una carta o escribe 'draw': ",
+        time_left: "Quedan {0} segundos",
+        time_warning: "¡Solo quedan {0} segundos!",
+        time_up: "¡Se acabó el tiempo de {0}!",
+        drew_must_play: "¡Robaste la carta {0}! Se puede jugar sobre la carta actual, ¡así que tienes que jugarla!",
+        drew_play_it: "¡Robaste la carta {0}! Se puede jugar sobre la carta actual. ¿La juegas? (s/n): ",
+        challenge_question: "{0}, te han jugado un Roba 4. ¿Lo desafías? (s/n): ",
+        draw_four_warning: "Tienes una carta del color en juego, así que este Roba 4 se puede desafiar. ¿Lo juegas \
+            igualmente? (s/n): ",
+        help: "Puedes escribir el número de una carta, una carta como 'red 5', o uno de estos: {0}\nTambién valen las \
+            formas cortas: {1}",
+        concede_question: "¿Te rindes? Tus cartas saldrán del juego (s/n): ",
+        rules_title: "Las reglas de esta partida:",
+        indices_rejected: "Escribe los números de las cartas separados por comas, como 3,5",
+        did_you_mean: "¿Querías decir '{0}'?",
+        card_choice_rejected: "Escribe un número de carta del 1 al {0}, una carta como 'red 5' o 'wild blue', o uno de \
+            estos: {1}",
+        swap_title: "Elige un jugador con quien intercambiar la mano:",
+        swap_option: "{0}. {1} ({2} cartas)",
+        choose_listed_player: "¡Elige uno de los jugadores de la lista!",
+        index_out_of_range: "Escribe un número de carta del 1 al {0}, o 'draw' para robar",
+        must_play: "Tienes una carta jugable, ¡así que no puedes robar! Podrías jugar la carta {0}",
+        unplayable: "La carta {0} que elegiste no se puede jugar sobre la carta {1}. Elige otra carta o la opción \
+            'draw'",
+        cannot_go_out_on_wild: "Tu última carta no puede ser la carta {0} en esta partida, así que tendrás que robar",
+        no_identical: "{0} no tiene ninguna carta idéntica a la carta {1} de arriba del montón",
+        name_taken: "El nombre '{0}' ya está en uso",
+
+        pass_computer: "Pásale el ordenador a {0} y pulsa Intro",
+        nowhere_to_save: "No hay dónde guardar la partida. Prueba con 'save <archivo>'",
+        save_failed: "No se pudo guardar la partida en {0}: {1}",
+        saved: "Partida guardada en {0}",
+        keep_playing: "¿Seguir jugando? (s/n): ",
+        save_waiting: "¡La partida te estará esperando la próxima vez!",
+        save_unavailable: "Las partidas solo se pueden guardar con la característica serde",
+        hand_sizes: "Cartas en la mano: {0}",
+        played_copies: "¡{0} jugó {1} copias de la carta {2}!",
+        played: "¡{0} jugó la carta {1}!",
+        last_card_winner: "¡{0} ha jugado su última carta! ¡Es quien gana!",
+        swapped: "¡{0} intercambió la mano con {1}!",
+        rotated: "¡Todos pasaron su mano al siguiente jugador!",
+        reversed: "¡Cambia el sentido del juego! Ahora va {0}",
+        new_turn_order: "Nuevo orden de turno: {0}",
+        drew_cards: "{0} robó {1}",
+        skipped: "¡{0} perdió su turno!",
+        must_stack: "¡{0} tiene que acumular una carta de robar o robar {1} cartas!",
+        thinking: "{0} está pensando...",
+        wild_color_now: "El color del comodín ahora es {0}",
+        drew_unplayable_hidden: "¡{0} robó una carta y no pudo jugarla! Se acabó su turno",
+        drew_playable_hidden: "{0} robó una carta jugable",
+        drew_unplayable: "¡Robaste la carta {0}! ¡No se puede jugar sobre la carta actual!",
+        unable_to_play: "¡{0} no pudo jugar ninguna carta! Se acabó su turno",
+        drew_playable: "¡Robaste la carta {0}! ¡Se puede jugar sobre la carta actual!",
+        drew_before_playable: "{0} robó {1} cartas antes de encontrar una jugable",
+        drew_none_playable: "¡{0} robó {1} cartas sin encontrar ninguna jugable! Se acabó su turno",
+        kept_drawn_hidden: "{0} se quedó la carta que robó. Se acabó su turno",
+        kept_drawn: "{0} se quedó la carta {1}. Se acabó su turno",
+        challenge_succeeded: "¡El desafío tuvo éxito! {0} tenía una carta que coincidía y robó {1}",
+        challenge_failed: "¡El desafío fracasó! {0} robó {1}",
+        jumped_in: "¡{0} se coló en el turno de {1}!",
+        called_uno: "¡{0} cantó Uno!",
+        caught: "¡{0} pilló a {1} sin cantar Uno! {2} robó {3}",
+        false_accusation: "¡{0} no necesita cantar Uno! {1} robó {2} por la acusación falsa",
+        joined: "¡{0} se unió a la partida y juega a continuación!",
+        left: "¡{0} dejó la partida! Sus {1} cartas quedan fuera del juego",
+        last_player_standing: "¡{0} es el último jugador que queda! ¡Es quien gana!",
+        nobody_at_keyboard: "No queda nadie al teclado, así que se acabó la partida. ¡Gracias por jugar!",
+        turn_passes: "El turno pasa a {0}",
+        demo_intro: "¡Mirando jugar a los bots! Escribe q y pulsa Intro para parar\nRepite esta partida con --demo \
+            --seed {0}",
+        demo_stopped: "Demostración detenida",
+        demo_turn: "Turno {0}, la carta de arriba es {1}",
+        turn_limit_reached: "¡Se ha alcanzado el límite de turnos!",
+        fewest_points_winner: "¡{0} tiene menos puntos en la mano! ¡Es quien gana!",
+        nobody_wins: "Nadie gana esta partida",
+        stats_headings: [
+            ["Jugador", "Jugadas", "Robadas", "Saltado", "Penalizado", "Penalizaciones", "Comodines", "Racha"],
+            ["", "", "", "", "", "dadas", "", "más larga"]
+        ],
+        summary_title: "Cómo fue la partida:",
+        standings_headings: ["", "Jugador", "Cartas", "Puntos"],
+        round_points: "{0} suma {1} puntos por las cartas que quedan en las manos de los demás",
+        summary_turns: "{0} turnos en {1}",
+        draw_for_deal: "Se roba para repartir, el número más alto empieza:",
+        drew_for_deal: "{0} robó la carta {1}",
+        starting_game: "¡Empieza la partida! Empieza {0}",
+        put_back: "Se dio la vuelta a la carta {0} y se devolvió al mazo",
+        new_starting_player: "Ahora empieza {0}",
+        wins_so_far: "Victorias hasta ahora: {0}",
+        play_again: "¿Jugar otra vez con los mismos jugadores? (s/n): ",
+        pass_deal: "¿Pasar el reparto al siguiente jugador? (s/n): ",
+        move_seats: "¿Mover a todos un asiento? (s/n): ",
+        match_round: "¡Ronda {0}! Gana la partida quien llegue antes a {1} puntos",
+        match_title: "La partida hasta ahora:",
+        match_headings: ["", "Jugador", "Esta ronda", "Total"],
+        spoken_match_standing: "{0}. {1}: {2} puntos en esta ronda, {3} en total.",
+        match_won: "¡{0} gana la partida con {1} puntos!",
+        next_round: "Siguiente ronda: reparte {0}",
+        between_rounds_options: ["Jugar la siguiente ronda", "Ver la clasificación", "Guardar la partida y parar"],
+        match_nowhere_to_save: "No hay dónde guardar la partida",
+        resuming: "¡Retomando la partida en el turno {0}! Le toca a {1}",
+        log_open_failed: "No se pudo abrir el archivo de registro {0}: {1}",
+        log_write_failed: "No se pudo escribir toda la partida en el archivo de registro {0}: {1}",
+        press_enter_to_close: "Pulsa Intro para cerrar el programa...",
+        save_unreadable: "No se pudo leer la partida guardada en {0}: {1}",
+        starting_new_game: "Se empieza una partida nueva",
+        resume_question: "¿Retomar la partida guardada el {0}? (s/n): ",
+        resume_failed: "No se pudo retomar la partida guardada: {0}",
+        scenario_title: "Desafío: {0}",
+        goal_win: "Objetivo: {0} tiene que ganar",
+        goal_win_within: "Objetivo: {0} tiene que ganar en {1} turno(s) como mucho",
+        goal_met: "¡Objetivo cumplido! Desafío resuelto",
+        goal_missed: "No se cumplió el objetivo. ¡Inténtalo otra vez!",
+        replay_intro: "Reproduciendo una partida repartida con la semilla {0}",
+        replay_prompt: "Pulsa Intro para el siguiente turno, o escribe un turno al que ir, 'a' para que siga sola o \
+            'q' para parar: ",
+        replay_auto: "Sigue sola. Escribe 'a' y pulsa Intro para pausar",
+        replay_no_turn: "No hay turno {0}. Los turnos van del {1} al {2}",
+        replay_over: "Fin de la repetición",
+        replay_write_failed: "No se pudo escribir la repetición en {0}: {1}",
+
+        on: "activada",
+        off: "desactivada",
+        no_turn_limit: "ninguno",
+        house_rules_title: "Reglas de la casa:",
+        house_rules: [
+            "Acumular cartas de robar", "Desafíos al Roba 4", "Juego obligatorio", "Colarse", "Siete-cero",
+            "Penalizar acusaciones falsas de Uno", "Jugar automáticamente las cartas robadas", "Robar hasta poder jugar",
+            "Color estricto", "Límite de turnos", "Al alcanzar el límite de turnos", "Jugar cartas idénticas juntas",
+            "Qué se puede acumular", "No terminar con un comodín", "Robar para decidir quién empieza"
+        ],
+        house_rules_toggled: [
+            "Acumular cartas de robar", "Desafíos al Roba 4", "Juego obligatorio", "Colarse", "Siete-cero",
+            "Penalizar acusaciones falsas de Uno", "Jugar automáticamente las cartas robadas", "Robar hasta poder jugar",
+            "Color estricto", "Límite de turnos", "Al alcanzar el límite de turnos", "Jugar cartas idénticas juntas",
+            "Qué se puede acumular", "No terminar con un comodín", "Robar para decidir quién empieza"
+        ],
+        choose_rule: "Elige una regla para cambiarla o pulsa Intro para volver: ",
+        max_turns_prompt: "Escribe el número máximo de turnos, o pulsa Intro para no poner límite: ",
+        max_turns_rejected: "¡Escribe un número de turnos mayor que 0!",
+        stalemate_now: "Al alcanzar el límite de turnos {0}",
+        stacking_now: "Ahora se puede acumular {0}",
+        rule_rejected: "¡Escribe una opción del 1 al 15!",
+        rule_now: "{0}: {1}",
+        stacking_policies: [
+            "solo el mismo tipo de carta de robar", "un Roba 4 sobre un Roba 2", "cualquier carta de robar sobre otra",
+            "un conjunto personalizado de cartas de robar"
+        ],
+        stalemate_policies: ["gana la mano más baja", "la partida acaba en empate"],
+        seating_title: "Orden de los asientos:",
+        seat_rejected: "¡Escribe un asiento del 1 al {0}!",
+        move_which: "¿A qué jugador quieres mover? ",
+        to_which_seat: "¿A qué asiento? ",
+        choose_bot: "Elige un bot:",
+        bot_rejected: "¡Escribe un bot del 1 al {0}!",
+        choose_difficulty: "Elige una dificultad:",
+        difficulty_rejected: "¡Escribe una dificultad del 1 al {0}!",
+        bot_name_prompt: "Escribe un nombre para el bot, o pulsa Intro para usar los nombres {0}: ",
+        added_bot: "¡Se añadió a {0}, el bot {1} ({2})!",
+        bad_bot_name: "'{0}' no se puede usar como nombre. Elige otro nombre",
+        choose_bot_names: "Elige de dónde salen los nombres de los bots:",
+        bot_names_chosen: "Los bots usarán los nombres {0}",
+        choice_rejected: "¡Escribe una opción del 1 al {0}!",
+        lobby_intro: "Para empezar la partida, añade al menos 2 jugadores y elige 'empezar'",
+        lobby_title: "Elige una opción:",
+        lobby_options: [
+            "Añadir un jugador", "Empezar la partida", "Cambiar el orden de los jugadores", "Barajar los asientos",
+            "Reglas de la casa", "Añadir un bot", "Nombres de los bots", "Aprender a jugar"
+        ],
+        choose_option: "Tu opción: ",
+        lobby_full: "¡La sala está llena! Una partida puede tener como mucho {0} jugadores",
+        lobby_rejected: "¡Escribe una opción del 1 al 8!",
+        shuffled: "¡Se barajaron los asientos!",
+        username_prompt: "Escribe un nombre de usuario: ",
+        added_player: "¡Se añadió a {0}!",
+        name_empty: "El nombre de usuario no puede estar vacío. Escribe un nombre de usuario",
+        name_too_long: "Los nombres de usuario pueden tener como mucho {0} caracteres. Elige un nombre más corto",
+        name_taken_retry: "El nombre '{0}' ya está en uso. Elige otro nombre",
+        name_invalid: "Los nombres de usuario no pueden tener caracteres de control. Elige otro nombre",
+
+        spoken_card: "Carta {0}: {1}.",
+        spoken_playable_card: "Carta {0}: {1}, jugable.",
+        spoken_new_card: "Recién robada.",
+        spoken_current_player: "Juega ahora: {0}.",
+        spoken_next: "Después: {0}.",
+        spoken_player_cards: "{0} con {1}",
+        spoken_then: ", luego ",
+        spoken_direction: "El juego va en sentido {0}.",
+        spoken_wild_color: "{0}, con el color {1}",
+        spoken_turn: "Turno {0}",
+        spoken_holds: "{0} tiene {1}.",
+        spoken_stats: "{0}: {1}.",
+        spoken_stat: "{0} {1}",
+        spoken_standing: "{0}. {1}: {2} cartas, {3} puntos.",
+
+        tutorial_intro: "¡Te damos la bienvenida al tutorial! Jugarás una partida corta contra {0}, con las cartas \
+            preparadas para enseñarte cómo funciona el Uno. Sigue el juego haciendo lo que pide cada lección",
+        tutorial_student: "Aprendiz",
+        tutorial_tutor: "Tutor",
+        tutorial_try_card: "Prueba a jugar la carta {0}: ",
+        tutorial_wrong_card: "Esta vez no. Esta lección trata de la carta {0}, la carta {1}",
+        tutorial_try_color: "Prueba a elegir {0}: ",
+        tutorial_wrong_color: "Esta vez no. Esta lección trata de elegir {0}",
+        tutorial_try_uno: "Escribe 'uno' para cantarlo: ",
+        tutorial_wrong_uno: "¡Escribe 'uno' antes de que te pillen!",
+        tutorial_done: "¡Has terminado el tutorial! Ya sabes todo lo necesario para una partida de verdad",
+        lesson_color: "Lección: una carta se puede poner sobre la de arriba cuando son del mismo color",
+        lesson_number: "Lección: una carta también se puede poner sobre la de arriba cuando tienen el mismo número, \
+            sean del color que sean",
+        lesson_skip: "Lección: un Salta hace que el siguiente jugador pierda su turno. Con solo dos jugadores, \
+            vuelves a jugar tú",
+        lesson_wild: "Lección: un Comodín se puede poner sobre cualquier carta, y eliges el color que hay que jugar \
+            después",
+        lesson_wild_color: "Lección: elige el color del que tengas más cartas, para tener algo que jugar la próxima \
+            vez",
+        lesson_draw_two: "Lección: un Roba 2 hace que el siguiente jugador robe dos cartas y pierda su turno",
+        lesson_uno: "Lección: te queda una carta, ¡canta Uno! Si alguien te pilla antes, robas dos cartas",
+        lesson_last_card: "Lección: gana quien juegue primero su última carta"
+    };
+
+    pub fn color(&self, color: Color) -> &'static str
+    {
+        match color {
+            Color::Red => self.colors[0],
+            Color::Green => self.colors[1],
+            Color::Blue => self.colors[2],
+            Color::Yellow => self.colors[3]
+        }
+    }
+
+    // A color named the way the players would type it, in this language or in English
+    pub fn parse_color(&self, text: &str) -> Option<Color>
+    {
+        [Color::Red, Color::Green, Color::Blue, Color::Yellow].into_iter()
+            .find(|color| self.color(*color).to_lowercase() == text.trim().to_lowercase())
+            .or_else(|| text.parse().ok())
+    }
+
+    pub fn card_type(&self, card_type: CardType) -> String
+    {
+        match card_type {
+            CardType::Number(digit) => digit.to_string(),
+            CardType::Skip => self.skip.to_owned(),
+            CardType::Reverse => self.reverse.to_owned(),
+            CardType::DrawTwo => self.draw_two.to_owned(),
+            CardType::Wildcard => self.wildcard.to_owned(),
+            CardType::DrawFourWildcard => self.draw_four.to_owned()
+        }
+    }
+
+    // e.g. "Red 5" or "Wildcard", like `Display for Card`
+    pub fn card(&self, card: Card) -> String
+    {
+        match card.color {
+            Some(color) => fill(self.card, &[&self.color(color), &self.card_type(card.card_type)]),
+            None => self.card_type(card.card_type)
+        }
+    }
+
+    pub fn direction(&self, direction: Direction) -> &'static str
+    {
+        match direction {
+            Direction::Clockwise => self.clockwise,
+            Direction::CounterClockwise => self.counter_clockwise
+        }
+    }
+
+    // "1 card" or "3 cards"
+    pub fn cards(&self, count: usize) -> String
+    {
+        match count {
+            1 => self.one_card.to_owned(),
+            count => fill(self.many_cards, &[&count])
+        }
+    }
+
+    pub fn is_yes(&self, answer: &str) -> bool
+    {
+        self.yes.contains(&answer)
+    }
+
+    pub fn is_no(&self, answer: &str) -> bool
+    {
+        self.no.contains(&answer)
+    }
+}
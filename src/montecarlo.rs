@@ -0,0 +1,295 @@
+use std::time::{Duration, Instant};
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::bots::{GreedyBot, GreedyWeights, RandomBot};
+use crate::controller::{self, Controllers, PlayerController};
+use crate::determinization::Determinizer;
+use crate::game::{Color, Game, GameEnd, Move, PlayerId};
+use crate::strategy;
+use crate::view::{BotObservation, PlayerView};
+
+const COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+
+// How the rest of the game is played out after the move being tried
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayoutPolicy
+{
+    Random,
+    #[default]
+    Greedy
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonteCarloConfig
+{
+    // Playouts run for each legal move
+    pub playouts: usize,
+    // Stop early once this much time has gone by. Every move always gets the same
+    // number of playouts, but how many that is depends on the machine, so set this to
+    // None when the bot has to be reproducible
+    pub time_budget: Option<Duration>,
+    // Playouts that run this many turns are scored by the cards left in each hand
+    pub max_playout_turns: u32,
+    pub policy: PlayoutPolicy,
+    pub seed: u64
+}
+
+impl Default for MonteCarloConfig
+{
+    fn default() -> Self
+    {
+        MonteCarloConfig {
+            playouts: 50,
+            time_budget: None,
+            max_playout_turns: 30,
+            policy: PlayoutPolicy::Greedy,
+            seed: 0
+        }
+    }
+}
+
+// Tries every legal move by playing the rest of the game out many times, with the
+// other players' hidden hands guessed afresh each time, and makes the move that won
+// the most often
+#[derive(Debug, Clone)]
+pub struct MonteCarloBot
+{
+    config: MonteCarloConfig,
+    rng: SmallRng,
+    // The color that won its playouts along with the wildcard just chosen
+    chosen_color: Option<Color>
+}
+
+impl MonteCarloBot
+{
+    pub fn new(config: MonteCarloConfig) -> MonteCarloBot
+    {
+        let rng = SmallRng::seed_from_u64(config.seed);
+        MonteCarloBot { config, rng, chosen_color: None }
+    }
+
+    pub fn config(&self) -> &MonteCarloConfig
+    {
+        &self.config
+    }
+}
+
+impl Default for MonteCarloBot
+{
+    fn default() -> Self
+    {
+        MonteCarloBot::new(MonteCarloConfig::default())
+    }
+}
+
+impl PlayerController for MonteCarloBot
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let (view, legal) = (&observation.view, &observation.legal_moves[..]);
+        self.chosen_color = None;
+        if legal.len() == 1 && !plays_wild(view, legal[0]) {
+            return legal[0];
+        }
+
+        // A wildcard is tried once with each color. Drawing instead of playing is almost
+        // never right, and with so few playouts it would win on luck often enough to
+        // throw games away, so it's only tried when nothing can be played
+        let can_play = legal.iter().any(|candidate| matches!(candidate, Move::Play(_)));
+        let candidates: Vec<Candidate> = legal.iter()
+            .filter(|&&candidate| !(can_play && candidate == Move::Draw))
+            .flat_map(|&candidate| {
+                let colors = if plays_wild(view, candidate) { COLORS.map(Some).to_vec() } else { vec![None] };
+                colors.into_iter().map(move |color| Candidate { first: candidate, color })
+            })
+            .collect();
+
+        let started = Instant::now();
+        // Round robin, so every move has had the same number of playouts when time runs out
+        let mut scores = vec![0.0; candidates.len()];
+        for _ in 0..self.config.playouts {
+            // Every move is tried against the same guess at the hidden hands and the same
+            // draws, so the difference in scores comes from the moves rather than luck.
+            // Each playout only depends on the seed, so a round could run on separate threads
+            let seed = self.rng.gen();
+            let round = candidates.iter().map(|candidate| playout(observation, *candidate, &self.config, seed));
+            for (score, result) in scores.iter_mut().zip(round) {
+                *score += result;
+            }
+            if self.config.time_budget.is_some_and(|budget| started.elapsed() >= budget) {
+                break;
+            }
+        }
+
+        // Ties go to the earlier move, which plays before drawing
+        let best = scores.iter().enumerate()
+            .fold(0, |best, (index, score)| if *score > scores[best] { index } else { best });
+        self.chosen_color = candidates[best].color;
+        candidates[best].first
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        self.chosen_color.take().unwrap_or_else(|| strategy::suggest_wild_color(view))
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// Plays `candidate` and then the rest of the game from a guess at the hidden hands
+fn playout(observation: &BotObservation, candidate: Candidate, config: &MonteCarloConfig, seed: u64) -> f64
+{
+    let mut observation = observation.clone();
+    let table = &mut observation.view.table;
+    table.config.max_turns = Some(table.turn_number + config.max_playout_turns);
+    let mut game = Determinizer::new().sample(&observation, &mut SmallRng::seed_from_u64(seed));
+    let view = &observation.view;
+
+    let mut controllers: Controllers = game.players().iter().enumerate()
+        .map(|(seat, player)| {
+            let policy = playout_controller(config.policy, seed.wrapping_add(seat as u64));
+            let controller: Box<dyn PlayerController> = if player.id() == view.id {
+                Box::new(FirstMove { first: Some(candidate), then: policy })
+            } else {
+                policy
+            };
+            (player.id(), controller)
+        })
+        .collect();
+
+    match controller::run_game(&mut game, &mut controllers, &mut ()) {
+        Ok(end) => score(&game, end, view.id),
+        Err(_) => 0.0
+    }
+}
+
+fn playout_controller(policy: PlayoutPolicy, seed: u64) -> Box<dyn PlayerController>
+{
+    match policy {
+        PlayoutPolicy::Random => Box::new(RandomBot::seeded(seed)),
+        PlayoutPolicy::Greedy => Box::new(GreedyBot::new(GreedyWeights::BALANCED))
+    }
+}
+
+// 1 for a win and 0 for a loss. A playout that was cut short scores the player's
+// share of the cards, against the others' average, so being well ahead counts for
+// nearly as much as winning. A win or a loss by a single card tells too little
+fn score(game: &Game, end: GameEnd, player: PlayerId) -> f64
+{
+    match end {
+        GameEnd::TurnLimitReached { .. } => {
+            let players = game.players();
+            let held = players.iter().find(|each| each.id() == player).map_or(0, |each| each.number_of_cards()) as f64;
+            let total: f64 = players.iter().map(|each| each.number_of_cards() as f64).sum();
+            let others = (total - held) / (players.len() - 1).max(1) as f64;
+            others / (held + others)
+        },
+        _ => match end.winner() {
+            Some(winner) if winner == player => 1.0,
+            Some(_) => 0.0,
+            None => 0.5
+        }
+    }
+}
+
+fn plays_wild(view: &PlayerView, candidate: Move) -> bool
+{
+    let index = match candidate {
+        Move::Play(index) => Some(index),
+        Move::PlayDrawn => view.drawn_card,
+        _ => None
+    };
+    index.is_some_and(|index| view.hand[index].is_wild())
+}
+
+// A move to try, with the color to pick if it plays a wildcard
+#[derive(Debug, Clone, Copy)]
+struct Candidate
+{
+    first: Move,
+    color: Option<Color>
+}
+
+// Makes the move being tried, then leaves the rest of the playout to the policy
+#[derive(Clone)]
+struct FirstMove
+{
+    first: Option<Candidate>,
+    then: Box<dyn PlayerController>
+}
+
+impl PlayerController for FirstMove
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let legal = &observation.legal_moves[..];
+        match self.first {
+            Some(Candidate { first, color }) if legal.contains(&first) => {
+                self.first = color.map(|color| Candidate { first, color: Some(color) });
+                first
+            },
+            _ => {
+                self.first = None;
+                self.then.choose_move(observation)
+            }
+        }
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        match self.first.take().and_then(|candidate| candidate.color) {
+            Some(color) => color,
+            None => self.then.choose_color(view)
+        }
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        self.then.choose_swap_target(view)
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::simulation::{Contestant, SimulationRunner};
+
+    fn monte_carlo() -> Contestant
+    {
+        Contestant::new("Monte Carlo", |seed| Box::new(MonteCarloBot::new(MonteCarloConfig {
+            playouts: 40,
+            seed,
+            ..MonteCarloConfig::default()
+        })))
+    }
+
+    fn greedy() -> Contestant
+    {
+        Contestant::new("Greedy", |_| Box::new(GreedyBot::new(GreedyWeights::BALANCED)))
+    }
+
+    #[test]
+    fn beats_greedy_head_to_head()
+    {
+        // Half the games each way round, so going first doesn't favour either bot
+        let games = 100;
+        let first = SimulationRunner::new(vec![monte_carlo(), greedy()], GameConfig::default(), 1, games / 2);
+        let second = SimulationRunner::new(vec![greedy(), monte_carlo()], GameConfig::default(), 2, games / 2);
+        let wins = first.run().unwrap().seats[0].wins + second.run().unwrap().seats[1].wins;
+        assert!(wins * 2 > games, "Monte Carlo won {} of {}", wins, games);
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+// Themed sets of names for bots that weren't given one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NamePack
+{
+    // CPU 1, CPU 2, CPU 3...
+    #[default]
+    Classic,
+    Animals,
+    FamousPlayers
+}
+
+impl NamePack
+{
+    pub const ALL: [NamePack; 3] = [NamePack::Classic, NamePack::Animals, NamePack::FamousPlayers];
+
+    // The names before any numbers are added. Each pack other than classic has enough
+    // for a full lobby of bots
+    pub fn names(self) -> &'static [&'static str]
+    {
+        match self {
+            NamePack::Classic => &["CPU"],
+            NamePack::Animals => &["Fox", "Badger", "Otter", "Heron", "Lynx", "Moose", "Panda", "Raven", "Tiger", "Walrus"],
+            NamePack::FamousPlayers => &["Capablanca", "Tal", "Polgar", "Fischer", "Kasparov", "Carlsen", "Morphy", "Lasker",
+                "Anand", "Botvinnik"]
+        }
+    }
+
+    pub fn name(self) -> &'static str
+    {
+        match self {
+            NamePack::Classic => "classic",
+            NamePack::Animals => "animals",
+            NamePack::FamousPlayers => "famous players"
+        }
+    }
+}
+
+impl fmt::Display for NamePack
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Hands out names for bots that no other player has, ignoring case like a lobby does
+pub struct BotNames;
+
+impl BotNames
+{
+    // The first name in the pack that isn't taken. Once the whole pack is used up the
+    // names come round again with a number added, like "Fox 2". Classic names are
+    // always numbered
+    pub fn next(pack: NamePack, existing: &[String]) -> String
+    {
+        let taken = |name: &str| existing.iter().any(|other| other.to_lowercase() == name.to_lowercase());
+        (1..)
+            .flat_map(|round| pack.names().iter().map(move |name| match (pack, round) {
+                (NamePack::Classic, round) | (_, round @ 2..) => format!("{} {}", name, round),
+                _ => name.to_string()
+            }))
+            .find(|name| !taken(name))
+            .expect("There are only so many names that can be taken")
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::controller::FirstMoveBot;
+    use crate::game::Lobby;
+
+    fn names(names: &[&str]) -> Vec<String>
+    {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn classic_names_are_numbered()
+    {
+        assert_eq!(BotNames::next(NamePack::Classic, &[]), "CPU 1");
+        assert_eq!(BotNames::next(NamePack::Classic, &names(&["CPU 1", "cpu 2"])), "CPU 3");
+    }
+
+    #[test]
+    fn a_name_a_human_took_is_skipped()
+    {
+        assert_eq!(BotNames::next(NamePack::Animals, &names(&["Fox"])), "Badger");
+        assert_eq!(BotNames::next(NamePack::Animals, &names(&["fox", "Badger"])), "Otter");
+
+        let mut lobby = Lobby::new();
+        lobby.set_bot_names(NamePack::Animals);
+        lobby.add_player("Fox").unwrap();
+        assert!(lobby.add_bot("", Box::new(FirstMoveBot)));
+        assert!(lobby.add_bot(" ", Box::new(FirstMoveBot)));
+        let seated: Vec<&str> = lobby.seating().iter().map(|player| player.name().as_str()).collect();
+        assert_eq!(seated, ["Fox", "Badger", "Otter"]);
+    }
+
+    #[test]
+    fn a_used_up_pack_starts_again_with_numbers()
+    {
+        for pack in [NamePack::Animals, NamePack::FamousPlayers] {
+            let mut existing = Vec::new();
+            for _ in 0..pack.names().len() {
+                existing.push(BotNames::next(pack, &existing));
+            }
+            assert_eq!(existing, names(pack.names()));
+            assert_eq!(BotNames::next(pack, &existing), format!("{} 2", pack.names()[0]));
+
+            existing.push(BotNames::next(pack, &existing));
+            assert_eq!(BotNames::next(pack, &existing), format!("{} 2", pack.names()[1]));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::events::GameEvent;
+use crate::game::{Color, PlayerId};
+
+// How many hand sizes each profile remembers
+pub const HAND_SIZE_SAMPLES: usize = 20;
+
+// What can be worked out about a player from their public actions alone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpponentProfile
+{
+    // Colors that were active when the player drew instead of playing, and that they
+    // haven't played since
+    pub likely_void_colors: Vec<Color>,
+    // The size of their hand at the end of each of their recent turns, oldest first
+    pub hand_sizes: VecDeque<usize>,
+    // Every color they picked for a wildcard, oldest first
+    pub wild_colors_chosen: Vec<Color>
+}
+
+impl OpponentProfile
+{
+    // Positive when the player's hand has been growing over their recent turns
+    pub fn hand_size_trend(&self) -> isize
+    {
+        match (self.hand_sizes.front(), self.hand_sizes.back()) {
+            (Some(&first), Some(&last)) => last as isize - first as isize,
+            _ => 0
+        }
+    }
+}
+
+// Profiles of every player in a game, kept up to date by the game as things happen
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpponentModel
+{
+    profiles: BTreeMap<PlayerId, OpponentProfile>
+}
+
+impl OpponentModel
+{
+    pub fn profile(&self, id: PlayerId) -> Option<&OpponentProfile>
+    {
+        self.profiles.get(&id)
+    }
+
+    fn profile_mut(&mut self, id: PlayerId) -> &mut OpponentProfile
+    {
+        self.profiles.entry(id).or_default()
+    }
+
+    // The player drew a card rather than play on `active_color`
+    pub(crate) fn record_draw(&mut self, id: PlayerId, active_color: Option<Color>)
+    {
+        let profile = self.profile_mut(id);
+        if let Some(color) = active_color {
+            if !profile.likely_void_colors.contains(&color) {
+                profile.likely_void_colors.push(color);
+            }
+        }
+    }
+
+    pub(crate) fn record_hand_size(&mut self, id: PlayerId, hand_size: usize)
+    {
+        let hand_sizes = &mut self.profile_mut(id).hand_sizes;
+        if hand_sizes.len() == HAND_SIZE_SAMPLES {
+            hand_sizes.pop_front();
+        }
+        hand_sizes.push_back(hand_size);
+    }
+
+    pub(crate) fn record(&mut self, event: &GameEvent)
+    {
+        match event {
+            GameEvent::CardDealt { player, .. } | GameEvent::PlayerJoined { player } => {
+                self.profile_mut(*player);
+            },
+            GameEvent::CardPlayed { player, card } if !card.is_wild() => {
+                self.profile_mut(*player).likely_void_colors.retain(|color| Some(*color) != card.color);
+            },
+            GameEvent::WildColorChosen { player, color } => {
+                self.profile_mut(*player).wild_colors_chosen.push(*color);
+            },
+            // What was known about each hand now describes the other player
+            GameEvent::HandsSwapped { player, target } => {
+                let player_voids = std::mem::take(&mut self.profile_mut(*player).likely_void_colors);
+                let target_voids = std::mem::replace(&mut self.profile_mut(*target).likely_void_colors, player_voids);
+                self.profile_mut(*player).likely_void_colors = target_voids;
+            },
+            // Every hand moved, so start over rather than track where each one went
+            GameEvent::HandsRotated => {
+                for profile in self.profiles.values_mut() {
+                    profile.likely_void_colors.clear();
+                }
+            },
+            _ => ()
+        }
+    }
+}
@@ -0,0 +1,540 @@
+use crate::game::{Card, CardType, Color, Direction};
+use crate::history::TurnAction;
+use crate::messages::{fill, Messages};
+
+const RESET: &str = "\x1b[0m";
+
+// When the terminal's width isn't known
+pub const DEFAULT_WIDTH: usize = 80;
+
+// How a hand is laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CardStyle
+{
+    // One card to a line
+    #[default]
+    Plain,
+    // Small cards drawn side by side, see `Renderer::card_boxes`
+    Fancy,
+    // Every card as a colored square and its value, like "🟥7", with the whole hand on
+    // one line
+    Compact
+}
+
+// How the terminal frontend writes cards out. The engine's `Display` impls stay plain
+// text, and everything a player can turn on for the terminal is added here
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Renderer
+{
+    // Paint cards in their colors with ANSI escape codes
+    pub color: bool,
+    // Mark every card with a letter for its color, like "[R] Red 5", so no card can
+    // only be told apart by its hue
+    pub symbols: bool,
+    pub style: CardStyle,
+    // Whether the terminal can show the compact style's squares. Without them cards are
+    // written with a letter for their color instead, like "R7"
+    pub unicode: bool,
+    // Columns the terminal has, for laying out a fancy hand or a plain one in columns
+    pub width: usize,
+    // Lay a plain hand out in as many columns as fit in `width`, rather than one card
+    // to a line. Left off when the output isn't a terminal, so it stays easy to read
+    // from a script
+    pub columns: bool,
+    // Write for a screen reader: sentences instead of arrows, brackets and markers, and
+    // the hand read out a card at a time. Meant to go with colors off and the plain style
+    pub accessible: bool,
+    // The language cards and everything else are written in
+    pub messages: &'static Messages
+}
+
+impl Renderer
+{
+    pub const PLAIN: Renderer = Renderer {
+        color: false, symbols: false, style: CardStyle::Plain, unicode: true, width: DEFAULT_WIDTH, columns: false,
+        accessible: false, messages: &Messages::ENGLISH
+    };
+    pub const COLORED: Renderer = Renderer { color: true, ..Renderer::PLAIN };
+    pub const SYMBOLS: Renderer = Renderer { symbols: true, ..Renderer::PLAIN };
+    pub const FANCY: Renderer = Renderer { style: CardStyle::Fancy, ..Renderer::PLAIN };
+    pub const COMPACT: Renderer = Renderer { style: CardStyle::Compact, ..Renderer::PLAIN };
+    pub const ACCESSIBLE: Renderer = Renderer { accessible: true, ..Renderer::PLAIN };
+
+    // A title in bold, when there are colors
+    pub fn heading(&self, text: &str) -> String
+    {
+        if self.color {
+            format!("\x1b[1m{}{}", text, RESET)
+        } else {
+            text.to_owned()
+        }
+    }
+
+    // e.g. "Red 5", or "Wildcard" in white on black. "🟥5" and "🃏" in the compact style
+    pub fn card(&self, card: Card) -> String
+    {
+        if self.style == CardStyle::Compact {
+            return self.paint_only(card.color, &self.compact_card(card));
+        }
+        self.paint(card.color, &self.messages.card(card))
+    }
+
+    // e.g. "Blue" in blue
+    pub fn color(&self, color: Color) -> String
+    {
+        self.paint(Some(color), self.messages.color(color))
+    }
+
+    // e.g. "played a Red 5"
+    pub fn turn_action(&self, action: TurnAction) -> String
+    {
+        let text = self.messages;
+        match action {
+            TurnAction::Played(card) => fill(text.action_played, &[&self.card(card)]),
+            TurnAction::DrewAndPlayed { drawn, card } =>
+                fill(text.action_drew_and_played, &[&text.cards(drawn), &self.card(card)]),
+            TurnAction::Drew(drawn) => fill(text.action_drew, &[&text.cards(drawn)]),
+            TurnAction::Penalized(drawn) => fill(text.action_penalized, &[&text.cards(drawn)]),
+            TurnAction::Skipped => text.action_skipped.to_owned()
+        }
+    }
+
+    // e.g. "Red 5, Blue Skip"
+    pub fn list(&self, cards: &[Card]) -> String
+    {
+        let names: Vec<String> = cards.iter().map(|card| self.card(*card)).collect();
+        names.join(", ")
+    }
+
+    // e.g. "2 cards: Red 5, Blue Skip"
+    pub fn describe(&self, cards: &[Card]) -> String
+    {
+        fill(self.messages.described_cards, &[&cards.len(), &self.list(cards)])
+    }
+
+    // e.g. "Wildcard (Blue)" once a color has been chosen for a wildcard on top
+    pub fn top_card(&self, top: Card, active_color: Option<Color>) -> String
+    {
+        match active_color {
+            Some(color) if top.is_wild() && self.accessible =>
+                fill(self.messages.spoken_wild_color, &[&self.card(top), &self.color(color)]),
+            Some(color) if top.is_wild() => format!("{} ({})", self.card(top), self.color(color)),
+            _ => self.card(top)
+        }
+    }
+
+    // e.g. "Red 5 -> Red 7 -> Blue 7", or "Red 5, then Red 7, then Blue 7" read aloud
+    pub fn sequence(&self, items: &[String]) -> String
+    {
+        items.join(if self.accessible { self.messages.spoken_then } else { " -> " })
+    }
+
+    // e.g. "T14", or "Turn 14" read aloud
+    pub fn turn_label(&self, turn: u32) -> String
+    {
+        if self.accessible { fill(self.messages.spoken_turn, &[&turn]) } else { format!("T{}", turn) }
+    }
+
+    // e.g. "[Alice·4] -> Bob·1! -> Carol·7 ↻ clockwise", from the current player in the
+    // order of play, with a "!" on anyone down to their last card. Like `Display for
+    // Game`, but with everyone's card count
+    pub fn turn_order<'a>(&self, players: impl IntoIterator<Item = (&'a str, usize)>, direction: Direction) -> String
+    {
+        if self.accessible {
+            return self.spoken_turn_order(players, direction);
+        }
+        let dot = if self.unicode { '·' } else { ':' };
+        let names: Vec<String> = players.into_iter().enumerate()
+            .map(|(place, (name, cards))| {
+                let last_card = if cards == 1 { "!" } else { "" };
+                match place {
+                    0 => format!("[{}{}{}{}]", name, dot, cards, last_card),
+                    _ => format!("{}{}{}{}", name, dot, cards, last_card)
+                }
+            })
+            .collect();
+        let words = match direction {
+            Direction::Clockwise => self.messages.arrow_clockwise,
+            Direction::CounterClockwise => self.messages.arrow_counter_clockwise
+        };
+        let arrow = match (self.unicode, direction) {
+            (true, Direction::Clockwise) => format!("↻ {}", words),
+            (true, Direction::CounterClockwise) => format!("↺ {}", words),
+            (false, _) => format!("({})", words)
+        };
+        match names.len() {
+            0..=2 => names.join(" -> "),
+            _ => format!("{} {}", names.join(" -> "), arrow)
+        }
+    }
+
+    // e.g. "Current player: Alice with 4 cards. Next: Bob with 1 card, then Carol with 7
+    // cards. Play goes clockwise."
+    fn spoken_turn_order<'a>(&self, players: impl IntoIterator<Item = (&'a str, usize)>, direction: Direction)
+        -> String
+    {
+        let text = self.messages;
+        let players: Vec<String> = players.into_iter()
+            .map(|(name, cards)| fill(text.spoken_player_cards, &[&name, &text.cards(cards)]))
+            .collect();
+        let Some((current, others)) = players.split_first() else {
+            return String::new();
+        };
+        let mut sentences = vec![fill(text.spoken_current_player, &[current])];
+        if !others.is_empty() {
+            sentences.push(fill(text.spoken_next, &[&self.sequence(others)]));
+        }
+        if players.len() > 2 {
+            let words = match direction {
+                Direction::Clockwise => text.arrow_clockwise,
+                Direction::CounterClockwise => text.arrow_counter_clockwise
+            };
+            sentences.push(fill(text.spoken_direction, &[&words]));
+        }
+        sentences.join(" ")
+    }
+
+    // The hand in the chosen style, numbered from 1 the way players choose cards
+    pub fn hand(&self, hand: &[Card]) -> String
+    {
+        self.marked_hand(hand, &[], &[])
+    }
+
+    // The hand with an asterisk by the cards at `marked`, like "3. Red 5 *", and the
+    // cards at `new` pointed out as just drawn, like "4. Blue 2 (new)". There's only room
+    // for a plus sign by the number in the fancy and compact styles
+    pub fn marked_hand(&self, hand: &[Card], marked: &[usize], new: &[usize]) -> String
+    {
+        let text = self.messages;
+        let mark = |index: usize| if marked.contains(&index) { "*" } else { "" };
+        let new_mark = |index: usize, mark: &'static str| if new.contains(&index) { mark } else { "" };
+        if self.accessible {
+            // One card to a line, with the playable and new ones said in words
+            return hand.iter().enumerate()
+                .map(|(index, card)| {
+                    let template = if marked.contains(&index) { text.spoken_playable_card } else { text.spoken_card };
+                    let sentence = fill(template, &[&(index + 1), &self.card(*card)]);
+                    if new.contains(&index) {
+                        format!("{} {}\n", sentence, text.spoken_new_card)
+                    } else {
+                        sentence + "\n"
+                    }
+                })
+                .collect();
+        }
+        match self.style {
+            CardStyle::Plain => {
+                let entries: Vec<String> = hand.iter().enumerate()
+                    .map(|(index, card)| {
+                        let marks = format!("{} {}", mark(index), new_mark(index, text.new_mark));
+                        format!("{}. {} {}", index + 1, self.card(*card), marks.trim()).trim_end().to_owned()
+                    })
+                    .collect();
+                columns(&entries, if self.columns { self.width } else { 0 })
+            },
+            CardStyle::Fancy => self.card_boxes(hand, self.width, marked, new),
+            CardStyle::Compact => {
+                let cards: Vec<String> = hand.iter().enumerate()
+                    .map(|(index, card)| {
+                        format!("{}{}:{}{}", index + 1, new_mark(index, "+"), self.card(*card), mark(index))
+                    })
+                    .collect();
+                cards.join(" ") + "\n"
+            }
+        }
+    }
+
+    fn compact_card(&self, card: Card) -> String
+    {
+        let mark = if self.unicode {
+            match card.color {
+                Some(Color::Red) => "🟥",
+                Some(Color::Green) => "🟩",
+                Some(Color::Blue) => "🟦",
+                Some(Color::Yellow) => "🟨",
+                None => "🃏"
+            }.to_owned()
+        } else {
+            self.color_letter(card.color).to_string()
+        };
+        format!("{}{}", mark, self.short_label(card.card_type).unwrap_or_default())
+    }
+
+    // Cards drawn as boxes side by side with their numbers above them, in as many rows
+    // as it takes to fit in `width` columns. The numbers of the cards at `marked` have an
+    // asterisk, those of the cards at `new` a plus sign, and wildcards have a double
+    // border:
+    //
+    //    1      2*+
+    // +-----+ #=====#
+    // |R    | #W    #
+    // |  7  | #Wild #
+    // |    R| #    W#
+    // +-----+ #=====#
+    pub fn card_boxes(&self, cards: &[Card], width: usize, marked: &[usize], new: &[usize]) -> String
+    {
+        const BOX_WIDTH: usize = 7;
+        let per_row = ((width + 1) / (BOX_WIDTH + 1)).max(1);
+
+        let mut text = String::new();
+        for (row, chunk) in cards.chunks(per_row).enumerate() {
+            let first = row * per_row;
+            let numbers: Vec<String> = (first..first + chunk.len())
+                .map(|index| {
+                    let mark = if marked.contains(&index) { "*" } else { "" };
+                    let new_mark = if new.contains(&index) { "+" } else { "" };
+                    format!("{:^7}", format!("{}{}{}", index + 1, mark, new_mark))
+                })
+                .collect();
+            text += numbers.join(" ").trim_end();
+            text.push('\n');
+
+            let boxes: Vec<[String; 5]> = chunk.iter().map(|card| self.card_box(*card)).collect();
+            for line in 0..5 {
+                let parts: Vec<&str> = boxes.iter().map(|lines| lines[line].as_str()).collect();
+                text += &parts.join(" ");
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    fn card_box(&self, card: Card) -> [String; 5]
+    {
+        let letter = self.color_letter(card.color);
+        let label = self.short_label(card.card_type).unwrap_or_else(|| self.messages.short_wild.to_owned());
+        let (edge, side) = if card.is_wild() { ("#=====#", '#') } else { ("+-----+", '|') };
+        [
+            edge.to_owned(),
+            format!("{}{:<5}{}", side, letter, side),
+            format!("{}{:^5}{}", side, label, side),
+            format!("{}{:>5}{}", side, letter, side),
+            edge.to_owned()
+        ].map(|line| self.paint_only(card.color, &line))
+    }
+
+    // Wildcards have no color of their own, so they're white on black and marked W
+    fn paint(&self, color: Option<Color>, text: &str) -> String
+    {
+        if self.symbols {
+            self.paint_only(color, &format!("[{}] {}", self.color_letter(color), text))
+        } else {
+            self.paint_only(color, text)
+        }
+    }
+
+    // The ANSI colors without the symbol
+    fn paint_only(&self, color: Option<Color>, text: &str) -> String
+    {
+        if !self.color {
+            return text.to_owned();
+        }
+        let code = match color {
+            Some(Color::Red) => "1;31",
+            Some(Color::Green) => "1;32",
+            Some(Color::Blue) => "1;34",
+            Some(Color::Yellow) => "1;33",
+            None => "1;97;40"
+        };
+        format!("\x1b[{}m{}{}", code, text, RESET)
+    }
+
+    fn color_letter(&self, color: Option<Color>) -> char
+    {
+        let letters = self.messages.color_letters;
+        match color {
+            Some(Color::Red) => letters[0],
+            Some(Color::Green) => letters[1],
+            Some(Color::Blue) => letters[2],
+            Some(Color::Yellow) => letters[3],
+            None => letters[4]
+        }
+    }
+
+    // What's written on a card after its color, in the fancy and compact styles. Plain
+    // wildcards have nothing
+    fn short_label(&self, card_type: CardType) -> Option<String>
+    {
+        match card_type {
+            CardType::Number(digit) => Some(digit.to_string()),
+            CardType::Skip => Some(self.messages.short_skip.to_owned()),
+            CardType::Reverse => Some(self.messages.short_reverse.to_owned()),
+            CardType::DrawTwo => Some(String::from("+2")),
+            CardType::Wildcard => None,
+            CardType::DrawFourWildcard => Some(String::from("+4"))
+        }
+    }
+}
+
+impl Default for Renderer
+{
+    fn default() -> Self
+    {
+        Renderer::PLAIN
+    }
+}
+
+// Entries side by side in rows, each column as wide as the widest entry, so as many fit
+// on a line as `width` allows. There's always at least one to a line, so a `width` of 0
+// puts every entry on its own
+pub fn columns(entries: &[String], width: usize) -> String
+{
+    const GAP: usize = 3;
+    let column_width = entries.iter().map(|entry| visible_width(entry)).max().unwrap_or(0);
+    let per_row = ((width + GAP) / (column_width + GAP)).max(1);
+
+    let mut text = String::new();
+    for row in entries.chunks(per_row) {
+        for (place, entry) in row.iter().enumerate() {
+            text += entry;
+            if place + 1 < row.len() {
+                text += &" ".repeat(column_width - visible_width(entry) + GAP);
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+// How many columns `text` takes up on the screen, leaving out ANSI escape codes
+fn visible_width(text: &str) -> usize
+{
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Everything up to the letter that ends the code
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn cards(names: &[&str]) -> Vec<Card>
+    {
+        names.iter().map(|name| name.parse().expect("Test cards are spelled right")).collect()
+    }
+
+    #[test]
+    fn cards_in_each_color_mode()
+    {
+        let hand = cards(&["red 5", "green skip", "wild"]);
+        let both = Renderer { color: true, ..Renderer::SYMBOLS };
+
+        assert_eq!(Renderer::PLAIN.hand(&hand), "1. Red 5\n2. Green Skip\n3. Wildcard\n");
+        assert_eq!(Renderer::PLAIN.top_card(hand[2], Some(Color::Blue)), "Wildcard (Blue)");
+
+        assert_eq!(Renderer::COLORED.hand(&hand),
+            "1. \x1b[1;31mRed 5\x1b[0m\n2. \x1b[1;32mGreen Skip\x1b[0m\n3. \x1b[1;97;40mWildcard\x1b[0m\n");
+        assert_eq!(Renderer::COLORED.top_card(hand[2], Some(Color::Blue)),
+            "\x1b[1;97;40mWildcard\x1b[0m (\x1b[1;34mBlue\x1b[0m)");
+
+        assert_eq!(Renderer::SYMBOLS.hand(&hand), "1. [R] Red 5\n2. [G] Green Skip\n3. [W] Wildcard\n");
+        assert_eq!(Renderer::SYMBOLS.top_card(hand[2], Some(Color::Blue)), "[W] Wildcard ([B] Blue)");
+        assert_eq!(Renderer::SYMBOLS.turn_action(TurnAction::Played(hand[0])), "played a [R] Red 5");
+
+        assert_eq!(both.list(&hand),
+            "\x1b[1;31m[R] Red 5\x1b[0m, \x1b[1;32m[G] Green Skip\x1b[0m, \x1b[1;97;40m[W] Wildcard\x1b[0m");
+    }
+
+    #[test]
+    fn compact_cards_with_and_without_squares()
+    {
+        let hand = cards(&["red 7", "blue skip", "wild", "yellow +2"]);
+        let letters = Renderer { unicode: false, ..Renderer::COMPACT };
+
+        assert_eq!(Renderer::COMPACT.card(hand[0]), "🟥7");
+        assert_eq!(Renderer::COMPACT.hand(&hand), "1:🟥7 2:🟦Skip 3:🃏 4:🟨+2\n");
+        assert_eq!(Renderer::COMPACT.top_card(hand[2], Some(Color::Green)), "🃏 (Green)");
+
+        // A terminal that can't show the squares gets a letter for each color instead
+        assert_eq!(letters.card(hand[0]), "R7");
+        assert_eq!(letters.hand(&hand), "1:R7 2:BSkip 3:W 4:Y+2\n");
+        assert_eq!(letters.top_card(hand[2], Some(Color::Green)), "W (Green)");
+        assert!(letters.hand(&hand).is_ascii());
+    }
+
+    #[test]
+    fn card_boxes_side_by_side()
+    {
+        let hand = cards(&["red 7", "wild", "blue +2", "+4"]);
+        assert_eq!(Renderer::FANCY.card_boxes(&hand, 80, &[1], &[3]), "   1      2*       3      4+
++-----+ #=====# +-----+ #=====#
+|R    | #W    # |B    | #W    #
+|  7  | #Wild # | +2  | # +4  #
+|    R| #    W# |    B| #    W#
++-----+ #=====# +-----+ #=====#
+");
+    }
+
+    #[test]
+    fn card_boxes_wrap_to_the_width()
+    {
+        let hand = cards(&["red 7", "wild", "blue +2", "+4"]);
+        assert_eq!(Renderer::FANCY.card_boxes(&hand, 20, &[], &[]), "   1       2
++-----+ #=====#
+|R    | #W    #
+|  7  | #Wild #
+|    R| #    W#
++-----+ #=====#
+   3       4
++-----+ #=====#
+|B    | #W    #
+| +2  | # +4  #
+|    B| #    W#
++-----+ #=====#
+");
+        // However narrow the terminal, there's a card to a row
+        assert_eq!(Renderer::FANCY.card_boxes(&hand, 0, &[], &[]).lines().count(), 24);
+    }
+
+    fn entries() -> Vec<String>
+    {
+        ["1. Red 5", "2. Green Skip", "3. Wildcard", "4. Blue 9", "5. Yellow Draw Two"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn columns_on_a_wide_terminal()
+    {
+        assert_eq!(columns(&entries(), 80), "\
+1. Red 5             2. Green Skip        3. Wildcard
+4. Blue 9            5. Yellow Draw Two
+");
+        assert_eq!(columns(&entries(), 200).lines().count(), 1);
+    }
+
+    #[test]
+    fn columns_on_a_narrow_terminal()
+    {
+        assert_eq!(columns(&entries(), 40), "\
+1. Red 5             2. Green Skip
+3. Wildcard          4. Blue 9
+5. Yellow Draw Two
+");
+        // Too narrow for two, or no width at all, is one to a line
+        let one_column = "1. Red 5\n2. Green Skip\n3. Wildcard\n4. Blue 9\n5. Yellow Draw Two\n";
+        assert_eq!(columns(&entries(), 20), one_column);
+        assert_eq!(columns(&entries(), 0), one_column);
+    }
+
+    #[test]
+    fn colors_dont_take_up_columns()
+    {
+        let hand = cards(&["red 5", "green skip", "wild", "blue 9"]);
+        let renderer = Renderer { columns: true, width: 30, ..Renderer::COLORED };
+        let plain = Renderer { columns: true, width: 30, ..Renderer::PLAIN };
+        assert_eq!(plain.hand(&hand), "1. Red 5        2. Green Skip\n3. Wildcard     4. Blue 9\n");
+        let without_codes = renderer.hand(&hand).replace(RESET, "")
+            .replace("\x1b[1;31m", "").replace("\x1b[1;32m", "").replace("\x1b[1;34m", "").replace("\x1b[1;97;40m", "");
+        assert_eq!(without_codes, plain.hand(&hand));
+    }
+}
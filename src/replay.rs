@@ -0,0 +1,520 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::{fs, io, path::Path};
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::config::GameConfig;
+use crate::controller::{Controllers, Decision, PlayerController};
+use crate::error::GameError;
+use crate::events::RecordedEvent;
+use crate::game::{Color, Game, Lobby, Move, PlayerId};
+use crate::view::{BotObservation, PlayerView};
+
+// Raised whenever the format changes, so an old replay is turned down instead of misread
+pub const REPLAY_VERSION: u32 = 1;
+
+// Everything needed to play a game over again: the lobby it was dealt from, the seed that
+// dealt it, and every answer the players gave in the order they gave it. The events are
+// kept too, so a game that doesn't come out the same is caught, see `Playback`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay
+{
+    pub version: u32,
+    // TOML integers are signed, so it's kept as text
+    pub seed: String,
+    pub config: GameConfig,
+    // Everyone at the start, in seating order
+    pub seating: Vec<(PlayerId, String)>,
+    pub answers: Vec<Answer>,
+    pub events: Vec<RecordedEvent>,
+    // How long the game took to play, in seconds
+    pub seconds: u64
+}
+
+// One thing a player's controller was asked and what it said. Saving is left out, since
+// it doesn't change the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Answer
+{
+    Move(Move),
+    PlayMany(Vec<usize>),
+    CallUno { seat: usize },
+    Catch { target: usize },
+    JumpIn { seat: usize },
+    Join { name: String },
+    Leave { seat: usize },
+    SortHand,
+    Color(Color),
+    SwapTarget(usize),
+    // Whether the player called Uno when they dropped to one card
+    Uno(bool)
+}
+
+#[derive(Debug)]
+pub enum ReplayError
+{
+    #[cfg(feature = "serde")]
+    Io(io::Error),
+    // The file isn't a replay at all, or has been damaged
+    Corrupt(String),
+    WrongVersion { found: u32 },
+    Game(GameError),
+    // Playing the answers again didn't give the game that was recorded
+    Diverged(String)
+}
+
+impl fmt::Display for ReplayError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            #[cfg(feature = "serde")]
+            ReplayError::Io(error) => write!(f, "{}", error),
+            ReplayError::Corrupt(reason) => write!(f, "the replay is damaged ({})", reason),
+            ReplayError::WrongVersion { found } => write!(f,
+                "the replay is version {}, and this version of Uno reads version {}", found, REPLAY_VERSION),
+            ReplayError::Game(error) => write!(f, "the game can't be played again: {}", error),
+            ReplayError::Diverged(reason) => write!(f, "the game didn't play out the way it was recorded: {}", reason)
+        }
+    }
+}
+
+impl Error for ReplayError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self {
+            #[cfg(feature = "serde")]
+            ReplayError::Io(error) => Some(error),
+            ReplayError::Game(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+impl From<GameError> for ReplayError
+{
+    fn from(error: GameError) -> Self
+    {
+        ReplayError::Game(error)
+    }
+}
+
+impl Replay
+{
+    // Deal the game again, before anyone has answered anything. A seating no lobby could
+    // have held, like two players with the same name, means the file was damaged
+    pub fn start(&self) -> Result<Game, ReplayError>
+    {
+        let seed = self.seed.parse().map_err(|_| ReplayError::Corrupt(format!("'{}' isn't a seed", self.seed)))?;
+        let lobby = Lobby::seated(self.config.clone(), &self.seating)
+            .map_err(|error| ReplayError::Corrupt(error.to_string()))?;
+        Ok(lobby.start_with_seed(seed)?)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn write(&self, path: &Path) -> Result<(), ReplayError>
+    {
+        let text = toml::to_string(self).map_err(|error| ReplayError::Corrupt(error.to_string()))?;
+        if let Some(folder) = path.parent().filter(|folder| !folder.as_os_str().is_empty()) {
+            fs::create_dir_all(folder).map_err(ReplayError::Io)?;
+        }
+        fs::write(path, text).map_err(ReplayError::Io)
+    }
+
+    // The version is checked before anything else, so a replay from another version is
+    // reported as that rather than as damaged
+    #[cfg(feature = "serde")]
+    pub fn read(path: &Path) -> Result<Replay, ReplayError>
+    {
+        let corrupt = |error: toml::de::Error| ReplayError::Corrupt(error.message().trim().replace('\n', ", "));
+        let text = fs::read_to_string(path).map_err(ReplayError::Io)?;
+        let table: toml::Table = text.parse().map_err(corrupt)?;
+        match table.get("version").and_then(toml::Value::as_integer) {
+            Some(version) if version == REPLAY_VERSION as i64 => (),
+            Some(version) => return Err(ReplayError::WrongVersion { found: version.try_into().unwrap_or(0) }),
+            None => return Err(ReplayError::Corrupt(String::from("it has no version")))
+        }
+        table.try_into().map_err(corrupt)
+    }
+}
+
+// Notes down every answer given by the controllers it wraps, to make a `Replay` of a game
+// from the moment it was dealt
+pub struct Recording
+{
+    seed: u64,
+    config: GameConfig,
+    seating: Vec<(PlayerId, String)>,
+    answers: Rc<RefCell<Vec<Answer>>>
+}
+
+impl Recording
+{
+    // Start recording `game`, which `seed` has just dealt
+    pub fn start(game: &Game, seed: u64) -> Recording
+    {
+        Recording {
+            seed,
+            config: game.config().clone(),
+            seating: game.players().iter().map(|player| (player.id(), player.name().clone())).collect(),
+            answers: Rc::new(RefCell::new(Vec::new()))
+        }
+    }
+
+    // The same controllers, noting down their answers
+    pub fn record(&self, controllers: Controllers) -> Controllers
+    {
+        controllers.into_iter()
+            .map(|(id, inner)| {
+                let recorder = Recorder { inner, answers: Rc::clone(&self.answers) };
+                (id, Box::new(recorder) as Box<dyn PlayerController>)
+            })
+            .collect()
+    }
+
+    // The game as it's been played so far, taking `took`
+    pub fn finish(&self, game: &Game, took: Duration) -> Replay
+    {
+        Replay {
+            version: REPLAY_VERSION,
+            seed: self.seed.to_string(),
+            config: self.config.clone(),
+            seating: self.seating.clone(),
+            answers: self.answers.borrow().clone(),
+            events: game.events().to_vec(),
+            seconds: took.as_secs()
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Recorder
+{
+    inner: Box<dyn PlayerController>,
+    answers: Rc<RefCell<Vec<Answer>>>
+}
+
+impl Recorder
+{
+    fn note(&self, answer: Answer)
+    {
+        self.answers.borrow_mut().push(answer);
+    }
+}
+
+impl PlayerController for Recorder
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let chosen = self.inner.choose_move(observation);
+        self.note(Answer::Move(chosen));
+        chosen
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        let color = self.inner.choose_color(view);
+        self.note(Answer::Color(color));
+        color
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        let target = self.inner.choose_swap_target(view);
+        self.note(Answer::SwapTarget(target));
+        target
+    }
+
+    fn wants_to_call_uno(&mut self, view: &PlayerView) -> bool
+    {
+        let call = self.inner.wants_to_call_uno(view);
+        self.note(Answer::Uno(call));
+        call
+    }
+
+    fn decide(&mut self, observation: &BotObservation) -> Decision
+    {
+        let decision = self.inner.decide(observation);
+        let answer = match &decision {
+            Decision::Move(chosen) => Answer::Move(*chosen),
+            Decision::PlayMany(indices) => Answer::PlayMany(indices.clone()),
+            Decision::CallUno { seat } => Answer::CallUno { seat: *seat },
+            Decision::Catch { target } => Answer::Catch { target: *target },
+            Decision::JumpIn { seat } => Answer::JumpIn { seat: *seat },
+            Decision::Join { name, .. } => Answer::Join { name: name.clone() },
+            Decision::Leave { seat } => Answer::Leave { seat: *seat },
+            Decision::SortHand => Answer::SortHand,
+            Decision::Save { .. } => return decision
+        };
+        self.note(answer);
+        // Whoever joins is recorded along with everyone else
+        match decision {
+            Decision::Join { name, controller } => {
+                let recorder = Recorder { inner: controller, answers: Rc::clone(&self.answers) };
+                Decision::Join { name, controller: Box::new(recorder) }
+            },
+            decision => decision
+        }
+    }
+
+    fn rejected(&mut self, view: &PlayerView, error: &GameError)
+    {
+        self.inner.rejected(view, error);
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// Plays a `Replay` back by giving its answers again, whoever is asked. When it's asked for
+// something other than what comes next, or runs out, everyone is made to leave so the game
+// ends quickly, and `finish` reports where it went wrong
+#[derive(Clone)]
+pub struct Playback
+{
+    script: Rc<RefCell<Script>>
+}
+
+struct Script
+{
+    answers: VecDeque<Answer>,
+    given: usize,
+    recorded: Vec<RecordedEvent>,
+    wrong: Option<String>
+}
+
+impl Playback
+{
+    pub fn new(replay: &Replay) -> Playback
+    {
+        let script = Script {
+            answers: replay.answers.iter().cloned().collect(),
+            given: 0,
+            recorded: replay.events.clone(),
+            wrong: None
+        };
+        Playback { script: Rc::new(RefCell::new(script)) }
+    }
+
+    // A controller for everyone in `game`, all giving answers from the same replay
+    pub fn controllers(&self, game: &Game) -> Controllers
+    {
+        game.players().iter()
+            .map(|player| (player.id(), Box::new(self.clone()) as Box<dyn PlayerController>))
+            .collect()
+    }
+
+    // The next answer, if it's the kind that was asked for
+    fn next<T>(&self, asked: &str, take: impl FnOnce(&Answer) -> Option<T>) -> Option<T>
+    {
+        let mut script = self.script.borrow_mut();
+        if script.wrong.is_some() {
+            return None;
+        }
+        let taken = script.answers.front().and_then(take);
+        match (taken, script.answers.front()) {
+            (Some(taken), _) => {
+                script.answers.pop_front();
+                script.given += 1;
+                Some(taken)
+            },
+            (None, Some(answer)) => {
+                let wrong = format!("answer {} is {:?}, but {} was asked for", script.given + 1, answer, asked);
+                script.wrong = Some(wrong);
+                None
+            },
+            (None, None) => {
+                script.wrong = Some(format!("the answers ran out when {} was asked for", asked));
+                None
+            }
+        }
+    }
+
+    // Check the game that was just played against the recording, event by event
+    pub fn finish(&self, game: &Game) -> Result<(), ReplayError>
+    {
+        let script = self.script.borrow();
+        if let Some(wrong) = &script.wrong {
+            return Err(ReplayError::Diverged(wrong.clone()));
+        }
+        let replayed = game.events();
+        if let Some(index) = (0..replayed.len().max(script.recorded.len()))
+            .find(|&index| replayed.get(index) != script.recorded.get(index)) {
+            let describe = |event: Option<&RecordedEvent>| event.map_or(String::from("nothing"),
+                |recorded| format!("{:?} on turn {}", recorded.event, recorded.turn));
+            return Err(ReplayError::Diverged(format!("event {} was {} but is now {}", index + 1,
+                describe(script.recorded.get(index)), describe(replayed.get(index)))));
+        }
+        if !script.answers.is_empty() {
+            return Err(ReplayError::Diverged(format!("{} answers were never asked for", script.answers.len())));
+        }
+        Ok(())
+    }
+}
+
+impl PlayerController for Playback
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let chosen = self.next("a move", |answer| match answer {
+            Answer::Move(chosen) => Some(*chosen),
+            _ => None
+        });
+        chosen.unwrap_or(observation.legal_moves[0])
+    }
+
+    fn choose_color(&mut self, _view: &PlayerView) -> Color
+    {
+        let color = self.next("a color", |answer| match answer {
+            Answer::Color(color) => Some(*color),
+            _ => None
+        });
+        color.unwrap_or(Color::Red)
+    }
+
+    fn choose_swap_target(&mut self, view: &PlayerView) -> usize
+    {
+        let target = self.next("someone to swap hands with", |answer| match answer {
+            Answer::SwapTarget(target) => Some(*target),
+            _ => None
+        });
+        target.unwrap_or((view.seat + 1) % view.table.players.len())
+    }
+
+    fn wants_to_call_uno(&mut self, _view: &PlayerView) -> bool
+    {
+        let call = self.next("whether to call Uno", |answer| match answer {
+            Answer::Uno(call) => Some(*call),
+            _ => None
+        });
+        call.unwrap_or(false)
+    }
+
+    fn decide(&mut self, observation: &BotObservation) -> Decision
+    {
+        let decision = self.next("a decision", |answer| Some(match answer {
+            Answer::Move(chosen) => Decision::Move(*chosen),
+            Answer::PlayMany(indices) => Decision::PlayMany(indices.clone()),
+            Answer::CallUno { seat } => Decision::CallUno { seat: *seat },
+            Answer::Catch { target } => Decision::Catch { target: *target },
+            Answer::JumpIn { seat } => Decision::JumpIn { seat: *seat },
+            Answer::Join { name } => Decision::Join { name: name.clone(), controller: Box::new(self.clone()) },
+            Answer::Leave { seat } => Decision::Leave { seat: *seat },
+            Answer::SortHand => Decision::SortHand,
+            Answer::Color(_) | Answer::SwapTarget(_) | Answer::Uno(_) => return None
+        }));
+        decision.unwrap_or(Decision::Leave { seat: observation.view.seat })
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::bots::Bot;
+    use crate::controller;
+
+    // A game between bots, recorded from the deal
+    fn recorded(seed: u64) -> (Game, Replay)
+    {
+        let mut lobby = Lobby::with_players(["Ann", "Ben", "Cat"]).unwrap();
+        lobby.set_config(GameConfig { seven_zero: true, max_turns: Some(300), ..GameConfig::default() });
+        let mut game = lobby.start_with_seed(seed).unwrap();
+        let recording = Recording::start(&game, seed);
+        let bots = [Bot::Random, Bot::Greedy, Bot::Aggressor];
+        let controllers = game.players().iter().zip(bots).enumerate()
+            .map(|(seat, (player, bot))| (player.id(), bot.seeded_controller(seed + seat as u64)))
+            .collect();
+        let mut controllers = recording.record(controllers);
+        controller::run_game(&mut game, &mut controllers, &mut ()).unwrap();
+        let replay = recording.finish(&game, Duration::ZERO);
+        (game, replay)
+    }
+
+    fn play_back(replay: &Replay) -> (Game, Result<(), ReplayError>)
+    {
+        let mut game = replay.start().unwrap();
+        let playback = Playback::new(replay);
+        let mut controllers = playback.controllers(&game);
+        controller::run_game(&mut game, &mut controllers, &mut ()).unwrap();
+        let checked = playback.finish(&game);
+        (game, checked)
+    }
+
+    #[test]
+    fn a_recorded_game_plays_back_the_same()
+    {
+        for seed in 0..20 {
+            let (original, replay) = recorded(seed);
+            let (replayed, checked) = play_back(&replay);
+            assert!(checked.is_ok(), "seed {}: {:?}", seed, checked);
+            assert_eq!(replayed.events(), original.events());
+            assert_eq!(replayed.end(), original.end());
+        }
+    }
+
+    #[test]
+    fn a_changed_answer_is_caught()
+    {
+        let (_, mut replay) = recorded(3);
+        let moves = replay.answers.iter().position(|answer| matches!(answer, Answer::Move(Move::Play(_))));
+        replay.answers[moves.unwrap()] = Answer::Move(Move::Draw);
+        assert!(matches!(play_back(&replay).1, Err(ReplayError::Diverged(_))));
+    }
+
+    // Players with their own ids and the given names
+    fn seating<S: AsRef<str>>(names: &[S]) -> Vec<(PlayerId, String)>
+    {
+        let mut lobby = Lobby::with_max_players(names.len());
+        for seat in 0..names.len() {
+            lobby.add_player(&format!("Player {}", seat)).unwrap();
+        }
+        lobby.seating().iter().zip(names).map(|(player, name)| (player.id(), name.as_ref().to_owned())).collect()
+    }
+
+    #[test]
+    fn a_seating_no_lobby_could_hold_is_corrupt()
+    {
+        let (_, replay) = recorded(0);
+        let mut same_id = seating(&["Ann", "Ben"]);
+        same_id[1].0 = same_id[0].0;
+        let seatings = [
+            same_id,
+            seating(&["Ann", "ann"]),
+            seating(&["Ann", " "]),
+            seating(&["Ann", "B\u{7}en"]),
+            seating(&["Ann"]),
+            seating(&(0..11).map(|seat| format!("Player {}", seat)).collect::<Vec<_>>())
+        ];
+        for seating in seatings {
+            let damaged = Replay { seating: seating.clone(), ..replay.clone() };
+            assert!(matches!(damaged.start(), Err(ReplayError::Corrupt(_))), "{:?}", seating);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn a_replay_reads_back_as_written()
+    {
+        let (_, replay) = recorded(7);
+        let path = std::env::temp_dir().join(format!("uno-replay-test-{}.toml", std::process::id()));
+        replay.write(&path).unwrap();
+        let read = Replay::read(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), replay);
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+use crate::game::{Card, Color, Move, PlayerId};
+use crate::view::SpectatorView;
+
+// Something a custom rule wants to happen after a card is played. Rules only describe
+// effects, the game applies them, so a replay with the same rules plays out the same
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RuleEffect
+{
+    // Skip one more player on top of whatever the card itself does
+    Skip,
+    Reverse,
+    Draw { player: PlayerId, number_of_cards: u8 },
+    RotateHands
+}
+
+impl fmt::Display for RuleEffect
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            RuleEffect::Skip => write!(f, "skip a player"),
+            RuleEffect::Reverse => write!(f, "reverse the direction of play"),
+            RuleEffect::Draw { number_of_cards, .. } => write!(f, "draw {} cards", number_of_cards),
+            RuleEffect::RotateHands => write!(f, "pass every hand on")
+        }
+    }
+}
+
+// A house rule added to a game with `Lobby::add_rule`. Every hook has a default that
+// leaves the official rules alone, so a rule only overrides what it changes
+pub trait RuleSet: fmt::Debug
+{
+    fn name(&self) -> &str;
+
+    // Decide whether `card` can go on `top`, or None to leave it to the other rules.
+    // The first rule to decide wins
+    fn is_playable(&self, _card: Card, _top: Card, _active_color: Option<Color>) -> Option<bool>
+    {
+        None
+    }
+
+    // Called once a card has been played, before its own effects are applied
+    fn on_card_played(&mut self, _view: &SpectatorView, _player: PlayerId, _card: Card) -> Vec<RuleEffect>
+    {
+        Vec::new()
+    }
+
+    // Adjust the moves `Game::legal_moves` offers. Use `is_playable` to actually
+    // forbid a card, this only changes what is suggested
+    fn modify_legal_moves(&self, _view: &SpectatorView, _moves: &mut Vec<Move>) {}
+
+    // Games are cloneable, so their rules have to be too
+    fn clone_box(&self) -> Box<dyn RuleSet>;
+}
+
+impl Clone for Box<dyn RuleSet>
+{
+    fn clone(&self) -> Self
+    {
+        self.clone_box()
+    }
+}
+
+// The strict color matching house rule, see `GameConfig::strict_color_matching`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictColorMatching;
+
+impl RuleSet for StrictColorMatching
+{
+    fn name(&self) -> &str
+    {
+        "Strict color matching"
+    }
+
+    fn is_playable(&self, card: Card, _top: Card, active_color: Option<Color>) -> Option<bool>
+    {
+        Some(card.is_wild() || (card.color.is_some() && card.color == active_color))
+    }
+
+    fn clone_box(&self) -> Box<dyn RuleSet>
+    {
+        Box::new(*self)
+    }
+}
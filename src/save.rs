@@ -0,0 +1,130 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{self, UtcTime};
+use crate::error::InvariantViolation;
+use crate::game::{Game, PlayerId, SavedGame};
+use crate::matches::SavedMatch;
+
+// Raised whenever the format changes, so an old save is turned down instead of misread
+pub const SAVE_VERSION: u32 = 1;
+
+// A game written to a TOML file to be finished later
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveFile
+{
+    pub version: u32,
+    // Seconds since the Unix epoch
+    pub saved_at: u64,
+    // Seeds the deck when the game carries on. TOML integers are signed, so it's kept
+    // as text
+    pub seed: String,
+    // The players bots were playing for
+    pub bots: Vec<PlayerId>,
+    pub game: SavedGame,
+    // The match the game is a round of, before the round's points are added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_match: Option<SavedMatch>
+}
+
+#[derive(Debug)]
+pub enum SaveError
+{
+    Io(io::Error),
+    // The file isn't a save at all, or has been damaged
+    Corrupt(String),
+    WrongVersion { found: u32 },
+    // The file reads fine but the game in it doesn't hold together
+    Invalid(InvariantViolation)
+}
+
+impl fmt::Display for SaveError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            SaveError::Io(error) => write!(f, "{}", error),
+            SaveError::Corrupt(reason) => write!(f, "the save file is damaged ({})", reason),
+            SaveError::WrongVersion { found } =>
+                write!(f, "the save file is version {}, and this version of Uno reads version {}", found, SAVE_VERSION),
+            SaveError::Invalid(violation) => write!(f, "the saved game doesn't make sense: {}", violation)
+        }
+    }
+}
+
+impl Error for SaveError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self {
+            SaveError::Io(error) => Some(error),
+            SaveError::Invalid(violation) => Some(violation),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for SaveError
+{
+    fn from(error: io::Error) -> Self
+    {
+        SaveError::Io(error)
+    }
+}
+
+impl SaveFile
+{
+    pub fn new(game: &Game, bots: Vec<PlayerId>, seed: u64, in_match: Option<SavedMatch>) -> SaveFile
+    {
+        SaveFile { version: SAVE_VERSION, saved_at: clock::now(), seed: seed.to_string(), bots, game: game.save(),
+            in_match }
+    }
+
+    // Creates the folders the file goes in if they aren't there yet
+    pub fn write(&self, path: &Path) -> Result<(), SaveError>
+    {
+        let text = toml::to_string(self).map_err(|error| SaveError::Corrupt(error.to_string()))?;
+        if let Some(folder) = path.parent().filter(|folder| !folder.as_os_str().is_empty()) {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    // The version is checked before anything else, so a save from another version is
+    // reported as that rather than as damaged
+    pub fn read(path: &Path) -> Result<SaveFile, SaveError>
+    {
+        let text = fs::read_to_string(path)?;
+        let table: toml::Table = text.parse().map_err(corrupt)?;
+        match table.get("version").and_then(toml::Value::as_integer) {
+            Some(version) if version == SAVE_VERSION as i64 => (),
+            Some(version) => return Err(SaveError::WrongVersion { found: version.try_into().unwrap_or(0) }),
+            None => return Err(SaveError::Corrupt(String::from("it has no version"))),
+        }
+        table.try_into().map_err(corrupt)
+    }
+
+    pub fn resume(&self) -> Result<Game, SaveError>
+    {
+        let seed = self.seed.parse().map_err(|_| SaveError::Corrupt(format!("'{}' isn't a seed", self.seed)))?;
+        Game::resume(&self.game, seed).map_err(SaveError::Invalid)
+    }
+
+    // e.g. "2026-10-15 14:03 UTC"
+    pub fn saved_at_text(&self) -> String
+    {
+        UtcTime::from_unix(self.saved_at).to_minute()
+    }
+}
+
+// TOML's messages can run over several lines
+fn corrupt(error: toml::de::Error) -> SaveError
+{
+    SaveError::Corrupt(error.message().trim().replace('\n', ", "))
+}
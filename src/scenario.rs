@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::{fs, io, path::Path};
+
+use crate::config::GameConfig;
+use crate::counting::CardCounter;
+use crate::error::{GameError, InvariantViolation, ParseCardError, UnknownRule};
+use crate::events::GameEvent;
+use crate::game::{Card, Color, Direction, Game, Lobby, PlayerId};
+
+// A puzzle: a game set up part way through, with a goal for the player whose turn it
+// is. Cards are written the way players type them, like "red 8", and seats count from
+// 0 in seating order. The files in examples/ show the TOML it's read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scenario
+{
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub description: String,
+    pub top_card: String,
+    // Needed when the top card is a wildcard. Otherwise it's the top card's color
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub active_color: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub current_seat: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub counter_clockwise: bool,
+    // The house rules that are on, by the names in `config::RULE_NAMES`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub goal: Option<Goal>,
+    pub players: Vec<ScenarioPlayer>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioPlayer
+{
+    pub name: String,
+    // Bots play as the settings say, everyone else at the keyboard
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bot: bool,
+    pub hand: Vec<String>
+}
+
+// What the player whose turn it is at the start has to do. Written `goal = "win"` or
+// `goal = { win_within = 2 }`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Goal
+{
+    Win,
+    // Go out within this many of their own turns, counting the first
+    WinWithin(u32)
+}
+
+#[derive(Debug)]
+pub enum ScenarioError
+{
+    #[cfg(feature = "serde")]
+    Io(io::Error),
+    // The file isn't TOML, or doesn't have the fields a scenario needs
+    Format(String),
+    Card(ParseCardError),
+    // More copies of a card than there are in a real deck
+    TooManyCopies { card: Card, copies: usize, in_deck: usize },
+    EmptyHand(String),
+    NoActiveColor,
+    ColorMismatch { top: Card, active_color: Color },
+    NoSuchSeat { seat: usize, number_of_players: usize },
+    Rule(UnknownRule),
+    // The players can't make a game, like when two have the same name or there's only one
+    Player(GameError),
+    Invalid(InvariantViolation)
+}
+
+impl fmt::Display for ScenarioError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            #[cfg(feature = "serde")]
+            ScenarioError::Io(error) => write!(f, "{}", error),
+            ScenarioError::Format(reason) => write!(f, "the scenario can't be read ({})", reason),
+            ScenarioError::Card(error) => write!(f, "{}", error),
+            ScenarioError::TooManyCopies { card, copies, in_deck } =>
+                write!(f, "there are {} of {} but a deck only has {}", copies, card, in_deck),
+            ScenarioError::EmptyHand(name) => write!(f, "{} has no cards, so the game would already be over", name),
+            ScenarioError::NoActiveColor => write!(f, "a wildcard is on top, so the active color has to be given"),
+            ScenarioError::ColorMismatch { top, active_color } =>
+                write!(f, "the active color is {} but the top card is a {}", active_color, top),
+            ScenarioError::NoSuchSeat { seat, number_of_players } => write!(f,
+                "it's seat {}'s turn, but the seats go from 0 to {}", seat, number_of_players.saturating_sub(1)),
+            ScenarioError::Rule(error) => write!(f, "{}", error),
+            ScenarioError::Player(error) => write!(f, "{}", error),
+            ScenarioError::Invalid(violation) => write!(f, "the game doesn't make sense: {}", violation)
+        }
+    }
+}
+
+impl Error for ScenarioError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self {
+            #[cfg(feature = "serde")]
+            ScenarioError::Io(error) => Some(error),
+            ScenarioError::Card(error) => Some(error),
+            ScenarioError::Player(error) => Some(error),
+            ScenarioError::Invalid(violation) => Some(violation),
+            _ => None
+        }
+    }
+}
+
+impl From<ParseCardError> for ScenarioError
+{
+    fn from(error: ParseCardError) -> Self
+    {
+        ScenarioError::Card(error)
+    }
+}
+
+impl Scenario
+{
+    #[cfg(feature = "serde")]
+    pub fn read(path: &Path) -> Result<Scenario, ScenarioError>
+    {
+        let text = fs::read_to_string(path).map_err(ScenarioError::Io)?;
+        toml::from_str(&text).map_err(|error| ScenarioError::Format(error.message().trim().replace('\n', ", ")))
+    }
+
+    // Set the game up, drawing from a deck seeded with `seed` once it's under way. The
+    // players get ids in seating order
+    pub fn start(&self, seed: u64) -> Result<Game, ScenarioError>
+    {
+        let top_card: Card = self.top_card.parse()?;
+        let hands = self.players.iter()
+            .map(|player| player.hand.iter().map(|card| card.parse()).collect::<Result<Vec<Card>, _>>())
+            .collect::<Result<Vec<Vec<Card>>, _>>()?;
+        self.check_copies(top_card, &hands)?;
+        if let Some(player) = self.players.iter().find(|player| player.hand.is_empty()) {
+            return Err(ScenarioError::EmptyHand(player.name.clone()));
+        }
+        let active_color = match (&self.active_color, top_card.color) {
+            (Some(color), top) => {
+                let color: Color = color.parse()?;
+                if top.is_some_and(|top| top != color) {
+                    return Err(ScenarioError::ColorMismatch { top: top_card, active_color: color });
+                }
+                color
+            },
+            (None, Some(color)) => color,
+            (None, None) => return Err(ScenarioError::NoActiveColor)
+        };
+        if self.current_seat >= self.players.len() {
+            return Err(ScenarioError::NoSuchSeat { seat: self.current_seat, number_of_players: self.players.len() });
+        }
+
+        let mut config = GameConfig::default();
+        for rule in &self.rules {
+            config.set_rule(rule, true).map_err(ScenarioError::Rule)?;
+        }
+        let mut lobby = Lobby::new();
+        lobby.set_config(config);
+        for player in &self.players {
+            lobby.add_player(&player.name).map_err(ScenarioError::Player)?;
+        }
+
+        // A game dealt the usual way, with everything that was dealt swapped for the
+        // scenario's cards
+        let mut saved = lobby.start_with_seed(seed).map_err(ScenarioError::Player)?.save();
+        saved.table.top_card = top_card;
+        saved.table.recently_played = vec![top_card];
+        saved.table.discarded = vec![top_card];
+        saved.table.active_color = Some(active_color);
+        saved.table.direction = if self.counter_clockwise { Direction::CounterClockwise } else { Direction::Clockwise };
+        saved.table.current_seat = self.current_seat;
+        saved.table.pending_action = None;
+        saved.table.recent_turns.clear();
+        saved.hands = hands;
+        saved.drawn_card = None;
+        Game::resume(&saved, seed).map_err(ScenarioError::Invalid)
+    }
+
+    // Every card in play, the top card included, has to be one a real deck could hold
+    fn check_copies(&self, top_card: Card, hands: &[Vec<Card>]) -> Result<(), ScenarioError>
+    {
+        let mut copies: HashMap<Card, usize> = HashMap::new();
+        for card in hands.iter().flatten().chain([&top_card]) {
+            *copies.entry(*card).or_default() += 1;
+        }
+        let deck = CardCounter::standard();
+        for (card, copies) in copies {
+            let in_deck = deck.remaining(card.card_type, card.color.unwrap_or(Color::Red));
+            if copies > in_deck {
+                return Err(ScenarioError::TooManyCopies { card, copies, in_deck });
+            }
+        }
+        Ok(())
+    }
+
+    // The seats played by bots
+    pub fn bot_seats(&self) -> impl Iterator<Item = usize> + '_
+    {
+        self.players.iter().enumerate().filter(|(_, player)| player.bot).map(|(seat, _)| seat)
+    }
+}
+
+impl Goal
+{
+    // Whether `player`, whose turn it was when the scenario started, did what they had
+    // to in `game`
+    pub fn met(self, game: &Game, player: PlayerId) -> bool
+    {
+        if game.winner().map(|winner| winner.id()) != Some(player) {
+            return false;
+        }
+        match self {
+            Goal::Win => true,
+            // Their first turn was already under way, so it has no event of its own
+            Goal::WinWithin(turns) => {
+                let taken = 1 + game.events().iter()
+                    .filter(|recorded| recorded.event == (GameEvent::TurnStarted { player }))
+                    .count();
+                taken <= turns as usize
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::game::CardType;
+
+    // Seat 0's turn with `top` on the discard pile, and each seat holding its hand
+    fn scenario(top: &str, hands: &[&[&str]]) -> Scenario
+    {
+        let players = hands.iter().enumerate()
+            .map(|(seat, hand)| ScenarioPlayer {
+                name: format!("P{}", seat),
+                bot: false,
+                hand: hand.iter().map(|card| card.to_string()).collect()
+            })
+            .collect();
+        Scenario {
+            name: String::from("Test"),
+            description: String::new(),
+            top_card: String::from(top),
+            active_color: None,
+            current_seat: 0,
+            counter_clockwise: false,
+            rules: Vec::new(),
+            goal: None,
+            players
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn example(file: &str) -> Scenario
+    {
+        Scenario::read(&Path::new(env!("CARGO_MANIFEST_DIR")).join("examples").join(file)).unwrap()
+    }
+
+    #[test]
+    fn the_table_is_set_as_written()
+    {
+        let mut written = scenario("wild", &[&["red 1", "blue 2"], &["green 3"], &["yellow 4", "yellow 5"]]);
+        written.active_color = Some(String::from("blue"));
+        written.current_seat = 2;
+        written.counter_clockwise = true;
+        written.rules = vec![String::from("stacking")];
+        let game = written.start(3).unwrap();
+
+        assert_eq!(game.top_card(), Card { card_type: CardType::Wildcard, color: None });
+        assert_eq!(game.active_color(), Some(Color::Blue));
+        assert_eq!(game.current_seat(), 2);
+        assert_eq!(game.direction(), Direction::CounterClockwise);
+        assert!(game.config().stacking);
+        let sizes: Vec<usize> = game.players().iter().map(|player| player.number_of_cards()).collect();
+        assert_eq!(sizes, [2, 1, 2]);
+    }
+
+    #[test]
+    fn mistakes_are_caught_before_the_game_starts()
+    {
+        let start = |scenario: Scenario| scenario.start(0).err().unwrap();
+        assert!(matches!(start(scenario("red 5", &[&["red 11"], &["blue 2"]])), ScenarioError::Card(_)));
+        assert!(matches!(start(scenario("red 5", &[&["red 5", "red 5"], &["blue 2"]])),
+            ScenarioError::TooManyCopies { copies: 3, in_deck: 2, .. }));
+        assert!(matches!(start(scenario("red 5", &[&[], &["blue 2"]])),
+            ScenarioError::EmptyHand(name) if name == "P0"));
+        assert!(matches!(start(scenario("wild", &[&["red 1"], &["blue 2"]])), ScenarioError::NoActiveColor));
+        assert!(matches!(start(scenario("red 5", &[&["red 1"]])), ScenarioError::Player(_)));
+
+        let mut mismatched = scenario("red 5", &[&["red 1"], &["blue 2"]]);
+        mismatched.active_color = Some(String::from("blue"));
+        assert!(matches!(start(mismatched), ScenarioError::ColorMismatch { active_color: Color::Blue, .. }));
+
+        let mut missing_seat = scenario("red 5", &[&["red 1"], &["blue 2"]]);
+        missing_seat.current_seat = 2;
+        assert_eq!(start(missing_seat).to_string(), "it's seat 2's turn, but the seats go from 0 to 1");
+
+        let mut unknown_rule = scenario("red 5", &[&["red 1"], &["blue 2"]]);
+        unknown_rule.rules = vec![String::from("double-uno")];
+        assert!(matches!(start(unknown_rule), ScenarioError::Rule(_)));
+
+        let mut twins = scenario("red 5", &[&["red 1"], &["blue 2"]]);
+        twins.players[1].name = String::from("P0");
+        assert!(matches!(start(twins), ScenarioError::Player(_)));
+    }
+
+    #[test]
+    fn goals_are_only_met_by_the_player_going_out_in_time()
+    {
+        let mut game = scenario("blue 5", &[&["red 4", "red skip", "blue skip"], &["green 2", "yellow 1"]]).start(0)
+            .unwrap();
+        let (you, other) = (game.players()[0].id(), game.players()[1].id());
+        assert!(!Goal::Win.met(&game, you));
+
+        // Each Skip gives seat 0 another turn
+        for index in [2, 1, 0] {
+            game.play(index).unwrap();
+        }
+        assert!(Goal::Win.met(&game, you));
+        assert!(Goal::WinWithin(3).met(&game, you));
+        assert!(!Goal::WinWithin(2).met(&game, you));
+        assert!(!Goal::Win.met(&game, other));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn the_examples_can_be_played()
+    {
+        let puzzle = example("skip_to_the_finish.toml");
+        assert_eq!(puzzle.goal, Some(Goal::WinWithin(3)));
+        assert_eq!(puzzle.bot_seats().collect::<Vec<usize>>(), [1]);
+        let mut game = puzzle.start(0).unwrap();
+        let you = game.players()[0].id();
+        for index in [2, 1, 0] {
+            game.play(index).unwrap();
+        }
+        assert!(Goal::WinWithin(3).met(&game, you));
+
+        let puzzle = example("one_card_left.toml");
+        assert_eq!(puzzle.goal, Some(Goal::WinWithin(2)));
+        let game = puzzle.start(0).unwrap();
+        assert!(game.config().draw_four_challenge);
+        assert_eq!(game.active_color(), Some(Color::Green));
+        assert_eq!(game.players()[1].number_of_cards(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn a_missing_file_is_an_io_error()
+    {
+        let error = Scenario::read(Path::new("examples/no_such_puzzle.toml")).unwrap_err();
+        assert!(matches!(error, ScenarioError::Io(_)));
+    }
+}
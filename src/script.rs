@@ -0,0 +1,140 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::io;
+use std::process;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use uno::console::{Console, StdioConsole};
+
+// Input read from a file instead of the keyboard, so a whole game can be played
+// without anyone at it. Each line answers one prompt. Lines starting with '#' are
+// skipped, and a line starting with '!' is input the game should turn down. A line
+// of just '~' lets a timed read run out, and the script keeps its own clock so that
+// no time passes otherwise. Running out of lines or having a line turned down
+// unexpectedly ends the program with an error
+#[derive(Debug, Clone)]
+pub struct ScriptConsole
+{
+    script: Rc<RefCell<Script>>,
+    output: StdioConsole,
+    echo: bool
+}
+
+#[derive(Debug)]
+struct Script
+{
+    lines: Vec<String>,
+    // The line number of the next line to read
+    next: usize,
+    // The line number of a '!' line that hasn't been turned down yet
+    awaiting_rejection: Option<usize>,
+    started: Instant,
+    // How long '~' lines have let pass
+    waited: Duration
+}
+
+impl ScriptConsole
+{
+    pub fn new(text: &str) -> ScriptConsole
+    {
+        let lines = text.lines().map(str::to_owned).collect();
+        let script = Script { lines, next: 1, awaiting_rejection: None, started: Instant::now(),
+            waited: Duration::ZERO };
+        ScriptConsole { script: Rc::new(RefCell::new(script)), output: StdioConsole, echo: true }
+    }
+
+    // Leave the lines out of the output, for output a program reads
+    pub fn without_echo(self) -> ScriptConsole
+    {
+        ScriptConsole { echo: false, ..self }
+    }
+}
+
+fn script_failed(message: impl fmt::Display) -> !
+{
+    eprintln!("\nerror: {}", message);
+    process::exit(1)
+}
+
+impl Console for ScriptConsole
+{
+    // Echoed so the output reads as if it had been typed, unless `without_echo` was used
+    fn read_line(&mut self) -> io::Result<String>
+    {
+        let mut script = self.script.borrow_mut();
+        if let Some(number) = script.awaiting_rejection {
+            script_failed(format!("line {} of the script should have been turned down, but it was accepted", number));
+        }
+        loop {
+            let number = script.next;
+            let Some(line) = script.lines.get(number - 1).cloned() else {
+                script_failed(format!("the script ran out after line {} before the game was over", number - 1));
+            };
+            script.next += 1;
+            if line.starts_with('#') {
+                continue;
+            }
+            let line = match line.strip_prefix('!') {
+                Some(line) => {
+                    script.awaiting_rejection = Some(number);
+                    line.to_owned()
+                },
+                None => line
+            };
+            if self.echo {
+                self.output.write(&format!("{}\n", line));
+            }
+            break Ok(format!("{}\n", line));
+        }
+    }
+
+    fn read_line_within(&mut self, timeout: Duration) -> io::Result<Option<String>>
+    {
+        {
+            let mut script = self.script.borrow_mut();
+            while script.lines.get(script.next - 1).is_some_and(|line| line.starts_with('#')) {
+                script.next += 1;
+            }
+            if script.lines.get(script.next - 1).is_some_and(|line| line == "~") {
+                script.next += 1;
+                script.waited += timeout;
+                if self.echo {
+                    self.output.write("~\n");
+                }
+                return Ok(None);
+            }
+        }
+        self.read_line().map(Some)
+    }
+
+    fn now(&self) -> Instant
+    {
+        let script = self.script.borrow();
+        script.started + script.waited
+    }
+
+    fn write(&mut self, text: &str)
+    {
+        self.output.write(text);
+    }
+
+    fn is_tty(&self) -> bool
+    {
+        self.output.is_tty()
+    }
+
+    fn clear(&mut self)
+    {
+        self.output.clear();
+    }
+
+    // Input that couldn't be used has to have been marked with '!'
+    fn input_rejected(&mut self)
+    {
+        let mut script = self.script.borrow_mut();
+        if script.awaiting_rejection.take().is_none() {
+            script_failed(format!("line {} of the script was turned down", script.next - 1));
+        }
+    }
+}
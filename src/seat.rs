@@ -0,0 +1,136 @@
+use crate::error::{GameError, JumpInError};
+use crate::game::{ChallengeOutcome, CatchOutcome, Color, DrawUntilOutcome, DrawnCard, Game, JumpInOutcome,
+                  PenaltyOutcome, PlayOutcome, PlayerId};
+
+// Acts on the game as the player in one seat. Turn actions are rejected with
+// `GameError::NotYourTurn` unless it's that player's turn. Calling Uno, catching a
+// player who forgot to and jumping in can be done at any time, as the rules allow
+pub struct SeatHandle<'a>
+{
+    game: &'a mut Game,
+    seat: usize
+}
+
+impl Game
+{
+    // A handle for acting as the player in `seat`
+    pub fn seat(&mut self, seat: usize) -> Result<SeatHandle<'_>, GameError>
+    {
+        if seat >= self.players().len() {
+            return Err(GameError::InvalidSeat(seat));
+        }
+        Ok(SeatHandle { game: self, seat })
+    }
+}
+
+impl SeatHandle<'_>
+{
+    pub fn seat(&self) -> usize
+    {
+        self.seat
+    }
+
+    pub fn id(&self) -> PlayerId
+    {
+        self.game.players()[self.seat].id()
+    }
+
+    pub fn game(&self) -> &Game
+    {
+        self.game
+    }
+
+    pub fn is_my_turn(&self) -> bool
+    {
+        self.game.current_seat() == self.seat
+    }
+
+    fn check_turn(&self) -> Result<(), GameError>
+    {
+        if self.is_my_turn() { Ok(()) } else { Err(GameError::NotYourTurn { seat: self.seat }) }
+    }
+
+    pub fn play(&mut self, card_index: usize) -> Result<PlayOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.play(card_index)
+    }
+
+    pub fn play_wild(&mut self, card_index: usize, color: Color) -> Result<PlayOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.play_wild(card_index, color)
+    }
+
+    pub fn draw(&mut self) -> Result<DrawnCard, GameError>
+    {
+        self.check_turn()?;
+        self.game.draw_one()
+    }
+
+    pub fn draw_until_playable(&mut self, limit: Option<usize>) -> Result<DrawUntilOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.draw_until_playable(limit)
+    }
+
+    pub fn play_drawn(&mut self) -> Result<PlayOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.play_drawn()
+    }
+
+    pub fn play_drawn_wild(&mut self, color: Color) -> Result<PlayOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.play_drawn_wild(color)
+    }
+
+    pub fn keep_drawn(&mut self) -> Result<PlayerId, GameError>
+    {
+        self.check_turn()?;
+        self.game.keep_drawn()
+    }
+
+    pub fn accept_penalty(&mut self) -> Result<PenaltyOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.accept_penalty()
+    }
+
+    pub fn set_wildcard_color(&mut self, color: Color) -> Result<Option<PlayOutcome>, GameError>
+    {
+        self.check_turn()?;
+        self.game.set_wildcard_color(color)
+    }
+
+    pub fn choose_swap_target(&mut self, target_seat: usize) -> Result<PlayOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.choose_swap_target(target_seat)
+    }
+
+    pub fn challenge_draw_four(&mut self) -> Result<ChallengeOutcome, GameError>
+    {
+        self.check_turn()?;
+        self.game.challenge_draw_four()
+    }
+
+    // Allowed out of turn
+    pub fn call_uno(&mut self) -> Result<(), GameError>
+    {
+        self.game.call_uno(self.seat)
+    }
+
+    // Allowed out of turn
+    pub fn catch(&mut self, target_seat: usize) -> Result<CatchOutcome, GameError>
+    {
+        self.game.catch_failure_to_call(self.seat, target_seat)
+    }
+
+    // Allowed out of turn, since that is the point of jumping in
+    pub fn jump_in(&mut self, card_index: usize) -> Result<JumpInOutcome, JumpInError>
+    {
+        self.game.jump_in(self.seat, card_index)
+    }
+}
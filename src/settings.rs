@@ -0,0 +1,356 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uno::bots::{Bot, Difficulty};
+use uno::config::GameConfig;
+use uno::console::Console;
+use uno::game::RECENT_TURNS;
+use uno::messages::Language;
+use uno::render::{CardStyle, Renderer, DEFAULT_WIDTH};
+
+use uno::cli::{parse_duration, BotDelay, CliOptions};
+
+// Whether cards are printed in color
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode
+{
+    // Only when printing to a terminal
+    #[default]
+    Auto,
+    Always,
+    Never
+}
+
+// How a card's color is shown. Symbols mark every card with a letter for its color,
+// for players who can't tell the colors apart
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorDisplay
+{
+    #[default]
+    Colors,
+    Symbols,
+    // Symbols with the colors as well, when colors are on
+    Both
+}
+
+// Whether the compact style uses colored squares, or letters for terminals that can't
+// show them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Glyphs
+{
+    // Squares when the locale is UTF-8
+    #[default]
+    Auto,
+    Unicode,
+    Letters
+}
+
+// Everything the binary can be told before a game, from the config file and then the
+// command line. Written out by `--print-config`, so it can be pasted into the file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config
+{
+    // Seated before any bots. With none here or on the command line, the lobby menu
+    // sets the game up
+    pub players: Vec<String>,
+    pub bots: usize,
+    pub bot_style: String,
+    pub difficulty: String,
+    // House rules to turn on, see `config::RULE_NAMES`
+    pub rules: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hand_size: Option<u8>,
+    pub junior: bool,
+    pub color: ColorMode,
+    pub color_mode: ColorDisplay,
+    pub style: CardStyle,
+    pub glyphs: Glyphs,
+    // Clear the screen between players when more than one person is playing
+    pub privacy: bool,
+    // Let players type 'hint' for a suggestion
+    pub hints: bool,
+    // Ask before playing a Draw Four that could be challenged, see `CliOptions::draw_four_warning`
+    pub draw_four_warning: bool,
+    // Sort every player's hand at the start of their turn
+    pub auto_sort: bool,
+    // Write for a screen reader, see `Renderer::accessible`. Overrides the colors and
+    // the card style
+    pub accessible: bool,
+    // How many turns 'history' shows
+    pub history_length: usize,
+    // Play matches to this many points instead of single games, see `matches::Match`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_target: Option<u32>,
+    // How long each player has for their turn, like "30s". Without one, there's no limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_timer: Option<String>,
+    // Where 'save' writes the game, instead of `Config::default_save_path`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save_file: Option<PathBuf>,
+    // Where to log every game in full, everyone's cards included, whatever `privacy` says
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<PathBuf>,
+    // Where to write each game that's dealt, for `uno replay` to play back
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay_file: Option<PathBuf>,
+    // The language everything is written in. Without one, it comes from the locale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    pub bot_delay: String,
+    pub bot_jitter: String
+}
+
+impl Default for Config
+{
+    fn default() -> Self
+    {
+        Config {
+            players: Vec::new(),
+            bots: 0,
+            bot_style: String::from("greedy"),
+            difficulty: String::from("hard"),
+            rules: Vec::new(),
+            hand_size: None,
+            junior: false,
+            color: ColorMode::Auto,
+            color_mode: ColorDisplay::Colors,
+            style: CardStyle::Plain,
+            glyphs: Glyphs::Auto,
+            privacy: true,
+            hints: true,
+            draw_four_warning: true,
+            auto_sort: false,
+            accessible: false,
+            history_length: 8,
+            match_target: None,
+            turn_timer: None,
+            save_file: None,
+            log_file: None,
+            replay_file: None,
+            language: None,
+            bot_delay: String::from("800ms"),
+            bot_jitter: String::from("400ms")
+        }
+    }
+}
+
+impl Config
+{
+    // `$XDG_CONFIG_HOME/uno/config.toml`, or `~/.config/uno/config.toml`
+    pub fn default_path() -> Option<PathBuf>
+    {
+        let base = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+        Some(base.join("uno").join("config.toml"))
+    }
+
+    // `$XDG_DATA_HOME/uno/save.toml`, or `~/.local/share/uno/save.toml`
+    pub fn default_save_path() -> Option<PathBuf>
+    {
+        let base = std::env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("share")))?;
+        Some(base.join("uno").join("save.toml"))
+    }
+
+    pub fn save_path(&self) -> Option<PathBuf>
+    {
+        self.save_file.clone().or_else(Config::default_save_path)
+    }
+
+    // Read the file at `path`, or the default file if there is one. A file that was
+    // asked for by name has to exist
+    pub fn load(path: Option<&Path>) -> Result<Config, String>
+    {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Config::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default())
+            }
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if !required && !path.exists() => return Ok(Config::default()),
+            Err(error) => return Err(format!("can't read {}: {}", path.display(), error))
+        };
+        let config: Config = toml::from_str(&text).map_err(|error| format!("in {}: {}", path.display(), error))?;
+        config.validate().map_err(|error| format!("in {}: {}", path.display(), error))?;
+        Ok(config)
+    }
+
+    // Check every setting that is stored as text
+    pub fn validate(&self) -> Result<(), String>
+    {
+        parse_bot(&self.bot_style)?;
+        parse_difficulty(&self.difficulty)?;
+        parse_duration_arg(&self.bot_delay)?;
+        parse_duration_arg(&self.bot_jitter)?;
+        if !(1..=RECENT_TURNS).contains(&self.history_length) {
+            return Err(format!("history_length must be from 1 to {}", RECENT_TURNS));
+        }
+        if self.match_target == Some(0) {
+            return Err(String::from("match_target has to be more than 0"));
+        }
+        if let Some(limit) = &self.turn_timer {
+            if parse_duration_arg(limit)?.is_zero() {
+                return Err(String::from("turn_timer has to be longer than 0"));
+            }
+        }
+        if self.hand_size.is_some_and(|size| !(1..=30).contains(&size)) {
+            return Err(String::from("hand_size must be from 1 to 30"));
+        }
+        let mut config = GameConfig::default();
+        for rule in &self.rules {
+            config.set_rule(rule, true).map_err(|error| error.to_string())?;
+        }
+        Ok(())
+    }
+
+    // The junior preset first, then the hand size and rules on top of it
+    pub fn game_config(&self) -> GameConfig
+    {
+        let mut config = if self.junior { GameConfig::junior() } else { GameConfig::default() };
+        if let Some(hand_size) = self.hand_size {
+            config.hand_size = Some(hand_size as usize);
+        }
+        for rule in &self.rules {
+            config.set_rule(rule, true).expect("Rules are checked when they're read");
+        }
+        config
+    }
+
+    pub fn bot_style(&self) -> Bot
+    {
+        parse_bot(&self.bot_style).expect("The bot style is checked when it's read")
+    }
+
+    pub fn difficulty(&self) -> Difficulty
+    {
+        parse_difficulty(&self.difficulty).expect("The difficulty is checked when it's read")
+    }
+
+    pub fn language(&self) -> Language
+    {
+        self.language.unwrap_or_else(Language::from_env)
+    }
+
+    pub fn turn_timer(&self) -> Option<Duration>
+    {
+        self.turn_timer.as_deref()
+            .map(|limit| parse_duration_arg(limit).expect("The turn timer is checked when it's read"))
+    }
+
+    pub fn bot_delay(&self) -> BotDelay
+    {
+        BotDelay {
+            delay: parse_duration_arg(&self.bot_delay).expect("The bot delay is checked when it's read"),
+            jitter: parse_duration_arg(&self.bot_jitter).expect("The bot jitter is checked when it's read")
+        }
+    }
+
+    // Colors are left off when asked to, and otherwise on automatically only for a
+    // terminal, unless `NO_COLOR` is set (see no-color.org)
+    pub fn colors_on(&self, console: &impl Console) -> bool
+    {
+        match self.color {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => console.is_tty() && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+        }
+    }
+
+    // The first locale variable that's set decides, as it does for the C library
+    pub fn unicode_on(&self) -> bool
+    {
+        match self.glyphs {
+            Glyphs::Unicode => true,
+            Glyphs::Letters => false,
+            Glyphs::Auto => ["LC_ALL", "LC_CTYPE", "LANG"].into_iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .find(|value| !value.is_empty())
+                .is_some_and(|locale| {
+                    let locale = locale.to_ascii_lowercase();
+                    locale.contains("utf-8") || locale.contains("utf8")
+                })
+        }
+    }
+
+    pub fn cli_options(&self, console: &impl Console) -> CliOptions
+    {
+        // A screen reader gets one line for each thing, with nothing but words on it
+        let renderer = Renderer {
+            color: !self.accessible && self.color_mode != ColorDisplay::Symbols && self.colors_on(console),
+            symbols: !self.accessible && self.color_mode != ColorDisplay::Colors,
+            style: if self.accessible { CardStyle::Plain } else { self.style },
+            unicode: self.unicode_on(),
+            width: terminal_width(),
+            columns: !self.accessible && console.is_tty(),
+            accessible: self.accessible,
+            messages: self.language().messages()
+        };
+        CliOptions { bot_delay: self.bot_delay(), renderer, privacy: self.privacy, hints: self.hints,
+            auto_sort: self.auto_sort, history_length: self.history_length, save_file: self.save_path(),
+            log_file: self.log_file.clone(), turn_timer: self.turn_timer(), replay_file: self.replay_file.clone(),
+            draw_four_warning: self.draw_four_warning }
+    }
+}
+
+// From `COLUMNS`, which most shells set for a terminal
+fn terminal_width() -> usize
+{
+    std::env::var("COLUMNS").ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+// Bots are named with dashes on the command line and in the file, like "color-fixer"
+pub fn bot_key(bot: Bot) -> String
+{
+    bot.name().replace(' ', "-")
+}
+
+pub fn parse_bot(name: &str) -> Result<Bot, String>
+{
+    Bot::from_name(&name.replace('-', " ")).ok_or_else(|| {
+        let names: Vec<String> = Bot::ALL.into_iter().map(bot_key).collect();
+        format!("there's no bot called '{}'. The bots are: {}", name, names.join(", "))
+    })
+}
+
+pub fn parse_difficulty(name: &str) -> Result<Difficulty, String>
+{
+    Difficulty::ALL.into_iter()
+        .find(|difficulty| difficulty.to_string().eq_ignore_ascii_case(name.trim()))
+        .ok_or(format!("'{}' isn't a difficulty. Try easy, normal or hard", name))
+}
+
+pub fn parse_style(name: &str) -> Result<CardStyle, String>
+{
+    match name.trim().to_ascii_lowercase().as_str() {
+        "plain" => Ok(CardStyle::Plain),
+        "fancy" => Ok(CardStyle::Fancy),
+        "compact" => Ok(CardStyle::Compact),
+        _ => Err(format!("'{}' isn't a style. Try plain, fancy or compact", name))
+    }
+}
+
+pub fn parse_language(code: &str) -> Result<Language, String>
+{
+    Language::from_code(code).ok_or_else(|| {
+        let codes: Vec<&str> = Language::ALL.into_iter().map(Language::code).collect();
+        format!("there's no translation for '{}'. The languages are: {}", code, codes.join(", "))
+    })
+}
+
+pub fn parse_duration_arg(text: &str) -> Result<Duration, String>
+{
+    parse_duration(text).ok_or(format!("'{}' isn't a duration. Try something like 800ms or 1.5s", text))
+}
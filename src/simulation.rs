@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use crate::bots::Bot;
+use crate::config::GameConfig;
+use crate::controller::{self, Controllers, PlayerController};
+use crate::error::GameError;
+use crate::game::{GameEnd, Lobby};
+
+// Simulated games stop here when the config has no turn limit of its own, so a pair
+// of bots that never get rid of their cards can't run forever
+pub const SIMULATION_MAX_TURNS: u32 = 1000;
+
+pub type ControllerFactory = Arc<dyn Fn(u64) -> Box<dyn PlayerController> + Send + Sync>;
+
+// A named way to make a fresh controller for each game. The seed it is given is
+// different every game, but always the same for the same game
+#[derive(Clone)]
+pub struct Contestant
+{
+    name: String,
+    factory: ControllerFactory
+}
+
+impl Contestant
+{
+    pub fn new<F>(name: &str, factory: F) -> Contestant
+        where F: Fn(u64) -> Box<dyn PlayerController> + Send + Sync + 'static
+    {
+        Contestant { name: name.to_owned(), factory: Arc::new(factory) }
+    }
+
+    pub fn bot(bot: Bot) -> Contestant
+    {
+        Contestant::new(bot.name(), move |seed| bot.seeded_controller(seed))
+    }
+
+    pub fn name(&self) -> &str
+    {
+        &self.name
+    }
+
+    pub fn controller(&self, seed: u64) -> Box<dyn PlayerController>
+    {
+        (self.factory)(seed)
+    }
+}
+
+// How a single simulated game went
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord
+{
+    // The seat of the winner, None if nobody won
+    pub winner: Option<usize>,
+    pub turns: u32,
+    pub turn_limit_reached: bool,
+    pub cards_drawn: usize,
+    pub penalty_cards: usize
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeatResults
+{
+    pub seat: usize,
+    pub controller: String,
+    pub wins: usize,
+    pub win_rate: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TurnPercentiles
+{
+    pub p10: u32,
+    pub p50: u32,
+    pub p90: u32,
+    pub p99: u32,
+    pub max: u32
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationResults
+{
+    pub games: usize,
+    pub seats: Vec<SeatResults>,
+    // Games that ended without a winner
+    pub no_winner: usize,
+    pub turn_limit_reached: usize,
+    pub mean_turns: f64,
+    pub turn_percentiles: TurnPercentiles,
+    // Across all players, per game
+    pub mean_cards_drawn: f64,
+    pub mean_penalty_cards: f64,
+    // The share of games where anyone had to draw a penalty
+    pub penalty_frequency: f64
+}
+
+impl SimulationResults
+{
+    // Sum up the records of games played by the given controllers, in seating order
+    pub fn from_records(controllers: &[&str], records: &[GameRecord]) -> SimulationResults
+    {
+        let games = records.len();
+        let rate = |count: usize| if games == 0 { 0.0 } else { count as f64 / games as f64 };
+
+        let seats = controllers.iter().enumerate()
+            .map(|(seat, controller)| {
+                let wins = records.iter().filter(|record| record.winner == Some(seat)).count();
+                SeatResults { seat, controller: controller.to_string(), wins, win_rate: rate(wins) }
+            })
+            .collect();
+
+        let mut turns: Vec<u32> = records.iter().map(|record| record.turns).collect();
+        turns.sort_unstable();
+        // Nearest rank
+        let percentile = |percent: usize| match turns.len() {
+            0 => 0,
+            length => turns[((length * percent).div_ceil(100)).clamp(1, length) - 1]
+        };
+
+        SimulationResults {
+            games,
+            seats,
+            no_winner: records.iter().filter(|record| record.winner.is_none()).count(),
+            turn_limit_reached: records.iter().filter(|record| record.turn_limit_reached).count(),
+            mean_turns: rate(turns.iter().map(|&turns| turns as usize).sum()),
+            turn_percentiles: TurnPercentiles {
+                p10: percentile(10),
+                p50: percentile(50),
+                p90: percentile(90),
+                p99: percentile(99),
+                max: turns.last().copied().unwrap_or(0)
+            },
+            mean_cards_drawn: rate(records.iter().map(|record| record.cards_drawn).sum()),
+            mean_penalty_cards: rate(records.iter().map(|record| record.penalty_cards).sum()),
+            penalty_frequency: rate(records.iter().filter(|record| record.penalty_cards > 0).count())
+        }
+    }
+}
+
+// Plays many games between the same controllers without anyone watching. Every game
+// is seeded from the master seed, so a run can be repeated exactly
+pub struct SimulationRunner
+{
+    roster: Vec<Contestant>,
+    config: GameConfig,
+    seed: u64,
+    games: usize
+}
+
+impl SimulationRunner
+{
+    // The roster is in seating order, one contestant per seat
+    pub fn new(roster: Vec<Contestant>, config: GameConfig, seed: u64, games: usize) -> SimulationRunner
+    {
+        SimulationRunner { roster, config, seed, games }
+    }
+
+    pub fn roster(&self) -> &[Contestant]
+    {
+        &self.roster
+    }
+
+    pub fn run(&self) -> Result<SimulationResults, GameError>
+    {
+        let records = (0..self.games)
+            .map(|index| self.play(game_seed(self.seed, index as u64)))
+            .collect::<Result<Vec<GameRecord>, GameError>>()?;
+        Ok(self.results(&records))
+    }
+
+    // Like `run`, but spread over `threads` threads, or as many as there are cores when
+    // it's 0. Each game's seed comes from its index, and the records are summed up in
+    // order, so the results are the same as `run` gives whatever the thread count
+    #[cfg(feature = "rayon")]
+    pub fn run_parallel(&self, threads: usize) -> Result<SimulationResults, GameError>
+    {
+        use rayon::prelude::*;
+
+        let play_all = || (0..self.games)
+            .into_par_iter()
+            .map(|index| self.play(game_seed(self.seed, index as u64)))
+            .collect::<Result<Vec<GameRecord>, GameError>>();
+        let records = match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => pool.install(play_all)?,
+            Err(_) => play_all()?
+        };
+        Ok(self.results(&records))
+    }
+
+    fn results(&self, records: &[GameRecord]) -> SimulationResults
+    {
+        let names: Vec<&str> = self.roster.iter().map(|contestant| contestant.name()).collect();
+        SimulationResults::from_records(&names, records)
+    }
+
+    // Play one game. Seats are named after their number so the roster can hold the
+    // same controller more than once
+    fn play(&self, seed: u64) -> Result<GameRecord, GameError>
+    {
+        let mut config = self.config.clone();
+        config.max_turns = config.max_turns.or(Some(SIMULATION_MAX_TURNS));
+
+        let mut lobby = Lobby::with_players((1..=self.roster.len()).map(|seat| format!("Seat {}", seat)))?;
+        lobby.set_config(config);
+        let mut game = lobby.start_with_seed(seed)?;
+
+        let ids: Vec<_> = game.players().iter().map(|player| player.id()).collect();
+        let mut controllers: Controllers = ids.iter().zip(&self.roster).enumerate()
+            .map(|(seat, (id, contestant))| (*id, contestant.controller(game_seed(seed, seat as u64 + 1))))
+            .collect();
+        let end = controller::run_game(&mut game, &mut controllers, &mut ())?;
+
+        let stats: Vec<_> = game.stats().iter().map(|(_, stats)| stats).collect();
+        Ok(GameRecord {
+            winner: end.winner().and_then(|winner| ids.iter().position(|id| *id == winner)),
+            turns: game.turn_number(),
+            turn_limit_reached: matches!(end, GameEnd::TurnLimitReached { .. }),
+            cards_drawn: stats.iter().map(|stats| stats.cards_drawn).sum(),
+            penalty_cards: stats.iter().map(|stats| stats.penalty_cards_received).sum()
+        })
+    }
+}
+
+// Spread `index` out into a seed that shares nothing with its neighbours (SplitMix64)
+pub fn game_seed(master: u64, index: u64) -> u64
+{
+    let mut z = master.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn runner(games: usize) -> SimulationRunner
+    {
+        let roster = [Bot::Random, Bot::Greedy, Bot::Aggressor].map(Contestant::bot).to_vec();
+        SimulationRunner::new(roster, GameConfig::default(), 11, games)
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_results()
+    {
+        let results = runner(300).run().unwrap();
+        assert_eq!(runner(300).run().unwrap(), results);
+        assert_eq!(results.games, 300);
+        assert_eq!(results.seats.iter().map(|seat| seat.wins).sum::<usize>() + results.no_winner, 300);
+        assert!(results.turn_limit_reached <= results.no_winner);
+        for seat in &results.seats {
+            assert_eq!(seat.win_rate, seat.wins as f64 / 300.0);
+        }
+
+        // Every game takes at least a turn and stops at the limit
+        let turns = &results.turn_percentiles;
+        assert!(1 <= turns.p10 && turns.p10 <= turns.p50 && turns.p50 <= turns.p90 && turns.p90 <= turns.p99);
+        assert!(turns.p99 <= turns.max && turns.max <= SIMULATION_MAX_TURNS);
+        assert!(turns.p10 as f64 <= results.mean_turns && results.mean_turns <= turns.max as f64);
+
+        // Cards get drawn, and with the Aggressor at the table some of them are penalties
+        assert!(results.mean_cards_drawn >= 1.0);
+        assert!(0.0 < results.mean_penalty_cards && results.mean_penalty_cards <= results.mean_cards_drawn);
+        assert!(0.0 < results.penalty_frequency && results.penalty_frequency <= 1.0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn running_in_parallel_gives_the_same_results()
+    {
+        let serial = runner(60).run().unwrap();
+        for threads in [0, 1, 2, 4] {
+            assert_eq!(runner(60).run_parallel(threads).unwrap(), serial, "with {} threads", threads);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use crate::events::GameEvent;
+use crate::game::PlayerId;
+use crate::history::{TurnAction, TurnRecord};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerStats
+{
+    pub cards_played: usize,
+    // Every card drawn from the deck after the deal, penalties included
+    pub cards_drawn: usize,
+    pub times_skipped: usize,
+    // Cards drawn as a penalty, and cards other players had to draw from Draw Twos
+    // and Draw Fours this player played
+    pub penalty_cards_received: usize,
+    pub penalty_cards_dealt: usize,
+    pub wilds_played: usize,
+    // The most turns in a row this player played a card on
+    pub longest_play_streak: usize,
+    play_streak: usize
+}
+
+// Statistics for everyone who has played in a game, including players who have
+// since left. Players are keyed by id so seat changes don't affect them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameStats
+{
+    players: BTreeMap<PlayerId, PlayerStats>
+}
+
+impl GameStats
+{
+    pub fn player(&self, id: PlayerId) -> Option<&PlayerStats>
+    {
+        self.players.get(&id)
+    }
+
+    // Every player in the order they joined the game
+    pub fn iter(&self) -> impl Iterator<Item = (PlayerId, &PlayerStats)>
+    {
+        self.players.iter().map(|(id, stats)| (*id, stats))
+    }
+
+    pub(crate) fn player_mut(&mut self, id: PlayerId) -> &mut PlayerStats
+    {
+        self.players.entry(id).or_default()
+    }
+
+    // Update the counts that follow directly from an event
+    pub(crate) fn record(&mut self, event: &GameEvent)
+    {
+        match event {
+            GameEvent::CardDealt { player, .. } | GameEvent::PlayerJoined { player } => {
+                self.player_mut(*player);
+            },
+            GameEvent::CardPlayed { player, card } => {
+                let stats = self.player_mut(*player);
+                stats.cards_played += 1;
+                if card.is_wild() {
+                    stats.wilds_played += 1;
+                }
+            },
+            GameEvent::CardDrawn { player, .. } => self.player_mut(*player).cards_drawn += 1,
+            GameEvent::TurnSkipped { player } => self.player_mut(*player).times_skipped += 1,
+            _ => ()
+        }
+    }
+
+    pub(crate) fn record_turn(&mut self, record: &TurnRecord)
+    {
+        let stats = self.player_mut(record.player);
+        match record.action {
+            TurnAction::Played(_) | TurnAction::DrewAndPlayed { .. } => {
+                stats.play_streak += 1;
+                stats.longest_play_streak = stats.longest_play_streak.max(stats.play_streak);
+            },
+            _ => stats.play_streak = 0
+        }
+    }
+}
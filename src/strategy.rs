@@ -0,0 +1,135 @@
+use crate::game::{CardType, Color, Game, Move};
+use crate::view::PlayerView;
+
+// A simple hint for a player, built only from what they can see: their own hand, the
+// top card and how many cards everyone else holds.
+//
+// The heuristic, in order:
+// - Play rather than draw whenever something can be played
+// - Hold on to wildcards unless nothing else can be played
+// - When an opponent is down to one card, get rid of the highest scoring card
+// - Otherwise play into the color the hand holds the most of, so later turns have
+//   plenty to match, breaking ties by dumping the higher scoring card
+// - Never challenge a Draw Four since the offender's hand is hidden
+pub fn suggest_move(view: &PlayerView, moves: &[Move]) -> Option<Move>
+{
+    if moves.contains(&Move::PlayDrawn) {
+        return Some(Move::PlayDrawn);
+    }
+
+    let opponent_on_uno = view.opponents().any(|(_, player)| player.number_of_cards == 1);
+    // A Draw Four that could be challenged successfully is worth holding back
+    let risky_draw_four = view.table.config.draw_four_challenge && !view.can_legally_play_draw_four();
+
+    let best_play = moves.iter()
+        .filter_map(|candidate| match candidate {
+            Move::Play(index) => Some((*index, view.hand[*index])),
+            _ => None
+        })
+        .max_by_key(|(_, card)| {
+            let risky = risky_draw_four && card.card_type == CardType::DrawFourWildcard;
+            let held_back = card.is_wild() as u8 + risky as u8;
+            let color_count = match card.color {
+                Some(color) if !opponent_on_uno => view.color_count(color),
+                _ => 0
+            };
+            (std::cmp::Reverse(held_back), color_count, card.points())
+        })
+        .map(|(index, _)| Move::Play(index));
+
+    best_play
+        .or(moves.iter().copied().find(|candidate| matches!(candidate, Move::AcceptPenalty | Move::Draw)))
+}
+
+// The color the player holds the most of, for choosing a wildcard's color
+pub fn suggest_wild_color(view: &PlayerView) -> Color
+{
+    [Color::Red, Color::Green, Color::Blue, Color::Yellow].into_iter()
+        .max_by_key(|color| view.color_count(*color))
+        .unwrap_or(Color::Red)
+}
+
+impl Game
+{
+    // A suggestion for the current player, see `strategy::suggest_move`
+    pub fn suggest_move(&self) -> Option<Move>
+    {
+        let view = self.view_for(self.current_seat()).ok()?;
+        suggest_move(&view, &self.legal_moves())
+    }
+
+    pub fn suggest_wild_color(&self) -> Color
+    {
+        self.view_for(self.current_seat()).map_or(Color::Red, |view| suggest_wild_color(&view))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::scenario::{Scenario, ScenarioPlayer};
+
+    // Seat 0's turn with `top` on the discard pile, and each seat holding its hand
+    fn table(top: &str, hands: &[&[&str]]) -> Game
+    {
+        let players = hands.iter().enumerate()
+            .map(|(seat, hand)| ScenarioPlayer {
+                name: format!("P{}", seat),
+                bot: false,
+                hand: hand.iter().map(|card| card.to_string()).collect()
+            })
+            .collect();
+        let scenario = Scenario {
+            name: String::from("Hint"),
+            description: String::new(),
+            top_card: String::from(top),
+            active_color: None,
+            current_seat: 0,
+            counter_clockwise: false,
+            rules: Vec::new(),
+            goal: None,
+            players
+        };
+        scenario.start(1).unwrap()
+    }
+
+    #[test]
+    fn playing_beats_drawing()
+    {
+        let game = table("red 5", &[&["blue 2", "red 1", "green 3"], &["green 4", "green 6"]]);
+        assert_eq!(game.suggest_move(), Some(Move::Play(1)));
+
+        let game = table("red 5", &[&["blue 2", "green 3"], &["green 4", "green 6"]]);
+        assert_eq!(game.suggest_move(), Some(Move::Draw));
+    }
+
+    #[test]
+    fn wildcards_are_saved_for_when_nothing_else_plays()
+    {
+        let game = table("red 5", &[&["wild", "+4", "red 1", "green 3"], &["green 4", "green 6"]]);
+        assert_eq!(game.suggest_move(), Some(Move::Play(2)));
+
+        let game = table("red 5", &[&["+4", "wild", "blue 2"], &["green 4", "green 6"]]);
+        assert!(matches!(game.suggest_move(), Some(Move::Play(0 | 1))));
+    }
+
+    #[test]
+    fn play_into_the_color_the_hand_has_most_of()
+    {
+        let game = table("red 5", &[&["red 1", "blue 5", "blue 7", "blue 8"], &["green 4", "green 6"]]);
+        assert_eq!(game.suggest_move(), Some(Move::Play(1)));
+        assert_eq!(game.suggest_wild_color(), Color::Blue);
+    }
+
+    #[test]
+    fn points_are_dumped_when_an_opponent_is_down_to_one_card()
+    {
+        let hand: &[&str] = &["red 1", "red skip", "blue 5", "blue 7", "blue 8"];
+        let game = table("red 5", &[hand, &["green 4", "green 6"]]);
+        assert_eq!(game.suggest_move(), Some(Move::Play(2)));
+
+        let game = table("red 5", &[hand, &["green 4"]]);
+        assert_eq!(game.suggest_move(), Some(Move::Play(1)));
+    }
+}
@@ -0,0 +1,409 @@
+use std::fmt;
+
+use crate::config::GameConfig;
+use crate::error::GameError;
+use crate::simulation::{game_seed, Contestant, SimulationRunner};
+
+// Who sits where at each table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeatAssignment
+{
+    // Contestants keep the seats they were listed in
+    Fixed,
+    // The games at a table are split between every rotation of the seats, so nobody
+    // gains from always sitting in the same place
+    #[default]
+    Rotate
+}
+
+// A share of games won, with a 95% confidence interval (Wilson score)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WinRate
+{
+    pub rate: f64,
+    pub low: f64,
+    pub high: f64
+}
+
+impl WinRate
+{
+    pub fn new(wins: usize, games: usize) -> WinRate
+    {
+        if games == 0 {
+            return WinRate { rate: 0.0, low: 0.0, high: 1.0 };
+        }
+
+        const Z: f64 = 1.96;
+        let n = games as f64;
+        let rate = wins as f64 / n;
+        let denominator = 1.0 + Z * Z / n;
+        let centre = (rate + Z * Z / (2.0 * n)) / denominator;
+        let margin = Z * (rate * (1.0 - rate) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+        WinRate { rate, low: (centre - margin).max(0.0), high: (centre + margin).min(1.0) }
+    }
+}
+
+impl fmt::Display for WinRate
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{:.0}% ({:.0}-{:.0})", self.rate * 100.0, self.low * 100.0, self.high * 100.0)
+    }
+}
+
+// The games two contestants played against each other on their own
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pairing
+{
+    pub first: String,
+    pub second: String,
+    pub games: usize,
+    pub first_wins: usize,
+    pub second_wins: usize
+}
+
+// A contestant's record across every table they sat at
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Standing
+{
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub win_rate: WinRate
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentResults
+{
+    pub contestants: Vec<String>,
+    pub pairings: Vec<Pairing>,
+    // In the order the contestants were entered
+    pub standings: Vec<Standing>
+}
+
+impl TournamentResults
+{
+    // How often `row` beat `column` when the two played alone
+    pub fn win_rate(&self, row: &str, column: &str) -> Option<WinRate>
+    {
+        self.pairings.iter().find_map(|pairing| {
+            if pairing.first == row && pairing.second == column {
+                Some(WinRate::new(pairing.first_wins, pairing.games))
+            } else if pairing.first == column && pairing.second == row {
+                Some(WinRate::new(pairing.second_wins, pairing.games))
+            } else {
+                None
+            }
+        })
+    }
+
+    // One line per head to head result and per standing
+    pub fn to_csv(&self) -> String
+    {
+        let mut csv = String::from("contestant,opponent,games,wins,win_rate,low,high\n");
+        for row in &self.contestants {
+            for column in self.contestants.iter().filter(|column| *column != row) {
+                if let Some(pairing) = self.pairings.iter()
+                    .find(|pairing| (&pairing.first, &pairing.second) == (row, column) ||
+                        (&pairing.first, &pairing.second) == (column, row)) {
+                    let wins = if &pairing.first == row { pairing.first_wins } else { pairing.second_wins };
+                    let rate = WinRate::new(wins, pairing.games);
+                    csv += &format!("{},{},{},{},{:.4},{:.4},{:.4}\n", csv_field(row), csv_field(column),
+                        pairing.games, wins, rate.rate, rate.low, rate.high);
+                }
+            }
+        }
+        for standing in &self.standings {
+            csv += &format!("{},all,{},{},{:.4},{:.4},{:.4}\n", csv_field(&standing.name), standing.games,
+                standing.wins, standing.win_rate.rate, standing.win_rate.low, standing.win_rate.high);
+        }
+        csv
+    }
+}
+
+// A name as a CSV field, in quotes when it holds a comma, a quote or a line break
+fn csv_field(text: &str) -> String
+{
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+// A cross table of head to head win rates, each row's rate against each column,
+// followed by the overall standings
+impl fmt::Display for TournamentResults
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        const WIDTH: usize = 16;
+        write!(f, "{:<WIDTH$}", "")?;
+        for column in &self.contestants {
+            write!(f, " {:>WIDTH$}", column)?;
+        }
+        writeln!(f)?;
+
+        for row in &self.contestants {
+            write!(f, "{:<WIDTH$}", row)?;
+            for column in &self.contestants {
+                let cell = self.win_rate(row, column).map_or("-".to_string(), |rate| rate.to_string());
+                write!(f, " {:>WIDTH$}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{:<WIDTH$} {:>6} {:>6} {:>WIDTH$}", "Contestant", "Games", "Wins", "Win rate")?;
+        for standing in &self.standings {
+            writeln!(f, "{:<WIDTH$} {:>6} {:>6} {:>WIDTH$}", standing.name, standing.games, standing.wins,
+                standing.win_rate.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+// Plays every pair of contestants against each other, and optionally every group of
+// three or four too, to see how they compare. Seeded like `SimulationRunner`
+pub struct Tournament
+{
+    contestants: Vec<Contestant>,
+    config: GameConfig,
+    seed: u64,
+    games_per_table: usize,
+    seating: SeatAssignment,
+    table_sizes: Vec<usize>
+}
+
+impl Tournament
+{
+    // Head to head only, with the seats rotated
+    pub fn new(contestants: Vec<Contestant>, config: GameConfig, seed: u64, games_per_table: usize) -> Tournament
+    {
+        Tournament { contestants, config, seed, games_per_table, seating: SeatAssignment::Rotate, table_sizes: vec![2] }
+    }
+
+    pub fn with_seating(mut self, seating: SeatAssignment) -> Tournament
+    {
+        self.seating = seating;
+        self
+    }
+
+    // e.g. `&[2, 3, 4]` to add mixed tables of three and four
+    pub fn with_table_sizes(mut self, table_sizes: &[usize]) -> Tournament
+    {
+        self.table_sizes = table_sizes.to_vec();
+        self
+    }
+
+    pub fn run(&self) -> Result<TournamentResults, GameError>
+    {
+        let names: Vec<String> = self.contestants.iter().map(|contestant| contestant.name().to_owned()).collect();
+        let mut wins = vec![0; self.contestants.len()];
+        let mut games = vec![0; self.contestants.len()];
+        let mut pairings = Vec::new();
+
+        let mut table_number = 0;
+        for &size in &self.table_sizes {
+            for table in combinations(self.contestants.len(), size) {
+                let table_wins = self.play_table(&table, table_number)?;
+                table_number += 1;
+
+                for (&contestant, &won) in table.iter().zip(&table_wins) {
+                    wins[contestant] += won;
+                    games[contestant] += self.games_per_table;
+                }
+                if let [first, second] = table[..] {
+                    pairings.push(Pairing {
+                        first: names[first].clone(),
+                        second: names[second].clone(),
+                        games: self.games_per_table,
+                        first_wins: table_wins[0],
+                        second_wins: table_wins[1]
+                    });
+                }
+            }
+        }
+
+        let standings = names.iter().enumerate()
+            .map(|(index, name)| Standing {
+                name: name.clone(),
+                games: games[index],
+                wins: wins[index],
+                win_rate: WinRate::new(wins[index], games[index])
+            })
+            .collect();
+        Ok(TournamentResults { contestants: names, pairings, standings })
+    }
+
+    // Play the games at one table, returning how many each of its contestants won
+    fn play_table(&self, table: &[usize], table_number: u64) -> Result<Vec<usize>, GameError>
+    {
+        let rotations = match self.seating {
+            SeatAssignment::Fixed => 1,
+            SeatAssignment::Rotate => table.len()
+        };
+
+        let mut wins = vec![0; table.len()];
+        for (rotation, games) in split_games(self.games_per_table, rotations).into_iter().enumerate() {
+            let seated: Vec<usize> = (0..table.len()).map(|seat| (seat + rotation) % table.len()).collect();
+            let roster = seated.iter().map(|&member| self.contestants[table[member]].clone()).collect();
+
+            let seed = game_seed(self.seed, table_number * 16 + rotation as u64);
+            let results = SimulationRunner::new(roster, self.config.clone(), seed, games).run()?;
+            for (seat, member) in seated.iter().enumerate() {
+                wins[*member] += results.seats[seat].wins;
+            }
+        }
+        Ok(wins)
+    }
+}
+
+// `games` spread as evenly as possible over `parts`, with any left over going to the
+// first parts
+fn split_games(games: usize, parts: usize) -> Vec<usize>
+{
+    (0..parts).map(|part| games / parts + usize::from(part < games % parts)).collect()
+}
+
+// Every way of choosing `size` of `count` items, in order
+fn combinations(count: usize, size: usize) -> Vec<Vec<usize>>
+{
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    if size > count {
+        return Vec::new();
+    }
+
+    let mut all = Vec::new();
+    for first in 0..=count - size {
+        for rest in combinations(count - first - 1, size - 1) {
+            let mut table = vec![first];
+            table.extend(rest.into_iter().map(|index| index + first + 1));
+            all.push(table);
+        }
+    }
+    all
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::bots::Bot;
+
+    fn close(found: WinRate, rate: f64, low: f64, high: f64)
+    {
+        let differences = [found.rate - rate, found.low - low, found.high - high];
+        assert!(differences.iter().all(|difference| difference.abs() < 1e-4), "{:?}", found);
+    }
+
+    fn tournament(seed: u64) -> Tournament
+    {
+        let contestants = [Bot::Random, Bot::Greedy, Bot::Aggressor].map(Contestant::bot).to_vec();
+        Tournament::new(contestants, GameConfig::default(), seed, 5).with_table_sizes(&[2, 3])
+    }
+
+    #[test]
+    fn win_rates_come_with_a_wilson_interval()
+    {
+        close(WinRate::new(5, 10), 0.5, 0.2366, 0.7634);
+        close(WinRate::new(30, 100), 0.3, 0.2189, 0.3959);
+        // The interval never leaves 0 to 1
+        close(WinRate::new(0, 10), 0.0, 0.0, 0.2775);
+        close(WinRate::new(10, 10), 1.0, 0.7225, 1.0);
+        assert_eq!(WinRate::new(0, 0), WinRate { rate: 0.0, low: 0.0, high: 1.0 });
+        assert_eq!(WinRate::new(5, 10).to_string(), "50% (24-76)");
+    }
+
+    #[test]
+    fn every_table_is_chosen_once()
+    {
+        assert_eq!(combinations(4, 2), [[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]]);
+        assert_eq!(combinations(4, 3), [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]]);
+        assert_eq!(combinations(3, 3), [[0, 1, 2]]);
+        assert!(combinations(2, 3).is_empty());
+        assert_eq!(combinations(3, 0), [Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn games_are_split_between_the_rotations()
+    {
+        assert_eq!(split_games(7, 3), [3, 2, 2]);
+        assert_eq!(split_games(6, 3), [2, 2, 2]);
+        assert_eq!(split_games(2, 3), [1, 1, 0]);
+        assert_eq!(split_games(5, 1), [5]);
+
+        // Every game at the table is played, left over ones included
+        let dealt = Arc::new(AtomicUsize::new(0));
+        let counted = {
+            let dealt = dealt.clone();
+            Contestant::new("Counted", move |seed| {
+                dealt.fetch_add(1, Ordering::Relaxed);
+                Bot::Greedy.seeded_controller(seed)
+            })
+        };
+        let contestants = vec![counted, Contestant::bot(Bot::Random), Contestant::bot(Bot::Aggressor)];
+        for (seating, games) in [(SeatAssignment::Rotate, 7), (SeatAssignment::Fixed, 7), (SeatAssignment::Rotate, 2)] {
+            dealt.store(0, Ordering::Relaxed);
+            let tournament = Tournament::new(contestants.clone(), GameConfig::default(), 3, games)
+                .with_seating(seating);
+            let wins = tournament.play_table(&[0, 1, 2], 0).unwrap();
+            assert_eq!(dealt.load(Ordering::Relaxed), games, "{:?}", seating);
+            assert!(wins.iter().sum::<usize>() <= games);
+        }
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_results()
+    {
+        let results = tournament(4).run().unwrap();
+        assert_eq!(tournament(4).run().unwrap(), results);
+
+        // Three pairs, then one table of all three
+        assert_eq!(results.pairings.len(), 3);
+        assert!(results.pairings.iter()
+            .all(|pairing| pairing.games == 5 && pairing.first_wins + pairing.second_wins <= 5));
+        let games: Vec<usize> = results.standings.iter().map(|standing| standing.games).collect();
+        assert_eq!(games, [15, 15, 15]);
+        for standing in &results.standings {
+            assert_eq!(standing.win_rate, WinRate::new(standing.wins, standing.games));
+        }
+    }
+
+    #[test]
+    fn results_as_csv()
+    {
+        let results = TournamentResults {
+            contestants: vec![String::from("Greedy"), String::from("Smith, \"Bot\"")],
+            pairings: vec![Pairing {
+                first: String::from("Greedy"),
+                second: String::from("Smith, \"Bot\""),
+                games: 10,
+                first_wins: 5,
+                second_wins: 4
+            }],
+            standings: vec![
+                Standing { name: String::from("Greedy"), games: 10, wins: 5, win_rate: WinRate::new(5, 10) },
+                Standing { name: String::from("Smith, \"Bot\""), games: 10, wins: 4, win_rate: WinRate::new(4, 10) }
+            ]
+        };
+        assert_eq!(results.to_csv(), [
+            "contestant,opponent,games,wins,win_rate,low,high",
+            "Greedy,\"Smith, \"\"Bot\"\"\",10,5,0.5000,0.2366,0.7634",
+            "\"Smith, \"\"Bot\"\"\",Greedy,10,4,0.4000,0.1682,0.6873",
+            "Greedy,all,10,5,0.5000,0.2366,0.7634",
+            "\"Smith, \"\"Bot\"\"\",all,10,4,0.4000,0.1682,0.6873",
+            ""
+        ].join("\n"));
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::clock::UtcTime;
+use crate::controller::Narrator;
+use crate::events::{GameEvent, RecordedEvent};
+use crate::game::{Direction, Game, GameEnd, PlayerId};
+
+// A log of everything that happens in a game, a line at a time with the time it happened,
+// for checking afterwards what really went on. It's written from the game's events, so
+// it can't disagree with the game, and it holds every card anyone was dealt or drew no
+// matter who was meant to see it. Each line is flushed as it's written, so a crash loses
+// nothing before it
+#[derive(Debug)]
+pub struct Transcript
+{
+    file: File,
+    // Players who have left are no longer in the game, so everyone's name is kept
+    names: HashMap<PlayerId, String>,
+    // The first write that failed. Writing stops there
+    error: Option<io::Error>
+}
+
+impl Transcript
+{
+    // Added on to the end of the file, which is created if it isn't there
+    pub fn open(path: &Path) -> io::Result<Transcript>
+    {
+        let file = OpenOptions::new().append(true).create(true).open(path)?;
+        Ok(Transcript { file, names: HashMap::new(), error: None })
+    }
+
+    pub fn error(&self) -> Option<&io::Error>
+    {
+        self.error.as_ref()
+    }
+
+    // A line that isn't part of any turn, like the start of a game
+    pub fn note(&mut self, text: &str)
+    {
+        self.write(None, text);
+    }
+
+    // The result and what everyone had left
+    pub fn game_over(&mut self, game: &Game, end: GameEnd)
+    {
+        let turn = Some(game.turn_number());
+        let result = match end {
+            GameEnd::Won(player) => format!("{} won", self.name(game, player)),
+            GameEnd::LastPlayerStanding(player) => format!("{} won as the last player left", self.name(game, player)),
+            GameEnd::TurnLimitReached { winner: Some(winner) } =>
+                format!("The turn limit was reached, and {} won with the fewest points", self.name(game, winner)),
+            GameEnd::TurnLimitReached { winner: None } => String::from("The turn limit was reached, and nobody won")
+        };
+        self.write(turn, &result);
+        for player in game.players().iter().filter(|player| player.number_of_cards() > 0) {
+            let cards: Vec<String> = player.cards().iter().map(|card| card.to_string()).collect();
+            self.write(turn, &format!("{} was left with {}", player.name(), cards.join(", ")));
+        }
+    }
+
+    fn name(&mut self, game: &Game, id: PlayerId) -> String
+    {
+        if let Some(player) = game.player_by_id(id) {
+            self.names.insert(id, player.name().clone());
+        }
+        self.names.get(&id).cloned().unwrap_or_else(|| String::from("Unknown player"))
+    }
+
+    fn describe(&mut self, game: &Game, event: &GameEvent) -> String
+    {
+        match event {
+            GameEvent::CardDealt { player, card } => format!("{} was dealt a {}", self.name(game, *player), card),
+            GameEvent::DrewForDeal { player, card } => format!("{} drew a {} for the deal", self.name(game, *player), card),
+            GameEvent::GameStarted { players, top_card } => {
+                let names: Vec<String> = players.iter().map(|player| self.name(game, *player)).collect();
+                format!("The game started with {} in that order. The top card is a {}", names.join(", "), top_card)
+            },
+            GameEvent::TurnStarted { player } => format!("It's {}'s turn", self.name(game, *player)),
+            GameEvent::CardPlayed { player, card } => format!("{} played a {}", self.name(game, *player), card),
+            GameEvent::CardDrawn { player, card } => format!("{} drew a {}", self.name(game, *player), card),
+            GameEvent::WildColorChosen { player, color } => format!("{} chose {}", self.name(game, *player), color),
+            GameEvent::TurnSkipped { player } => format!("{} was skipped", self.name(game, *player)),
+            GameEvent::JumpedIn { player, interrupted } =>
+                format!("{} jumped in on {}'s turn", self.name(game, *player), self.name(game, *interrupted)),
+            GameEvent::HandsSwapped { player, target } =>
+                format!("{} swapped hands with {}", self.name(game, *player), self.name(game, *target)),
+            GameEvent::HandsRotated => String::from("Everyone passed their hand along"),
+            GameEvent::PlayerJoined { player } => format!("{} joined the game", self.name(game, *player)),
+            GameEvent::PlayerLeft { player } => format!("{} left the game", self.name(game, *player)),
+            GameEvent::UnoCalled { player } => format!("{} called Uno", self.name(game, *player)),
+            GameEvent::UnoCaught { accuser, target, caught: true } =>
+                format!("{} caught {} without calling Uno", self.name(game, *accuser), self.name(game, *target)),
+            GameEvent::UnoCaught { accuser, target, caught: false } =>
+                format!("{} tried to catch {}, who had called Uno", self.name(game, *accuser), self.name(game, *target)),
+            GameEvent::DrawFourChallenged { challenger, offender, offender_guilty } => {
+                let verdict = if *offender_guilty { "could have played another card" } else { "played it fairly" };
+                format!("{} challenged {}'s Draw Four, and {} {}", self.name(game, *challenger),
+                    self.name(game, *offender), self.name(game, *offender), verdict)
+            },
+            GameEvent::DirectionReversed { direction: Direction::Clockwise } =>
+                String::from("Play reversed and goes clockwise"),
+            GameEvent::DirectionReversed { direction: Direction::CounterClockwise } =>
+                String::from("Play reversed and goes counter-clockwise"),
+            GameEvent::TurnLimitReached => String::from("The turn limit was reached")
+        }
+    }
+
+    // e.g. "2026-10-15 14:03:05  turn 12  Bob played a Red 5". Cards are dealt before
+    // the first turn
+    fn write(&mut self, turn: Option<u32>, text: &str)
+    {
+        if self.error.is_some() {
+            return;
+        }
+        let turn = match turn {
+            Some(0) => String::from("deal"),
+            Some(turn) => format!("turn {}", turn),
+            None => String::new()
+        };
+        let line = format!("{}  {:<8} {}\n", UtcTime::now(), turn, text);
+        if let Err(error) = self.file.write_all(line.as_bytes()).and_then(|()| self.file.flush()) {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl Narrator for Transcript
+{
+    fn event(&mut self, game: &Game, event: &RecordedEvent)
+    {
+        let text = self.describe(game, &event.event);
+        self.write(Some(event.turn), &text);
+    }
+}
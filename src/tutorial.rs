@@ -0,0 +1,217 @@
+use crate::cli::{normalize_input, read_line, reject, CliNarrator, CliOptions};
+use crate::config::StartingPlayer;
+use crate::console::Console;
+use crate::controller::{self, Controllers, PlayerController};
+use crate::error::GameError;
+use crate::game::{Card, Color, Lobby, Move};
+use crate::messages::{fill, Messages};
+use crate::strategy;
+use crate::view::{BotObservation, PlayerView};
+
+// A lesson's words, looked up in whichever language is being played in
+type Lesson = fn(&Messages) -> &'static str;
+
+// Something the player is taught on one of their turns, and the one thing they're let
+// do about it
+#[derive(Clone, Copy)]
+pub enum Step
+{
+    // Play the card at this place in the hand, counting from 1 as the hand is shown
+    Play { card: usize, lesson: Lesson },
+    // Choose this color for the wildcard that was just played
+    ChooseColor { color: Color, lesson: Lesson },
+    CallUno { lesson: Lesson }
+}
+
+// A rigged game against one bot that stops to explain the rules as they come up. The
+// cards are written the way players type them, like "red 8", and the bot plays the
+// cards in `tutor_plays` in turn, so every game goes the same way. Adding a lesson is a
+// matter of writing a new one of these
+pub struct Tutorial
+{
+    pub top_card: &'static str,
+    // Both hands are dealt the same number of cards
+    pub hand: &'static [&'static str],
+    pub tutor_hand: &'static [&'static str],
+    // Places in the tutor's hand at the time, counting from 1
+    pub tutor_plays: &'static [usize],
+    pub steps: &'static [Step]
+}
+
+impl Tutorial
+{
+    // Matching by color and by number, a Skip, a wildcard and its color, a Draw 2, then
+    // calling Uno and going out
+    pub const BASICS: Tutorial = Tutorial {
+        top_card: "red 5",
+        hand: &["red 8", "green 8", "green skip", "yellow draw 2", "wildcard", "yellow 3"],
+        tutor_hand: &["red 8", "green 4", "yellow 6", "blue 1", "blue 7", "red 9"],
+        tutor_plays: &[1, 1, 1],
+        steps: &[
+            Step::Play { card: 1, lesson: |text| text.lesson_color },
+            Step::Play { card: 1, lesson: |text| text.lesson_number },
+            Step::Play { card: 1, lesson: |text| text.lesson_skip },
+            Step::Play { card: 2, lesson: |text| text.lesson_wild },
+            Step::ChooseColor { color: Color::Yellow, lesson: |text| text.lesson_wild_color },
+            Step::Play { card: 1, lesson: |text| text.lesson_draw_two },
+            Step::CallUno { lesson: |text| text.lesson_uno },
+            Step::Play { card: 1, lesson: |text| text.lesson_last_card }
+        ]
+    };
+
+    // Top first, the way `Lobby::start_with_deck` deals it
+    fn draw_pile(&self) -> Vec<Card>
+    {
+        [self.top_card].iter().chain(self.hand).chain(self.tutor_hand)
+            .map(|card| card.parse().expect("Lesson cards are written correctly"))
+            .collect()
+    }
+
+    // Play the tutorial at the console as `name`. Everything is shown, since only the
+    // player is at the keyboard
+    pub fn run<C: Console + Clone + 'static>(&self, console: &mut C, options: &CliOptions, name: &str)
+        -> Result<(), GameError>
+    {
+        let messages = options.renderer.messages;
+        let mut lobby = Lobby::new();
+        let mut config = lobby.config().clone();
+        config.hand_size = Some(self.hand.len());
+        config.starting_player = StartingPlayer::Seat(0);
+        lobby.set_config(config);
+        lobby.add_player(name)?;
+        // Someone called Tutor gets a tutor with a name from the name pack
+        let tutor = Box::new(Tutor { plays: self.tutor_plays, played: 0 });
+        if !lobby.add_bot(messages.tutorial_tutor, tutor.clone()) && !lobby.add_bot("", tutor) {
+            return Err(GameError::LobbyFull { max_players: lobby.max_players() });
+        }
+
+        let mut controllers: Controllers = lobby.bots().clone();
+        let options = CliOptions { privacy: false, ..options.clone() };
+        let mut narrator = CliNarrator::new(console.clone(), controllers.keys().copied().collect(), options.clone());
+        let mut game = lobby.start_with_deck(rand::random(), self.draw_pile())?;
+        let student = Student { console: console.clone(), options, steps: self.steps, taken: 0 };
+        controllers.insert(game.players()[0].id(), Box::new(student));
+
+        writeln!(console, "\n{}\n", fill(messages.tutorial_intro, &[game.players()[1].name()]));
+        controller::run_game(&mut game, &mut controllers, &mut narrator)?;
+        writeln!(console, "{}\n", messages.tutorial_done);
+        Ok(())
+    }
+}
+
+// The player's seat in a tutorial, taking them through its steps one at a time
+#[derive(Clone)]
+struct Student<C: Console>
+{
+    console: C,
+    options: CliOptions,
+    steps: &'static [Step],
+    taken: usize
+}
+
+impl<C: Console> Student<C>
+{
+    fn next_step(&mut self) -> Option<Step>
+    {
+        let step = self.steps.get(self.taken).copied();
+        self.taken += 1;
+        step
+    }
+
+    // Keep asking with `prompt` until the player types something `accepts` takes
+    fn insist(&mut self, prompt: &str, accepts: impl Fn(&str) -> bool, correction: &str)
+    {
+        loop {
+            self.console.write(prompt);
+            if accepts(&normalize_input(&read_line(&mut self.console))) {
+                break;
+            }
+            reject(&mut self.console, format!("{}\n", correction));
+        }
+    }
+}
+
+impl<C: Console + Clone + 'static> PlayerController for Student<C>
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let view = &observation.view;
+        let Some(Step::Play { card, lesson }) = self.next_step() else {
+            return observation.legal_moves[0];
+        };
+        let renderer = self.options.renderer;
+        let messages = renderer.messages;
+        let top_card = renderer.top_card(view.table.top_card, view.table.active_color);
+        writeln!(self.console, "{}\n\n{}\n{}\n{}", fill(messages.top_card, &[&top_card]),
+            fill(messages.cards_of, &[&view.table.players[view.seat].name]),
+            renderer.marked_hand(&view.hand, &[card - 1], &[]), lesson(messages));
+
+        let correction = fill(messages.tutorial_wrong_card, &[&card, &renderer.card(view.hand[card - 1])]);
+        self.insist(&fill(messages.tutorial_try_card, &[&card]), |input| input == card.to_string(), &correction);
+        Move::Play(card - 1)
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        let Some(Step::ChooseColor { color, lesson }) = self.next_step() else {
+            return strategy::suggest_wild_color(view);
+        };
+        let messages = self.options.renderer.messages;
+        const COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+        writeln!(self.console, "{}\n{}", lesson(messages), messages.color_prompt);
+        for (index, color) in COLORS.iter().enumerate() {
+            writeln!(self.console, "{} - {}", index + 1, messages.color(*color));
+        }
+
+        let number = COLORS.iter().position(|each| *each == color).expect("Every color is listed") + 1;
+        let name = self.options.renderer.color(color);
+        let accepts = |input: &str| input == number.to_string() || messages.parse_color(input) == Some(color);
+        self.insist(&fill(messages.tutorial_try_color, &[&name]), accepts,
+            &fill(messages.tutorial_wrong_color, &[&name]));
+        color
+    }
+
+    fn wants_to_call_uno(&mut self, _view: &PlayerView) -> bool
+    {
+        let Some(Step::CallUno { lesson }) = self.next_step() else {
+            return true;
+        };
+        let messages = self.options.renderer.messages;
+        writeln!(self.console, "{}", lesson(messages));
+        self.insist(messages.tutorial_try_uno, |input| input == "uno", messages.tutorial_wrong_uno);
+        true
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
+
+// The bot in a tutorial, playing the cards it's told to
+#[derive(Debug, Clone)]
+struct Tutor
+{
+    plays: &'static [usize],
+    played: usize
+}
+
+impl PlayerController for Tutor
+{
+    fn choose_move(&mut self, observation: &BotObservation) -> Move
+    {
+        let chosen = self.plays.get(self.played).map(|card| Move::Play(card - 1));
+        self.played += 1;
+        chosen.filter(|chosen| observation.legal_moves.contains(chosen)).unwrap_or(observation.legal_moves[0])
+    }
+
+    fn choose_color(&mut self, view: &PlayerView) -> Color
+    {
+        strategy::suggest_wild_color(view)
+    }
+
+    fn clone_box(&self) -> Box<dyn PlayerController>
+    {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,184 @@
+use crate::config::GameConfig;
+use crate::game::{Card, Color, Direction, Move, PendingAction, PlayerId};
+use crate::history::{ActionRecord, TurnRecord};
+
+// Redacted snapshots of a game that are safe to show to a particular player or to
+// an onlooker. Unlike `Display for Player` these never include other players' hands
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PublicPlayer
+{
+    pub id: PlayerId,
+    pub name: String,
+    pub number_of_cards: usize,
+    pub called_uno: bool
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpectatorView
+{
+    // In seating order
+    pub players: Vec<PublicPlayer>,
+    pub top_card: Card,
+    // The last few cards played, oldest first
+    pub recently_played: Vec<Card>,
+    // Every card that has been on top of the pile, oldest first
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub discarded: Vec<Card>,
+    // None while a wildcard on top is waiting for a color
+    pub active_color: Option<Color>,
+    pub direction: Direction,
+    pub current_seat: usize,
+    pub pending_action: Option<PendingAction>,
+    // The Draw Four the current player may challenge: who played it, and the color
+    // that was active when they did
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub challengeable_draw_four: Option<(PlayerId, Option<Color>)>,
+    pub turn_number: u32,
+    // The last few turns, oldest first
+    pub recent_turns: Vec<TurnRecord>,
+    pub config: GameConfig,
+    pub winner: Option<PlayerId>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerView
+{
+    pub seat: usize,
+    pub id: PlayerId,
+    pub hand: Vec<Card>,
+    // Index in the hand of a playable card just drawn, waiting to be played or kept
+    pub drawn_card: Option<usize>,
+    // Cards drawn since the start of the player's previous turn that they still hold,
+    // so they can be pointed out in the hand
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub new_cards: Vec<Card>,
+    pub table: SpectatorView
+}
+
+impl SpectatorView
+{
+    // The seat that plays after the current one, unless something skips them
+    pub fn next_seat(&self) -> usize
+    {
+        let length = self.players.len();
+        match self.direction {
+            Direction::Clockwise => (self.current_seat + 1) % length,
+            Direction::CounterClockwise => (self.current_seat + length - 1) % length
+        }
+    }
+
+    // Every player once, starting with the current player and following the direction
+    // of play, like `Game::turn_order`
+    pub fn turn_order(&self) -> impl Iterator<Item = &PublicPlayer>
+    {
+        let length = self.players.len();
+        let step = match self.direction {
+            Direction::Clockwise => 1,
+            Direction::CounterClockwise => length - 1
+        };
+        (0..length).map(move |offset| &self.players[(self.current_seat + offset * step) % length])
+    }
+}
+
+impl PlayerView
+{
+    // How many cards of `color` are in the hand, not counting wildcards
+    pub fn color_count(&self, color: Color) -> usize
+    {
+        self.hand.iter().filter(|card| card.color == Some(color)).count()
+    }
+
+    // Whether a Draw Four could be played without breaking the rule that the player
+    // must not hold a card of the active color
+    pub fn can_legally_play_draw_four(&self) -> bool
+    {
+        !self.hand.iter().any(|card| card.color.is_some() && card.color == self.table.active_color)
+    }
+
+    // The seats of the other players, in seating order
+    pub fn opponents(&self) -> impl Iterator<Item = (usize, &PublicPlayer)>
+    {
+        self.table.players.iter().enumerate().filter(move |(seat, _)| *seat != self.seat)
+    }
+
+    // Where the cards in `new_cards` are in the hand. Identical cards can't be told
+    // apart, so any copy stands for a new one, and the marks follow the cards wherever
+    // they're moved to
+    pub fn new_card_indices(&self) -> Vec<usize>
+    {
+        let mut unmatched = self.new_cards.clone();
+        (0..self.hand.len())
+            .filter(|&index| match unmatched.iter().position(|card| *card == self.hand[index]) {
+                Some(found) => {
+                    unmatched.swap_remove(found);
+                    true
+                },
+                None => false
+            })
+            .collect()
+    }
+}
+
+// Everything a bot may know when choosing a move, in one place. It's built from the
+// player's own view and public information only, so it is safe to send to a bot
+// running somewhere else
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BotObservation
+{
+    pub view: PlayerView,
+    pub legal_moves: Vec<Move>,
+    // Cards of the standard deck the player hasn't seen, with how many copies of each
+    pub unseen: Vec<(Card, usize)>,
+    // The last few plays and draws, oldest first. Drawn cards are never included
+    pub recent_actions: Vec<ActionRecord>,
+    // By seat, the colors each player seems to be out of, see `OpponentProfile`
+    pub likely_void_colors: Vec<Vec<Color>>
+}
+
+impl BotObservation
+{
+    pub fn hand(&self) -> &[Card]
+    {
+        &self.view.hand
+    }
+
+    pub fn top_card(&self) -> Card
+    {
+        self.view.table.top_card
+    }
+
+    pub fn active_color(&self) -> Option<Color>
+    {
+        self.view.table.active_color
+    }
+
+    pub fn direction(&self) -> Direction
+    {
+        self.view.table.direction
+    }
+
+    // Each opponent's seat along with their card count and whether they called Uno
+    pub fn opponents(&self) -> impl Iterator<Item = (usize, &PublicPlayer)>
+    {
+        self.view.opponents()
+    }
+
+    // Cards the player must draw unless they stack, or 0
+    pub fn pending_penalty(&self) -> u8
+    {
+        match self.view.table.pending_action {
+            Some(PendingAction::DrawPenalty(cards)) => cards,
+            _ => 0
+        }
+    }
+
+    pub fn unseen_count(&self, card: Card) -> usize
+    {
+        self.unseen.iter().find(|(unseen, _)| *unseen == card).map_or(0, |(_, count)| *count)
+    }
+}
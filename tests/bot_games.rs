@@ -0,0 +1,69 @@
+// Whole games run by `controller::run_game` with a bot in every seat
+
+use uno::bots::Bot;
+use uno::controller::{self, Controllers, FirstMoveBot, Narrator, PlayerController};
+use uno::events::RecordedEvent;
+use uno::game::{Game, Lobby};
+
+// Keeps every event it's told about
+#[derive(Default)]
+struct Listener
+{
+    events: Vec<RecordedEvent>,
+    turns: usize
+}
+
+impl Narrator for Listener
+{
+    fn turn_starting(&mut self, _game: &Game, _seat: usize)
+    {
+        self.turns += 1;
+    }
+
+    fn event(&mut self, _game: &Game, event: &RecordedEvent)
+    {
+        self.events.push(event.clone());
+    }
+}
+
+fn play(names: &[&str], bots: Vec<Box<dyn PlayerController>>, seed: u64) -> (Game, Listener)
+{
+    let mut game = Lobby::with_players(names.iter().copied()).unwrap().start_with_seed(seed).unwrap();
+    let mut controllers: Controllers = game.players().iter().map(|player| player.id()).zip(bots).collect();
+    let mut listener = Listener::default();
+    let end = controller::run_game(&mut game, &mut controllers, &mut listener).unwrap();
+    assert_eq!(game.end(), Some(end));
+    (game, listener)
+}
+
+#[test]
+fn bots_play_a_game_to_the_end()
+{
+    let bots = [Bot::Random, Bot::Greedy, Bot::Hoarder, Bot::Aggressor, Bot::ColorFixer];
+    for seed in 0..20 {
+        let controllers = bots.iter().enumerate().map(|(seat, bot)| bot.seeded_controller(seed + seat as u64)).collect();
+        let (game, listener) = play(&["Ann", "Ben", "Cat", "Dan", "Eve"], controllers, seed);
+
+        assert!(game.is_over());
+        assert_eq!(game.check_invariants(), Ok(()));
+        // Everything the game recorded was told as it happened
+        assert_eq!(listener.events, game.events());
+        assert!(listener.turns > 0);
+    }
+}
+
+#[test]
+fn the_winner_has_no_cards_left()
+{
+    let (game, _) = play(&["Ann", "Ben"], vec![Box::new(FirstMoveBot), Box::new(FirstMoveBot)], 8);
+    let winner = game.winner().expect("Without a turn limit a game only ends when someone wins");
+    assert_eq!(winner.number_of_cards(), 0);
+}
+
+#[test]
+fn a_monte_carlo_bot_finishes_a_game()
+{
+    let controllers = vec![Bot::MonteCarlo.seeded_controller(1), Bot::Greedy.seeded_controller(2)];
+    let (game, _) = play(&["Ann", "Ben"], controllers, 3);
+    assert!(game.is_over());
+}
@@ -0,0 +1,84 @@
+// The `uno` binary playing seeded games from --script files, the way a person would
+// at the keyboard
+#![cfg(feature = "serde")]
+
+use std::fs;
+use std::process::{Command, Output};
+
+// The moves that take the game dealt by seed 5 to alice winning on turn 20
+const ALICE_WINS: [&str; 13] = ["3", "1", "3", "5", "3", "4", "4", "1", "2", "1", "1", "1", "1"];
+
+// Run a game between alice and bob dealt from `seed`, with `script` answering the
+// prompts. Any config file or saved game is kept out of it
+fn play(name: &str, seed: u64, script: &[&str]) -> Output
+{
+    let dir = std::env::temp_dir().join(format!("uno-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("script.txt");
+    fs::write(&path, script.join("\n")).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_uno"))
+        .args(["--player", "alice", "--player", "bob", "--seed", &seed.to_string(), "--script"])
+        .arg(&path)
+        .env("XDG_CONFIG_HOME", &dir)
+        .env("XDG_DATA_HOME", &dir)
+        .env("HOME", &dir)
+        .env("LANG", "C")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    output
+}
+
+fn lines(output: &Output) -> Vec<String>
+{
+    String::from_utf8_lossy(&output.stdout).lines().map(str::to_owned).collect()
+}
+
+#[test]
+fn a_scripted_game_is_played_to_the_end()
+{
+    let output = play("to-the-end", 5, &ALICE_WINS);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let lines = lines(&output);
+    for expected in ["alice has played their last card! They are the winner!",
+        "alice scores 50 points for the cards left in everyone else's hands", "20 turns in 0:00"] {
+        assert!(lines.iter().any(|line| line == expected), "'{}' wasn't written", expected);
+    }
+}
+
+#[test]
+fn turned_down_input_is_explained()
+{
+    let mut script = vec!["!9", "!drwa", "!red 3"];
+    script.extend(ALICE_WINS);
+    let output = play("turned-down", 5, &script);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let lines = lines(&output);
+    for expected in ["Please enter a card index in the range 1 - 7, or type 'draw' to draw", "Did you mean 'draw'?",
+        "alice has played their last card! They are the winner!"] {
+        assert!(lines.iter().any(|line| line == expected), "'{}' wasn't written", expected);
+    }
+}
+
+#[test]
+fn a_script_that_runs_out_fails()
+{
+    let output = play("runs-out", 5, &["3"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("the script ran out after line 1 before the game was over"));
+}
+
+#[test]
+fn a_line_that_should_have_been_turned_down_fails()
+{
+    let output = play("accepted", 5, &["!3"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("line 1 of the script should have been turned down, but it was accepted"));
+}
@@ -0,0 +1,106 @@
+// The engine as another crate sees it, through nothing but the public API
+
+use std::collections::HashMap;
+
+use uno::error::{GameError, NameError};
+use uno::game::{Card, CardType, Color, Digit, Game, GameEnd, InfiniteDeck, Lobby, Move, PendingAction};
+
+// Make the first legal move, playing wildcards as red
+fn first_move(game: &mut Game) -> Result<(), GameError>
+{
+    if game.pending_action() == Some(PendingAction::AwaitingWildColor) {
+        return game.set_wildcard_color(Color::Red).map(drop);
+    }
+    match game.legal_moves()[0] {
+        Move::Play(index) if game.player().cards()[index].is_wild() => game.play_wild(index, Color::Red).map(drop),
+        Move::Play(index) => game.play(index).map(drop),
+        Move::Draw => game.draw_one().map(drop),
+        Move::PlayDrawn => game.play_drawn().map(drop),
+        Move::KeepDrawn => game.keep_drawn().map(drop),
+        Move::AcceptPenalty => game.accept_penalty().map(drop),
+        Move::ChallengeDrawFour => game.challenge_draw_four().map(drop)
+    }
+}
+
+#[test]
+fn a_game_from_the_lobby_to_a_winner()
+{
+    for seed in 0..20 {
+        let mut lobby = Lobby::new();
+        lobby.add_player("Ann").unwrap();
+        lobby.add_player("Ben").unwrap();
+        lobby.add_player("Cat").unwrap();
+        let ids: Vec<_> = lobby.seating().iter().map(|player| player.id()).collect();
+
+        let mut game = lobby.start_with_seed(seed).unwrap();
+        for player in game.players() {
+            // A Draw Two or Draw Four turned over to start adds to the first player's hand
+            assert!(player.cards().len() >= 7);
+            assert_eq!(player.cards().len(), player.number_of_cards());
+            assert_eq!(player.hand_points(), player.cards().iter().map(Card::points).sum::<u32>());
+        }
+        while !game.is_over() {
+            first_move(&mut game).unwrap();
+        }
+
+        let winner = game.winner().expect("Without a turn limit a game only ends when someone wins");
+        assert_eq!(game.end(), Some(GameEnd::Won(winner.id())));
+        assert!(winner.cards().is_empty());
+        assert!(ids.contains(&winner.id()));
+        assert_eq!(game.play(0).unwrap_err(), GameError::GameOver);
+    }
+}
+
+#[test]
+fn the_same_seed_deals_the_same_game()
+{
+    let deal = |seed| {
+        let game = Lobby::with_players(["Ann", "Ben"]).unwrap().start_with_seed(seed).unwrap();
+        let hands: Vec<Vec<Card>> = game.players().iter().map(|player| player.cards().to_vec()).collect();
+        (hands, game.top_card(), game.current_seat())
+    };
+    assert_eq!(deal(3), deal(3));
+    assert_ne!(deal(3), deal(4));
+}
+
+#[test]
+fn a_lobby_turns_down_games_it_cant_start()
+{
+    let mut lobby = Lobby::with_max_players(2);
+    lobby.add_player("Ann").unwrap();
+    assert_eq!(lobby.add_player("ann"), Err(GameError::InvalidName(NameError::Taken(String::from("ann")))));
+    assert_eq!(lobby.add_player("  "), Err(GameError::InvalidName(NameError::Empty)));
+    assert_eq!(lobby.clone().start().err(), Some(GameError::NotEnoughPlayers));
+
+    lobby.add_player("Ben").unwrap();
+    assert_eq!(lobby.add_player("Cat"), Err(GameError::LobbyFull { max_players: 2 }));
+    assert!(lobby.start().is_ok());
+}
+
+#[test]
+fn an_infinite_deck_deals_with_a_real_decks_odds()
+{
+    let draws = 108_000;
+    let mut deck = InfiniteDeck::seeded(1);
+    let mut copies: HashMap<Card, usize> = HashMap::new();
+    for _ in 0..draws {
+        *copies.entry(deck.draw()).or_default() += 1;
+    }
+
+    // Every card a standard deck has turns up about as often as it would there
+    let in_a_deck = |card: &Card| match card.card_type {
+        CardType::Number(Digit::ZERO) => 1,
+        CardType::Wildcard | CardType::DrawFourWildcard => 4,
+        _ => 2
+    };
+    assert_eq!(copies.len(), 54);
+    for (card, copies) in &copies {
+        let expected = in_a_deck(card) * draws / 108;
+        assert!(copies.abs_diff(expected) < expected / 5, "{} turned up {} times, not about {}", card, copies,
+            expected);
+    }
+
+    let mut again = InfiniteDeck::seeded(1);
+    let mut deck = InfiniteDeck::seeded(1);
+    assert!((0..100).all(|_| deck.draw() == again.draw()));
+}